@@ -0,0 +1,97 @@
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use godot_properties_parser::parsers::parser_value::Value;
+use godot_properties_parser::{
+    SectionIter, parse_project_file, parse_property_file, parse_scene_file,
+};
+use std::hint::black_box;
+
+const SCENES: &[(&str, &str)] = &[
+    ("play_room", include_str!("../tests/scenes/play_room.tscn")),
+    (
+        "shop_sidebar",
+        include_str!("../tests/scenes/shop_sidebar.tscn"),
+    ),
+    (
+        "scene_with_builtin_script",
+        include_str!("../tests/scenes/scene_with_builtin_script.tscn"),
+    ),
+    (
+        "table_selection_entry",
+        include_str!("../tests/scenes/table_selection_entry.tscn"),
+    ),
+];
+
+const PROJECT: &str = include_str!("../tests/projects/project_gamblers_table.godot");
+
+fn bench_property_file(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_property_file");
+    for (name, content) in SCENES {
+        group.throughput(Throughput::Bytes(content.len() as u64));
+        group.bench_function(*name, |b| {
+            b.iter(|| parse_property_file(black_box(content)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_scene_file(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_scene_file");
+    for (name, content) in SCENES {
+        group.throughput(Throughput::Bytes(content.len() as u64));
+        group.bench_function(*name, |b| b.iter(|| parse_scene_file(black_box(content))));
+    }
+    group.finish();
+}
+
+fn bench_project_file(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_project_file");
+    group.throughput(Throughput::Bytes(PROJECT.len() as u64));
+    group.bench_function("gamblers_table", |b| {
+        b.iter(|| parse_project_file(black_box(PROJECT)))
+    });
+    group.finish();
+}
+
+/// Reads section headers only, skipping the bodies
+fn bench_skip_bodies(c: &mut Criterion) {
+    let mut group = c.benchmark_group("section_iter_skip_bodies");
+    for (name, content) in SCENES {
+        group.throughput(Throughput::Bytes(content.len() as u64));
+        group.bench_function(*name, |b| {
+            b.iter(|| SectionIter::new(black_box(content)).skip_bodies().count())
+        });
+    }
+    group.finish();
+}
+
+/// Parses every property value of a scene into a typed `Value`
+fn bench_typed_values(c: &mut Criterion) {
+    let mut group = c.benchmark_group("typed_values");
+    for (name, content) in SCENES {
+        let (_, file) = parse_property_file(content).unwrap();
+        let values: Vec<&str> = file
+            .sections
+            .iter()
+            .flat_map(|s| s.properties.iter().map(|p| p.value.as_ref()))
+            .collect();
+        group.throughput(Throughput::Bytes(content.len() as u64));
+        group.bench_function(*name, |b| {
+            b.iter(|| {
+                for value in &values {
+                    black_box(Value::parse(black_box(value)));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_property_file,
+    bench_scene_file,
+    bench_project_file,
+    bench_skip_bodies,
+    bench_typed_values
+);
+criterion_main!(benches);
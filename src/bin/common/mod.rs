@@ -0,0 +1,20 @@
+//! Reading and parsing shared by the command-line tools.
+
+use std::fs;
+use std::path::Path;
+
+use godot_properties_parser::parsers::encoding::decode;
+use godot_properties_parser::parsers::parser_property_file::PropertyFile;
+use godot_properties_parser::try_parse_property_file;
+
+/// Reads and decodes a text file
+pub fn read_text(path: &Path) -> Result<String, String> {
+    let bytes = fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(decode(&bytes).text.into_owned())
+}
+
+/// Parses a whole file, refusing one with a malformed section header or an unterminated
+/// string instead of working on the sections before it
+pub fn parse<'a>(text: &'a str, path: &Path) -> Result<PropertyFile<'a>, String> {
+    try_parse_property_file(text).map_err(|e| format!("{}:{}", path.display(), e))
+}
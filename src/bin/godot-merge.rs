@@ -0,0 +1,88 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+
+use godot_properties_parser::parsers::parser_project_file::ProjectFile;
+use godot_properties_parser::parsers::parser_scene_file::SceneFile;
+use godot_properties_parser::{merge_projects, merge_scenes};
+
+mod common;
+
+use common::{parse, read_text};
+
+/// Merge driver for Git that merges Godot scenes, resources and project files section by
+/// section.
+///
+/// Writes the result to OURS and exits with status 1 if it contains conflicts. Configure it
+/// with `git config merge.godot.driver "godot-merge %O %A %B %P"` and a `merge=godot`
+/// attribute in `.gitattributes`.
+#[derive(Debug, Parser)]
+#[command(name = "godot-merge", version)]
+struct Cli {
+    /// The common ancestor (%O)
+    base: PathBuf,
+    /// Our version, overwritten with the merge result (%A)
+    ours: PathBuf,
+    /// Their version (%B)
+    theirs: PathBuf,
+    /// The path of the file in the repository (%P), used in conflict messages
+    path: Option<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match merge(&cli) {
+        Ok(code) => code,
+        Err(message) => {
+            eprintln!("godot-merge: {}", message);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn merge(cli: &Cli) -> Result<ExitCode, String> {
+    let base = read_text(&cli.base)?;
+    let ours = read_text(&cli.ours)?;
+    let theirs = read_text(&cli.theirs)?;
+    let (base, ours, theirs) = (
+        parse(&base, &cli.base)?,
+        parse(&ours, &cli.ours)?,
+        parse(&theirs, &cli.theirs)?,
+    );
+
+    // Scenes and resources start with their `[gd_scene]` or `[gd_resource]` header; any
+    // other file, such as `project.godot` or `export_presets.cfg`, is written like a
+    // `ConfigFile`
+    let is_scene = [&ours, &theirs, &base]
+        .into_iter()
+        .find_map(|file| file.sections.first())
+        .is_some_and(|s| matches!(s.header_type.as_ref(), "gd_scene" | "gd_resource"));
+    let (text, conflicts) = if is_scene {
+        let result = merge_scenes(
+            &SceneFile::from_property_file(base),
+            &SceneFile::from_property_file(ours),
+            &SceneFile::from_property_file(theirs),
+        );
+        (result.to_string(), result.conflicts)
+    } else {
+        let result = merge_projects(
+            &ProjectFile::from_property_file(base),
+            &ProjectFile::from_property_file(ours),
+            &ProjectFile::from_property_file(theirs),
+        );
+        (result.to_string(), result.conflicts)
+    };
+
+    fs::write(&cli.ours, text).map_err(|e| format!("{}: {}", cli.ours.display(), e))?;
+    let name = cli.path.as_deref().unwrap_or(&cli.ours).display();
+    for conflict in &conflicts {
+        eprintln!("{}: conflict: {}", name, conflict);
+    }
+    Ok(if conflicts.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
@@ -0,0 +1,506 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+use godot_properties_parser::formatter::{ExtResourceOrder, FormatOptions};
+use godot_properties_parser::lint::{Linter, Severity};
+use godot_properties_parser::parsers::json::{
+    project_to_json, property_file_to_json, scene_to_json,
+};
+use godot_properties_parser::parsers::parser_project_file::ProjectFile;
+use godot_properties_parser::parsers::parser_property_file::{PropertyFile, Section};
+use godot_properties_parser::parsers::parser_scene_file::SceneFile;
+use godot_properties_parser::parsers::parser_value::Value;
+use godot_properties_parser::report::{Report, parse_diagnostics};
+use godot_properties_parser::scene::{ROOT_PATH, node_path, parent_path};
+use godot_properties_parser::{
+    diff_projects, format_text, parse_property_file, parse_property_file_bytes,
+};
+
+mod common;
+
+use common::{parse, read_text};
+
+/// Inspect and query Godot scenes, resources and project files without running Godot
+#[derive(Debug, Parser)]
+#[command(name = "godot-props", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Print the node hierarchy of a scene
+    Tree {
+        /// A `.tscn` file
+        file: PathBuf,
+    },
+    /// Print a project setting, e.g. `application/config/version`
+    Get {
+        /// A `project.godot` file or the directory containing it
+        project: PathBuf,
+        /// The section followed by the key, or a key before the first section such as
+        /// `config_version`
+        setting: String,
+    },
+    /// List the external and built-in resources of a scene or resource, one per line as
+    /// `ext|sub <id> <type> <path>`
+    Deps {
+        /// A `.tscn` or `.tres` file
+        file: PathBuf,
+    },
+    /// Report problems in files and exit with status 1 if any is found
+    Check {
+        /// `.tscn`, `.tres`, `.godot`, `.cfg` or `.import` files
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Print a file as JSON with typed values
+    Json {
+        /// A `.tscn`, `.tres`, `.godot`, `.cfg` or `.import` file
+        file: PathBuf,
+        /// Print the JSON on a single line
+        #[arg(long)]
+        compact: bool,
+    },
+    /// Print the changes between two versions of a scene, resource or project file, and exit
+    /// with status 1 if there are any
+    Diff {
+        /// The old version
+        old: PathBuf,
+        /// The new version
+        new: PathBuf,
+        /// Print the changes as a JSON array
+        #[arg(long)]
+        json: bool,
+    },
+    /// Format files in place
+    Fmt {
+        /// `.tscn`, `.tres`, `.godot`, `.cfg` or `.import` files
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// Do not write the files; print those that are not formatted and exit with status 1
+        /// if there are any
+        #[arg(long)]
+        check: bool,
+        /// How to order the external resources of scenes and resources
+        #[arg(long, value_enum, default_value_t = Order::Id)]
+        ext_resource_order: Order,
+    },
+    /// Run the built-in lints and exit with status 1 if any reports an error
+    Lint {
+        /// `.tscn`, `.tres` or `.godot` files
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// Report a rule as an error
+        #[arg(long = "error", value_name = "RULE")]
+        errors: Vec<String>,
+        /// Report a rule as a warning
+        #[arg(long = "warning", value_name = "RULE")]
+        warnings: Vec<String>,
+        /// Report a rule as information
+        #[arg(long = "info", value_name = "RULE")]
+        infos: Vec<String>,
+        /// Turn a rule off
+        #[arg(long = "allow", value_name = "RULE")]
+        allowed: Vec<String>,
+        /// How to print the diagnostics
+        #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+        /// The directory file paths are reported relative to, by default the current
+        /// directory
+        #[arg(long)]
+        root: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ReportFormat {
+    /// One `file:line:column: severity[rule]: message` line per diagnostic
+    Text,
+    /// A SARIF 2.1.0 log, e.g. for code scanning
+    Sarif,
+    /// JUnit XML, e.g. for test dashboards
+    Junit,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Order {
+    /// Keep the order of the file
+    Keep,
+    /// Order by the number at the start of the id, as Godot does
+    Id,
+    /// Order by path
+    Path,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Tree { file } => tree(&file),
+        Command::Get { project, setting } => get(&project, &setting),
+        Command::Deps { file } => deps(&file),
+        Command::Check { files } => check(&files),
+        Command::Json { file, compact } => json(&file, compact),
+        Command::Diff { old, new, json } => diff(&old, &new, json),
+        Command::Fmt {
+            files,
+            check,
+            ext_resource_order,
+        } => fmt(&files, check, ext_resource_order),
+        Command::Lint {
+            files,
+            errors,
+            warnings,
+            infos,
+            allowed,
+            format,
+            root,
+        } => {
+            let severities = [
+                (errors, Some(Severity::Error)),
+                (warnings, Some(Severity::Warning)),
+                (infos, Some(Severity::Info)),
+                (allowed, None),
+            ];
+            lint(&files, &severities, format, root.as_deref())
+        }
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(message) => {
+            eprintln!("godot-props: {}", message);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn read(path: &Path) -> Result<PropertyFile<'static>, String> {
+    let text = read_text(path)?;
+    Ok(parse(&text, path)?.into_owned())
+}
+
+fn read_scene(path: &Path) -> Result<SceneFile<'static>, String> {
+    read(path).map(SceneFile::from_property_file)
+}
+
+fn tree(path: &Path) -> Result<ExitCode, String> {
+    let scene = read_scene(path)?;
+    let ext_paths: BTreeMap<&str, &str> = scene
+        .ext_resources()
+        .into_iter()
+        .filter_map(|ext| Some((ext.get("id")?, ext.get("path")?)))
+        .collect();
+
+    let mut children: BTreeMap<String, Vec<(String, &Section)>> = BTreeMap::new();
+    let mut root = None;
+    for node in scene.nodes() {
+        let Some(path) = node_path(node) else {
+            continue;
+        };
+        match parent_path(&path) {
+            Some(parent) => children
+                .entry(parent.to_string())
+                .or_default()
+                .push((path.clone(), node)),
+            None => root = Some(node),
+        }
+    }
+
+    let Some(root) = root else {
+        return Err(format!("{}: scene has no root node", path.display()));
+    };
+    println!("{}", describe_node(root, &ext_paths));
+    print_children(ROOT_PATH, "", &children, &ext_paths);
+    Ok(ExitCode::SUCCESS)
+}
+
+fn print_children(
+    parent: &str,
+    indent: &str,
+    children: &BTreeMap<String, Vec<(String, &Section)>>,
+    ext_paths: &BTreeMap<&str, &str>,
+) {
+    let Some(nodes) = children.get(parent) else {
+        return;
+    };
+    for (i, (path, node)) in nodes.iter().enumerate() {
+        let last = i + 1 == nodes.len();
+        let (branch, nested) = if last {
+            ("└─ ", "   ")
+        } else {
+            ("├─ ", "│  ")
+        };
+        println!("{}{}{}", indent, branch, describe_node(node, ext_paths));
+        print_children(path, &format!("{}{}", indent, nested), children, ext_paths);
+    }
+}
+
+/// `Name (Type)`, or `Name [res://instanced.tscn]` for an instanced scene
+fn describe_node(node: &Section, ext_paths: &BTreeMap<&str, &str>) -> String {
+    let name = node.get("name").unwrap_or("?");
+    let instance = node
+        .get("instance")
+        .and_then(Value::parse)
+        .and_then(|value| value.as_ext_resource().map(str::to_string));
+
+    match (node.get("type"), instance) {
+        (Some(ty), _) => format!("{} ({})", name, ty),
+        (None, Some(id)) => {
+            let target = ext_paths.get(id.as_str()).copied().unwrap_or(id.as_str());
+            format!("{} [{}]", name, target)
+        }
+        (None, None) => name.to_string(),
+    }
+}
+
+fn get(project: &Path, setting: &str) -> Result<ExitCode, String> {
+    let path = if project.is_dir() {
+        project.join("project.godot")
+    } else {
+        project.to_path_buf()
+    };
+    let file = read(&path)?;
+
+    // The last occurrence wins, as when Godot loads the file
+    let property = match setting.split_once('/') {
+        Some((section, key)) => file
+            .sections
+            .iter()
+            .filter(|s| s.header_type == section)
+            .flat_map(|s| &s.properties)
+            .rfind(|p| p.key == key),
+        None => file.preamble_properties.iter().rfind(|p| p.key == setting),
+    };
+
+    let Some(property) = property else {
+        eprintln!("godot-props: {}: {} is not set", path.display(), setting);
+        return Ok(ExitCode::FAILURE);
+    };
+    match Value::parse(&property.value_text()) {
+        Some(Value::String(s)) => println!("{}", s),
+        _ => println!("{}", property.value),
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn deps(path: &Path) -> Result<ExitCode, String> {
+    let scene = read_scene(path)?;
+    for ext in scene.ext_resources() {
+        println!(
+            "ext\t{}\t{}\t{}",
+            ext.get("id").unwrap_or(""),
+            ext.get("type").unwrap_or(""),
+            ext.get("path").unwrap_or("")
+        );
+    }
+    for sub in scene.sub_resources() {
+        println!(
+            "sub\t{}\t{}\t",
+            sub.get("id").unwrap_or(""),
+            sub.get("type").unwrap_or("")
+        );
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn check(paths: &[PathBuf]) -> Result<ExitCode, String> {
+    let mut errors = 0;
+    for path in paths {
+        let bytes = fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        // The sections before a parse error are still checked
+        let parsed = parse_property_file_bytes(&bytes);
+        let mut report = |line: usize, message: String| {
+            println!("{}:{}: {}", path.display(), line, message);
+            errors += 1;
+        };
+
+        for diagnostic in &parsed.diagnostics {
+            report(
+                diagnostic.line,
+                format!("invalid UTF-8 sequence at column {}", diagnostic.column),
+            );
+        }
+        if let Some(error) = parsed.error {
+            report(
+                error.location.line,
+                format!("{}, the rest of the file is ignored", error.kind),
+            );
+        }
+        let file = parsed.value;
+
+        if is_scene(path) {
+            let scene = SceneFile::from_property_file(file);
+            let line_of = |node: &str| scene.find_node(node).map_or(1, |n| n.location.line);
+            for duplicate in scene.duplicate_unique_names() {
+                report(
+                    line_of(&duplicate.paths[1]),
+                    format!(
+                        "unique name %{} is used by {} nodes: {}",
+                        duplicate.name,
+                        duplicate.paths.len(),
+                        duplicate.paths.join(", ")
+                    ),
+                );
+            }
+            for broken in scene.broken_node_paths() {
+                report(
+                    line_of(&broken.node),
+                    format!(
+                        "{} of node {} points to missing node \"{}\"",
+                        broken.property, broken.node, broken.path
+                    ),
+                );
+            }
+        }
+    }
+
+    Ok(if errors == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+fn json(path: &Path, compact: bool) -> Result<ExitCode, String> {
+    let file = read(path)?;
+    let json = match extension(path) {
+        "godot" => project_to_json(&ProjectFile::from_property_file(file)),
+        _ if is_scene(path) => scene_to_json(&SceneFile::from_property_file(file)),
+        _ => property_file_to_json(&file),
+    };
+
+    let text = if compact {
+        serde_json::to_string(&json)
+    } else {
+        serde_json::to_string_pretty(&json)
+    };
+    println!("{}", text.map_err(|e| e.to_string())?);
+    Ok(ExitCode::SUCCESS)
+}
+
+fn diff(old: &Path, new: &Path, json: bool) -> Result<ExitCode, String> {
+    let (a, b) = (read(old)?, read(new)?);
+    let diff = match extension(new) {
+        "godot" => diff_projects(
+            &ProjectFile::from_property_file(a),
+            &ProjectFile::from_property_file(b),
+        ),
+        _ => godot_properties_parser::diff(
+            &SceneFile::from_property_file(a),
+            &SceneFile::from_property_file(b),
+        ),
+    };
+
+    if json {
+        let text = serde_json::to_string_pretty(&diff.to_json()).map_err(|e| e.to_string())?;
+        println!("{}", text);
+    } else {
+        print!("{}", diff);
+    }
+    Ok(if diff.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+fn fmt(paths: &[PathBuf], check: bool, order: Order) -> Result<ExitCode, String> {
+    let options = FormatOptions {
+        ext_resource_order: match order {
+            Order::Keep => ExtResourceOrder::Keep,
+            Order::Id => ExtResourceOrder::Id,
+            Order::Path => ExtResourceOrder::Path,
+        },
+    };
+    let mut unformatted = 0;
+    for path in paths {
+        let text = read_text(path)?;
+        let formatted =
+            format_text(&text, &options).map_err(|e| format!("{}: {}", path.display(), e))?;
+        if formatted == text {
+            continue;
+        }
+        if check {
+            println!("{}", path.display());
+            unformatted += 1;
+        } else {
+            fs::write(path, formatted).map_err(|e| format!("{}: {}", path.display(), e))?;
+        }
+    }
+    Ok(if unformatted == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+fn lint(
+    paths: &[PathBuf],
+    severities: &[(Vec<String>, Option<Severity>)],
+    format: ReportFormat,
+    root: Option<&Path>,
+) -> Result<ExitCode, String> {
+    let mut linter = Linter::default();
+    for (rules, severity) in severities {
+        for rule in rules {
+            if !linter.set_severity(rule, *severity) {
+                return Err(format!(
+                    "unknown rule {}, the rules are: {}",
+                    rule,
+                    linter.rules().join(", ")
+                ));
+            }
+        }
+    }
+
+    let absolute =
+        |path: &Path| std::path::absolute(path).map_err(|e| format!("{}: {}", path.display(), e));
+    let mut report = Report::new(absolute(root.unwrap_or(Path::new(".")))?);
+    for path in paths {
+        let text = read_text(path)?;
+        let result = parse_property_file(&text);
+        let mut diagnostics = parse_diagnostics(&text, &result);
+        if let Ok((_, file)) = result {
+            diagnostics.extend(match extension(path) {
+                "godot" => linter.lint_project(&ProjectFile::from_property_file(file)),
+                _ => linter.lint_scene(&SceneFile::from_property_file(file)),
+            });
+        }
+        report.add(&absolute(path)?, &text, diagnostics);
+    }
+
+    match format {
+        ReportFormat::Text => {
+            for file in report.files() {
+                for entry in &file.entries {
+                    println!("{}:{}", file.path, entry.diagnostic);
+                }
+            }
+        }
+        ReportFormat::Sarif => {
+            let text =
+                serde_json::to_string_pretty(&report.to_sarif()).map_err(|e| e.to_string())?;
+            println!("{}", text);
+        }
+        ReportFormat::Junit => print!("{}", report.to_junit()),
+    }
+    Ok(if report.has_errors() {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+fn extension(path: &Path) -> &str {
+    path.extension().and_then(|ext| ext.to_str()).unwrap_or("")
+}
+
+fn is_scene(path: &Path) -> bool {
+    matches!(extension(path), "tscn" | "tres" | "escn")
+}
@@ -0,0 +1,668 @@
+//! Semantic diffs of scenes, resources and project files.
+//!
+//! Unlike a text diff, the result is not affected by resource id churn: external resources
+//! are matched by `uid` or `path`, and `ExtResource(...)` references are compared by the path
+//! they load. Sub-resources are matched by type and body, or else by id, and
+//! `SubResource(...)` references are compared by the id of the match. Nodes are compared by
+//! path, and a node that moved or was renamed is reported once as a move rather than as a
+//! removal and an addition of its whole subtree.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::merge::identity;
+use crate::parsers::parser_project_file::ProjectFile;
+use crate::parsers::parser_property::UntypedProperty;
+use crate::parsers::parser_property_file::Section;
+use crate::parsers::parser_scene_file::SceneFile;
+use crate::scene::resources::{ResourceKind, rename_resource_refs};
+use crate::scene::{join_path, node_path, parent_path};
+
+/// The changes between two versions of a file
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Diff {
+    pub changes: Vec<Change>,
+}
+
+/// One change between two versions of a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// A node was added. `node_type` is its type, or the scene it instances.
+    NodeAdded {
+        path: String,
+        node_type: Option<String>,
+    },
+    /// A node was removed
+    NodeRemoved {
+        path: String,
+        node_type: Option<String>,
+    },
+    /// A node was moved or renamed, together with its children
+    NodeMoved { from: String, to: String },
+    /// A property or header attribute was added, removed or changed
+    PropertyChanged {
+        owner: Owner,
+        key: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
+    /// An external resource or sub-resource was added
+    ResourceAdded {
+        resource: String,
+        resource_type: Option<String>,
+    },
+    /// An external resource or sub-resource was removed
+    ResourceRemoved {
+        resource: String,
+        resource_type: Option<String>,
+    },
+    /// A connection was added, described by its header attributes
+    ConnectionAdded { connection: String },
+    /// A connection was removed, described by its header attributes
+    ConnectionRemoved { connection: String },
+}
+
+/// What a changed property belongs to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Owner {
+    /// A node, by its path in the new version
+    Node(String),
+    /// An external resource by path, or a sub-resource as `SubResource("id")` with its id in
+    /// the new version
+    Resource(String),
+    /// Any other section, by header type, or an empty string for the properties before the
+    /// first section of a project file
+    Section(String),
+}
+
+impl Diff {
+    /// Returns true if the two versions are equivalent
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Converts the diff to a JSON array with one object per change. Each object has a
+    /// `change` field such as `node_added` or `property_changed`, and the fields of the
+    /// change; the owner of a property is in a `node`, `resource` or `section` field.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::json;
+
+        let changes = self.changes.iter().map(|change| match change {
+            Change::NodeAdded { path, node_type } => {
+                json!({ "change": "node_added", "path": path, "type": node_type })
+            }
+            Change::NodeRemoved { path, node_type } => {
+                json!({ "change": "node_removed", "path": path, "type": node_type })
+            }
+            Change::NodeMoved { from, to } => {
+                json!({ "change": "node_moved", "from": from, "to": to })
+            }
+            Change::PropertyChanged {
+                owner,
+                key,
+                old,
+                new,
+            } => {
+                let (field, name) = match owner {
+                    Owner::Node(path) => ("node", path),
+                    Owner::Resource(resource) => ("resource", resource),
+                    Owner::Section(header) => ("section", header),
+                };
+                json!({
+                    "change": "property_changed",
+                    field: name,
+                    "key": key,
+                    "old": old,
+                    "new": new,
+                })
+            }
+            Change::ResourceAdded {
+                resource,
+                resource_type,
+            } => json!({ "change": "resource_added", "resource": resource, "type": resource_type }),
+            Change::ResourceRemoved {
+                resource,
+                resource_type,
+            } => {
+                json!({ "change": "resource_removed", "resource": resource, "type": resource_type })
+            }
+            Change::ConnectionAdded { connection } => {
+                json!({ "change": "connection_added", "connection": connection })
+            }
+            Change::ConnectionRemoved { connection } => {
+                json!({ "change": "connection_removed", "connection": connection })
+            }
+        });
+        serde_json::Value::Array(changes.collect())
+    }
+}
+
+/// Writes one change per line, prefixed with `+` for additions, `-` for removals, `>` for
+/// moves and `~` for property changes
+impl fmt::Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let with_type = |f: &mut fmt::Formatter<'_>, node_type: &Option<String>| match node_type {
+            Some(node_type) => write!(f, " ({})", node_type),
+            None => Ok(()),
+        };
+        match self {
+            Change::NodeAdded { path, node_type } => {
+                write!(f, "+ node {}", path)?;
+                with_type(f, node_type)
+            }
+            Change::NodeRemoved { path, node_type } => {
+                write!(f, "- node {}", path)?;
+                with_type(f, node_type)
+            }
+            Change::NodeMoved { from, to } => write!(f, "> node {} -> {}", from, to),
+            Change::PropertyChanged {
+                owner,
+                key,
+                old,
+                new,
+            } => {
+                let value = |value: &Option<String>| value.clone().unwrap_or("(unset)".to_string());
+                match owner {
+                    Owner::Section(header) if header.is_empty() => write!(f, "~ {}", key)?,
+                    owner => write!(f, "~ {} {}", owner, key)?,
+                }
+                write!(f, ": {} -> {}", value(old), value(new))
+            }
+            Change::ResourceAdded {
+                resource,
+                resource_type,
+            } => {
+                write!(f, "+ resource {}", resource)?;
+                with_type(f, resource_type)
+            }
+            Change::ResourceRemoved {
+                resource,
+                resource_type,
+            } => {
+                write!(f, "- resource {}", resource)?;
+                with_type(f, resource_type)
+            }
+            Change::ConnectionAdded { connection } => write!(f, "+ connection {}", connection),
+            Change::ConnectionRemoved { connection } => write!(f, "- connection {}", connection),
+        }
+    }
+}
+
+impl fmt::Display for Owner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Owner::Node(path) => write!(f, "node {}", path),
+            Owner::Resource(resource) => write!(f, "resource {}", resource),
+            Owner::Section(header) => write!(f, "[{}]", header),
+        }
+    }
+}
+
+/// Compares two versions of a scene or resource file.
+///
+/// # Example
+///
+/// ```
+/// use godot_properties_parser::diff::diff;
+/// use godot_properties_parser::parse_scene_file;
+///
+/// let (_, old) = parse_scene_file("[node name=\"Main\" type=\"Node2D\"]\n").unwrap();
+/// let (_, new) = parse_scene_file(
+///     "[node name=\"Main\" type=\"Node2D\"]\nvisible = false\n\n\
+///      [node name=\"Icon\" type=\"Sprite2D\" parent=\".\"]\n",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(
+///     diff(&old, &new).to_string(),
+///     "~ node . visible: (unset) -> false\n+ node Icon (Sprite2D)\n"
+/// );
+/// ```
+pub fn diff(a: &SceneFile, b: &SceneFile) -> Diff {
+    let mut changes = Vec::new();
+    let (a_paths, b_paths) = ext_resource_changes(a, b, &mut changes);
+    let normalize = |section: &Section, paths: &BTreeMap<String, String>| {
+        let mut section = section.clone().into_owned();
+        rename_resource_refs(&mut section, &|kind, id| match kind {
+            ResourceKind::Ext => paths.get(id).map(|path| path.to_string()),
+            ResourceKind::Sub => None,
+        });
+        section
+    };
+    let sections = |scene: &SceneFile, paths| {
+        scene
+            .all_sections
+            .iter()
+            .map(|section| normalize(section, paths))
+            .collect::<Vec<_>>()
+    };
+    let (mut a_sections, b_sections) = (sections(a, &a_paths), sections(b, &b_paths));
+
+    // Sub-resource references of the old version are compared by the id of their pair
+    let sub_pairs = pair_sub_resources(&a_sections, &b_sections);
+    let b_ids: BTreeSet<&str> = sub_resources(&b_sections)
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+    for section in &mut a_sections {
+        rename_resource_refs(section, &|kind, id| match kind {
+            ResourceKind::Ext => None,
+            ResourceKind::Sub => sub_pairs.get(id).cloned().or_else(|| {
+                // A removed sub-resource whose id the new version uses for another one
+                b_ids.contains(id).then(|| format!("{} (removed)", id))
+            }),
+        });
+    }
+
+    sub_resource_changes(&a_sections, &b_sections, &sub_pairs, &mut changes);
+    node_changes(&a_sections, &b_sections, &mut changes);
+
+    let connections = |sections: &[Section<'static>]| {
+        sections
+            .iter()
+            .filter(|s| s.header_type == "connection")
+            .map(|s| {
+                let attributes = s.attributes().iter().map(ToString::to_string);
+                attributes.collect::<Vec<_>>().join(" ")
+            })
+            .collect::<Vec<_>>()
+    };
+    let (a_connections, b_connections) = (connections(&a_sections), connections(&b_sections));
+    for connection in a_connections.iter().filter(|c| !b_connections.contains(c)) {
+        changes.push(Change::ConnectionRemoved {
+            connection: connection.clone(),
+        });
+    }
+    for connection in b_connections.iter().filter(|c| !a_connections.contains(c)) {
+        changes.push(Change::ConnectionAdded {
+            connection: connection.clone(),
+        });
+    }
+
+    let others = |sections: &[Section<'static>]| {
+        sections
+            .iter()
+            .filter(|s| {
+                !matches!(
+                    s.header_type.as_ref(),
+                    "node" | "ext_resource" | "sub_resource" | "connection"
+                )
+            })
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+    section_changes(&others(&a_sections), &others(&b_sections), &mut changes);
+
+    Diff { changes }
+}
+
+/// Compares two versions of a `project.godot` file setting by setting
+pub fn diff_projects(a: &ProjectFile, b: &ProjectFile) -> Diff {
+    let mut changes = Vec::new();
+    property_changes(
+        Owner::Section(String::new()),
+        &a.preamble_properties,
+        &b.preamble_properties,
+        &[],
+        &mut changes,
+    );
+    section_changes(&a.all_sections, &b.all_sections, &mut changes);
+    Diff { changes }
+}
+
+/// Reports the properties of `b` that differ from those of `a`, in the order of `a` followed
+/// by the properties only `b` has. Keys in `skip` are ignored.
+fn property_changes(
+    owner: Owner,
+    a: &[UntypedProperty],
+    b: &[UntypedProperty],
+    skip: &[&str],
+    changes: &mut Vec<Change>,
+) {
+    let mut keys: Vec<&str> = Vec::new();
+    for property in a.iter().chain(b) {
+        if !keys.contains(&property.key.as_ref()) && !skip.contains(&property.key.as_ref()) {
+            keys.push(&property.key);
+        }
+    }
+    // Later occurrences of a key win, as when Godot loads the file
+    let value = |properties: &[UntypedProperty], key: &str| {
+        properties
+            .iter()
+            .rfind(|p| p.key == key)
+            .map(|p| p.value_text().into_owned())
+    };
+    for key in keys {
+        let (old, new) = (value(a, key), value(b, key));
+        if old != new {
+            changes.push(Change::PropertyChanged {
+                owner: owner.clone(),
+                key: key.to_string(),
+                old,
+                new,
+            });
+        }
+    }
+}
+
+/// Compares sections matched by header, and by order among sections with the same header
+fn section_changes(a: &[Section], b: &[Section], changes: &mut Vec<Change>) {
+    let (a, b) = (keyed_sections(a), keyed_sections(b));
+    let mut keys: Vec<&(String, usize)> = a.iter().map(|(key, _)| key).collect();
+    keys.extend(
+        b.iter()
+            .map(|(key, _)| key)
+            .filter(|key| !a.iter().any(|(k, _)| k == *key)),
+    );
+
+    for key in keys {
+        let find = |sections: &[((String, usize), Section<'static>)]| {
+            sections
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, s)| s.properties.clone())
+                .unwrap_or_default()
+        };
+        let skip: &[&str] = match key.0.as_str() {
+            "gd_scene" | "gd_resource" => &["load_steps"],
+            _ => &[],
+        };
+        property_changes(
+            Owner::Section(key.0.clone()),
+            &find(&a),
+            &find(&b),
+            skip,
+            changes,
+        );
+    }
+}
+
+/// Pairs each section with its identity and how many sections before it had the same one
+fn keyed_sections(sections: &[Section]) -> Vec<((String, usize), Section<'static>)> {
+    let mut seen: BTreeMap<String, usize> = BTreeMap::new();
+    sections
+        .iter()
+        .map(|section| {
+            let key = identity(section);
+            let count = seen.entry(key.clone()).or_default();
+            *count += 1;
+            ((key, *count), section.clone().into_owned())
+        })
+        .collect()
+}
+
+/// Pairs the external resources of both files by `uid`, or by `path` where either has no
+/// uid, and reports the unpaired ones and the changes to paired ones. Returns the path each
+/// id should be compared as: the new path of a resource present in both.
+fn ext_resource_changes(
+    a: &SceneFile,
+    b: &SceneFile,
+    changes: &mut Vec<Change>,
+) -> (BTreeMap<String, String>, BTreeMap<String, String>) {
+    let (a_ext, b_ext) = (a.ext_resources(), b.ext_resources());
+    let attribute = |section: &Section, key| section.get(key).unwrap_or_default().to_string();
+    let mut paired = vec![None; a_ext.len()];
+    for (j, new) in b_ext.iter().enumerate() {
+        let matches = |old: &&Section| match (old.get("uid"), new.get("uid")) {
+            (Some(old), Some(new)) => old == new,
+            _ => old.get("path") == new.get("path"),
+        };
+        if let Some(i) = (0..a_ext.len()).find(|&i| paired[i].is_none() && matches(&a_ext[i])) {
+            paired[i] = Some(j);
+        }
+    }
+
+    let mut a_paths = BTreeMap::new();
+    let b_paths: BTreeMap<String, String> = b_ext
+        .iter()
+        .map(|ext| (attribute(ext, "id"), attribute(ext, "path")))
+        .collect();
+    for (i, old) in a_ext.iter().enumerate() {
+        match paired[i] {
+            Some(j) => {
+                let new = b_ext[j];
+                a_paths.insert(attribute(old, "id"), attribute(new, "path"));
+                property_changes(
+                    Owner::Resource(attribute(new, "path")),
+                    old.attributes(),
+                    new.attributes(),
+                    &["id"],
+                    changes,
+                );
+            }
+            None => {
+                a_paths.insert(attribute(old, "id"), attribute(old, "path"));
+                changes.push(Change::ResourceRemoved {
+                    resource: attribute(old, "path"),
+                    resource_type: old.get("type").map(str::to_string),
+                });
+            }
+        }
+    }
+    for (j, new) in b_ext.iter().enumerate() {
+        if !paired.contains(&Some(j)) {
+            changes.push(Change::ResourceAdded {
+                resource: attribute(new, "path"),
+                resource_type: new.get("type").map(str::to_string),
+            });
+        }
+    }
+    (a_paths, b_paths)
+}
+
+/// The sub-resources among `sections` with their ids, in file order
+fn sub_resources<'s, 'a>(sections: &'s [Section<'a>]) -> Vec<(&'s str, &'s Section<'a>)> {
+    sections
+        .iter()
+        .filter(|s| s.header_type == "sub_resource")
+        .map(|s| (s.get("id").unwrap_or_default(), s))
+        .collect()
+}
+
+/// Pairs the sub-resources of both versions and returns the id in `b` of each one in `a`
+/// that has a pair. Sub-resources with the same type and body are paired first, by the
+/// same id where possible, and the rest by id. The body of each sub-resource in `a` is
+/// compared with the pairs found before it applied, so one that refers to renumbered
+/// sub-resources declared earlier still finds its pair.
+fn pair_sub_resources(a: &[Section], b: &[Section]) -> BTreeMap<String, String> {
+    let content = |section: &Section| {
+        let body = section.body().iter().map(ToString::to_string);
+        (
+            section.get("type").map(str::to_string),
+            body.collect::<Vec<_>>().join("\n"),
+        )
+    };
+    let (a, b) = (sub_resources(a), sub_resources(b));
+    let b_by_id: BTreeMap<&str, usize> =
+        b.iter().enumerate().map(|(j, (id, _))| (*id, j)).collect();
+    let mut b_by_content: BTreeMap<_, Vec<usize>> = BTreeMap::new();
+    for (j, (_, section)) in b.iter().enumerate() {
+        b_by_content.entry(content(section)).or_default().push(j);
+    }
+
+    let mut pairs: BTreeMap<String, String> = BTreeMap::new();
+    let mut taken = vec![false; b.len()];
+    for &(id, old) in &a {
+        let mut old = old.clone();
+        rename_resource_refs(&mut old, &|kind, id| match kind {
+            ResourceKind::Ext => None,
+            ResourceKind::Sub => pairs.get(id).cloned(),
+        });
+        let candidates = b_by_content.get(&content(&old)).into_iter().flatten();
+        let free: Vec<usize> = candidates.copied().filter(|&j| !taken[j]).collect();
+        let same_id = free.iter().copied().find(|&j| b[j].0 == id);
+        if let Some(j) = same_id.or(free.first().copied()) {
+            taken[j] = true;
+            pairs.insert(id.to_string(), b[j].0.to_string());
+        }
+    }
+    for &(id, _) in &a {
+        if !pairs.contains_key(id)
+            && let Some(&j) = b_by_id.get(id)
+            && !taken[j]
+        {
+            taken[j] = true;
+            pairs.insert(id.to_string(), id.to_string());
+        }
+    }
+    pairs
+}
+
+/// Compares the sub-resources of `a` with their pairs in `b`, reporting the unpaired ones
+/// as removed or added. `pairs` maps ids in `a` to ids in `b`, see `pair_sub_resources`.
+fn sub_resource_changes(
+    a: &[Section],
+    b: &[Section],
+    pairs: &BTreeMap<String, String>,
+    changes: &mut Vec<Change>,
+) {
+    let b_index: BTreeMap<&str, &Section> = sub_resources(b).into_iter().collect();
+    let name = |id: &str| format!("SubResource(\"{}\")", id);
+    let resource_type = |section: &Section| section.get("type").map(str::to_string);
+
+    for (id, old) in sub_resources(a) {
+        match pairs
+            .get(id)
+            .and_then(|new_id| b_index.get_key_value(new_id.as_str()))
+        {
+            Some((new_id, new)) => property_changes(
+                Owner::Resource(name(new_id)),
+                &old.properties,
+                &new.properties,
+                &["id"],
+                changes,
+            ),
+            None => changes.push(Change::ResourceRemoved {
+                resource: name(id),
+                resource_type: resource_type(old),
+            }),
+        }
+    }
+    let paired: BTreeSet<&str> = pairs.values().map(String::as_str).collect();
+    for (id, new) in sub_resources(b) {
+        if !paired.contains(id) {
+            changes.push(Change::ResourceAdded {
+                resource: name(id),
+                resource_type: resource_type(new),
+            });
+        }
+    }
+}
+
+/// The type of a node, or the scene it instances
+fn node_type(node: &Section) -> Option<String> {
+    node.get("type")
+        .or(node.get("instance"))
+        .map(str::to_string)
+}
+
+/// Compares nodes by path, pairing removed nodes with added ones they were moved or renamed
+/// to. A node counts as moved when its parent moved with it, or when a node of the same type
+/// with the same body took its name or its place under the same parent.
+fn node_changes(a: &[Section], b: &[Section], changes: &mut Vec<Change>) {
+    let nodes = |sections: &'_ [Section<'_>]| -> Vec<(String, usize)> {
+        sections
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.header_type == "node")
+            .filter_map(|(i, s)| Some((node_path(s)?, i)))
+            .collect()
+    };
+    let (a_nodes, b_nodes) = (nodes(a), nodes(b));
+    let a_index: BTreeMap<&str, &Section> =
+        a_nodes.iter().map(|(p, i)| (p.as_str(), &a[*i])).collect();
+    let b_index: BTreeMap<&str, &Section> =
+        b_nodes.iter().map(|(p, i)| (p.as_str(), &b[*i])).collect();
+
+    // New path by old path and old path by new path, for every node that is in both versions
+    let mut paired: BTreeMap<&str, &str> = BTreeMap::new();
+    let mut pairs_of: BTreeMap<&str, &str> = BTreeMap::new();
+    // New path by old path, for the nodes that moved
+    let mut moves: BTreeMap<&str, String> = BTreeMap::new();
+    for (path, _) in &a_nodes {
+        if b_index.contains_key(path.as_str()) {
+            paired.insert(path, path);
+            pairs_of.insert(path, path);
+        }
+    }
+    for (path, i) in &a_nodes {
+        let (path, old) = (path.as_str(), &a[*i]);
+        if paired.contains_key(path) {
+            continue;
+        }
+        let is_free = |new_path: &str, pairs_of: &BTreeMap<&str, &str>| {
+            !a_index.contains_key(new_path) && !pairs_of.contains_key(new_path)
+        };
+        // Children of a moved node move with it
+        let inherited = parent_path(path)
+            .and_then(|parent| moves.get(parent))
+            .map(|to| join_path(to, old.get("name").unwrap_or_default()));
+        let new_path = match inherited.and_then(|p| b_index.get_key_value(p.as_str())) {
+            Some((&new_path, _)) if is_free(new_path, &pairs_of) => new_path,
+            _ => {
+                let candidates: Vec<(&str, &Section)> = b_nodes
+                    .iter()
+                    .map(|(new_path, j)| (new_path.as_str(), &b[*j]))
+                    .filter(|(new_path, new)| {
+                        is_free(new_path, &pairs_of)
+                            && node_type(new) == node_type(old)
+                            && old.body() == new.body()
+                            && (new.get("name") == old.get("name")
+                                || new.get("parent") == old.get("parent"))
+                    })
+                    .collect();
+                let best = candidates
+                    .iter()
+                    .find(|(_, new)| new.get("name") == old.get("name"))
+                    .or(candidates.first());
+                let Some(&(new_path, _)) = best else {
+                    continue;
+                };
+                changes.push(Change::NodeMoved {
+                    from: path.to_string(),
+                    to: new_path.to_string(),
+                });
+                new_path
+            }
+        };
+        paired.insert(path, new_path);
+        pairs_of.insert(new_path, path);
+        moves.insert(path, new_path.to_string());
+    }
+
+    for (path, i) in &a_nodes {
+        if !paired.contains_key(path.as_str()) {
+            changes.push(Change::NodeRemoved {
+                path: path.clone(),
+                node_type: node_type(&a[*i]),
+            });
+        }
+    }
+    for (path, i) in &b_nodes {
+        let new = &b[*i];
+        let Some(old) = pairs_of.get(path.as_str()).and_then(|old| a_index.get(old)) else {
+            changes.push(Change::NodeAdded {
+                path: path.clone(),
+                node_type: node_type(new),
+            });
+            continue;
+        };
+        property_changes(
+            Owner::Node(path.clone()),
+            &old.properties,
+            &new.properties,
+            &["name", "parent"],
+            changes,
+        );
+    }
+}
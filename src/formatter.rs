@@ -0,0 +1,309 @@
+//! Canonical formatting of Godot text files.
+//!
+//! Scenes and resources are written the way the Godot editor saves them: `key = value`
+//! properties, a blank line between sections, and no blank line between consecutive
+//! `[ext_resource]`, `[connection]` and `[editable]` lines. Other files, such as
+//! `project.godot`, `.cfg` and `.import` files, are written like Godot's `ConfigFile`:
+//! `key=value` properties and a blank line after each section header. Formatting is
+//! idempotent: formatting formatted text returns it unchanged.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::parsers::lexer::{Lexer, TokenKind};
+use crate::parsers::parser_property::UntypedProperty;
+use crate::parsers::parser_property_file::{PropertyFile, Section, try_parse_property_file};
+use crate::parsers::parser_value::Value;
+use crate::scene::resources::id_number;
+
+/// Constructors whose numbers Godot writes without a fraction when they are whole, e.g.
+/// `Vector2(64, 32)` rather than `Vector2(64.0, 32.0)`
+const REAL_CONSTRUCTORS: &[&str] = &[
+    "Vector2",
+    "Vector3",
+    "Vector4",
+    "Rect2",
+    "Transform2D",
+    "Transform3D",
+    "Basis",
+    "Quaternion",
+    "Plane",
+    "AABB",
+    "Projection",
+    "Color",
+    "PackedFloat32Array",
+    "PackedFloat64Array",
+    "PackedVector2Array",
+    "PackedVector3Array",
+    "PackedVector4Array",
+    "PackedColorArray",
+];
+
+/// Sections written on consecutive lines when they have no properties
+const LINE_SECTIONS: &[&str] = &["ext_resource", "connection", "editable"];
+
+/// How to order the `[ext_resource]` sections of a scene or resource
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExtResourceOrder {
+    /// Keep the order of the file
+    Keep,
+    /// Order by the number at the start of the id, as Godot numbers them in the order it
+    /// saves them. Ids without a number keep their place after the numbered ones.
+    #[default]
+    Id,
+    /// Order by `path`
+    Path,
+}
+
+/// Options for `format_text` and `format_property_file`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormatOptions {
+    pub ext_resource_order: ExtResourceOrder,
+}
+
+/// Error returned when text cannot be formatted because part of it could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatError {
+    /// The line of the parse error, counting from 1
+    pub line: usize,
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {} could not be parsed", self.line)
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+/// Formats the text of a Godot file.
+///
+/// The `;` comments at the top of the file, such as the header Godot writes at the start of
+/// `project.godot`, are kept with a blank line after them. Other comments stay with the
+/// section or property whose line they are on or precede, and comments after the last
+/// property stay at the end of the file.
+///
+/// # Example
+///
+/// ```
+/// use godot_properties_parser::formatter::{FormatOptions, format_text};
+///
+/// let text = "[gd_scene format=3]\n[node name=\"Main\" type=\"Node2D\"]\nposition=Vector2(64.0,32.0)\n\n\n";
+/// assert_eq!(
+///     format_text(text, &FormatOptions::default()).unwrap(),
+///     "[gd_scene format=3]\n\n[node name=\"Main\" type=\"Node2D\"]\nposition = Vector2(64, 32)\n"
+/// );
+/// ```
+pub fn format_text(text: &str, options: &FormatOptions) -> Result<String, FormatError> {
+    let file = try_parse_property_file(text).map_err(|error| FormatError {
+        line: error.location.line,
+    })?;
+
+    let comments = Comments::new(text, &file);
+    let mut formatted = String::new();
+    for comment in &comments.top {
+        formatted.push_str(comment);
+        formatted.push('\n');
+    }
+
+    let body = write_file(&file, options, &comments);
+    if !comments.top.is_empty() && !body.is_empty() {
+        formatted.push('\n');
+    }
+    formatted.push_str(&body);
+    Ok(formatted)
+}
+
+/// Formats a parsed file. The file is formatted as a scene or resource if its first section
+/// is `[gd_scene]` or `[gd_resource]`, and like a `ConfigFile` otherwise.
+pub fn format_property_file(file: &PropertyFile, options: &FormatOptions) -> String {
+    write_file(file, options, &Comments::default())
+}
+
+fn write_file(file: &PropertyFile, options: &FormatOptions, comments: &Comments) -> String {
+    let is_scene = file
+        .sections
+        .first()
+        .is_some_and(|s| matches!(s.header_type.as_ref(), "gd_scene" | "gd_resource"));
+    let mut sections: Vec<&Section> = file.sections.iter().collect();
+    if is_scene {
+        order_ext_resources(&mut sections, options.ext_resource_order);
+    }
+
+    let separator = if is_scene { " = " } else { "=" };
+    let mut text = String::new();
+    let write_property = |text: &mut String, property: &UntypedProperty| {
+        let start = property.key_location.span.start;
+        comments.write_leading(text, start);
+        text.push_str(&format!(
+            "{}{}{}",
+            property.key,
+            separator,
+            format_value(property)
+        ));
+        comments.end_line(text, start);
+    };
+    for property in &file.preamble_properties {
+        write_property(&mut text, property);
+    }
+    for (i, section) in sections.iter().enumerate() {
+        let previous = i.checked_sub(1).map(|i| sections[i]);
+        let on_next_line = previous.is_some_and(|previous| {
+            previous.header_type == section.header_type
+                && LINE_SECTIONS.contains(&section.header_type.as_ref())
+                && previous.body().is_empty()
+        });
+        if !text.is_empty() && !on_next_line {
+            text.push('\n');
+        }
+
+        let start = section.location.span.start;
+        comments.write_leading(&mut text, start);
+        text.push('[');
+        text.push_str(&section.header_type);
+        for attribute in section.attributes() {
+            text.push_str(&format!(" {}={}", attribute.key, format_value(attribute)));
+        }
+        text.push(']');
+        comments.end_line(&mut text, start);
+        if !is_scene {
+            text.push('\n');
+        }
+        for property in section.body() {
+            write_property(&mut text, property);
+        }
+    }
+    for comment in &comments.end {
+        text.push_str(comment);
+        text.push('\n');
+    }
+    text
+}
+
+/// The `;` comments of a file, keyed by the offset of the section or property they belong to
+#[derive(Debug, Default)]
+struct Comments<'a> {
+    /// Comments before the first section or property
+    top: Vec<&'a str>,
+    /// Comments on the lines before a section or property
+    leading: BTreeMap<usize, Vec<&'a str>>,
+    /// Comments at the end of the line of a section header or property
+    trailing: BTreeMap<usize, &'a str>,
+    /// Comments after the last section or property
+    end: Vec<&'a str>,
+}
+
+impl<'a> Comments<'a> {
+    fn new(text: &'a str, file: &PropertyFile) -> Self {
+        // Comments inside a value spanning several lines are part of the value
+        let values: Vec<_> = file
+            .preamble_properties
+            .iter()
+            .chain(file.sections.iter().flat_map(|s| &s.properties))
+            .map(|p| p.value_location.span)
+            .collect();
+        let mut starts: Vec<usize> = file
+            .preamble_properties
+            .iter()
+            .chain(file.sections.iter().flat_map(|s| s.body()))
+            .map(|p| p.key_location.span.start)
+            .chain(file.sections.iter().map(|s| s.location.span.start))
+            .collect();
+        starts.sort_unstable();
+
+        let mut comments = Self::default();
+        let mut line_start = true;
+        for token in Lexer::new(text) {
+            let offset = token.span.start;
+            let after_token = !line_start;
+            line_start = token.kind == TokenKind::Newline;
+            if token.kind != TokenKind::Comment
+                || values.iter().any(|v| v.start < offset && offset < v.end)
+            {
+                continue;
+            }
+
+            let comment = token.text.trim_end();
+            let next = starts.partition_point(|&start| start < offset);
+            if after_token && next > 0 {
+                comments.trailing.insert(starts[next - 1], comment);
+            } else if next == 0 {
+                comments.top.push(comment);
+            } else if next < starts.len() {
+                comments
+                    .leading
+                    .entry(starts[next])
+                    .or_default()
+                    .push(comment);
+            } else {
+                comments.end.push(comment);
+            }
+        }
+        comments
+    }
+
+    /// Writes the comment lines before the section or property starting at `start`
+    fn write_leading(&self, text: &mut String, start: usize) {
+        for comment in self.leading.get(&start).into_iter().flatten() {
+            text.push_str(comment);
+            text.push('\n');
+        }
+    }
+
+    /// Ends the line of the section header or property starting at `start`
+    fn end_line(&self, text: &mut String, start: usize) {
+        if let Some(comment) = self.trailing.get(&start) {
+            text.push(' ');
+            text.push_str(comment);
+        }
+        text.push('\n');
+    }
+}
+
+/// Sorts the `[ext_resource]` sections among the places they take in the file
+fn order_ext_resources(sections: &mut [&Section], order: ExtResourceOrder) {
+    let slots: Vec<usize> = (0..sections.len())
+        .filter(|&i| sections[i].header_type == "ext_resource")
+        .collect();
+    let mut ext_resources: Vec<&Section> = slots.iter().map(|&i| sections[i]).collect();
+    match order {
+        ExtResourceOrder::Keep => return,
+        ExtResourceOrder::Id => {
+            ext_resources.sort_by_key(|ext| ext.get("id").and_then(id_number).unwrap_or(usize::MAX))
+        }
+        ExtResourceOrder::Path => {
+            ext_resources.sort_by_key(|ext| ext.get("path").unwrap_or_default())
+        }
+    }
+    for (slot, ext) in slots.into_iter().zip(ext_resources) {
+        sections[slot] = ext;
+    }
+}
+
+/// Writes a value the way Godot does. Quoted strings, and values that are not valid Godot
+/// values, are written as they are.
+fn format_value(property: &UntypedProperty) -> String {
+    if property.quoted {
+        return property.value_text().into_owned();
+    }
+    let Some(mut value) = Value::parse(&property.value) else {
+        return property.value.trim().to_string();
+    };
+    value.visit_mut(&mut |value| {
+        if let Value::Constructor { name, args } = value
+            && REAL_CONSTRUCTORS.contains(&name.as_str())
+        {
+            for arg in args {
+                // An integer cannot hold the sign of -0.0
+                if let Value::Float(x) = *arg
+                    && x.fract() == 0.0
+                    && x.abs() < 1e15
+                    && !(x == 0.0 && x.is_sign_negative())
+                {
+                    *arg = Value::Int(x as i64);
+                }
+            }
+        }
+    });
+    value.to_string()
+}
@@ -1,5 +1,25 @@
+pub mod diff;
+pub mod formatter;
+pub mod lint;
+pub mod merge;
 pub mod parsers;
+pub mod project;
+pub mod report;
+pub mod scene;
 
-pub use parsers::parser_project_file::parse_project_file;
-pub use parsers::parser_property_file::parse_property_file;
-pub use parsers::parser_scene_file::parse_scene_file;
+pub use diff::{diff, diff_projects};
+pub use formatter::format_text;
+pub use merge::{merge_projects, merge_scenes};
+pub use parsers::encoding::{EncodingDiagnostic, ParsedBytes};
+pub use parsers::parser_project_file::{
+    parse_project_file, parse_project_file_bytes, try_parse_project_file,
+};
+pub use parsers::parser_property_file::{
+    ParseError, ParseErrorKind, parse_property_file, parse_property_file_bytes,
+    try_parse_property_file,
+};
+pub use parsers::parser_scene_file::{
+    parse_scene_file, parse_scene_file_bytes, try_parse_scene_file,
+};
+pub use parsers::parser_section_iter::{SectionIter, SectionReader};
+pub use project::parse_project_dir;
@@ -0,0 +1,217 @@
+//! Lints for scenes, resources and project files.
+//!
+//! A lint is a type implementing `Lint`, which inspects a parsed file and reports findings
+//! with the location they refer to. A `Linter` runs a set of lints, each with a severity
+//! that can be changed or turned off, and turns their findings into `Diagnostic`s sorted by
+//! position. `Linter::new()` runs the built-in lints from `rules`, `Linter::empty()` starts
+//! without any.
+
+pub mod rules;
+
+use std::fmt;
+
+use crate::parsers::parser_project_file::ProjectFile;
+use crate::parsers::parser_scene_file::SceneFile;
+use crate::parsers::source_map::Location;
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// Something a lint found, before the linter assigns it a severity
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub message: String,
+    /// The section header or property the finding is about
+    pub location: Location,
+}
+
+impl Finding {
+    pub fn new(message: impl Into<String>, location: Location) -> Self {
+        Self {
+            message: message.into(),
+            location,
+        }
+    }
+}
+
+/// A finding reported by a `Linter`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The name of the lint that reported it
+    pub rule: String,
+    pub severity: Severity,
+    pub message: String,
+    pub location: Location,
+}
+
+/// Writes `line:column: severity[rule]: message`
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}[{}]: {}",
+            self.location.line, self.location.column, self.severity, self.rule, self.message
+        )
+    }
+}
+
+/// A check over scenes, resources or project files.
+///
+/// Both checks report nothing by default, so a lint only implements the ones it applies to.
+///
+/// # Example
+///
+/// ```
+/// use godot_properties_parser::lint::{Finding, Lint, Linter, Severity};
+/// use godot_properties_parser::parse_scene_file;
+/// use godot_properties_parser::parsers::parser_scene_file::SceneFile;
+///
+/// /// Nodes should be named in PascalCase
+/// struct NodeNames;
+///
+/// impl Lint for NodeNames {
+///     fn name(&self) -> &'static str {
+///         "node-name-case"
+///     }
+///
+///     fn check_scene(&self, scene: &SceneFile) -> Vec<Finding> {
+///         scene
+///             .nodes()
+///             .into_iter()
+///             .filter(|node| node.get("name").is_some_and(|name| name.contains('_')))
+///             .map(|node| Finding::new("node name is not PascalCase", node.location))
+///             .collect()
+///     }
+/// }
+///
+/// let mut linter = Linter::empty();
+/// linter.add(NodeNames, Severity::Warning);
+/// let (_, scene) = parse_scene_file("[node name=\"main_menu\" type=\"Control\"]\n").unwrap();
+/// let diagnostics = linter.lint_scene(&scene);
+/// assert_eq!(
+///     diagnostics[0].to_string(),
+///     "1:1: warning[node-name-case]: node name is not PascalCase"
+/// );
+/// ```
+pub trait Lint {
+    /// A unique kebab-case name such as `unused-sub-resource`, used to configure the lint
+    fn name(&self) -> &'static str;
+
+    /// Checks a scene or resource file
+    fn check_scene(&self, _scene: &SceneFile) -> Vec<Finding> {
+        Vec::new()
+    }
+
+    /// Checks a `project.godot` file
+    fn check_project(&self, _project: &ProjectFile) -> Vec<Finding> {
+        Vec::new()
+    }
+}
+
+/// A lint and the severity of its findings, or `None` when it is turned off
+struct ConfiguredLint {
+    lint: Box<dyn Lint>,
+    severity: Option<Severity>,
+}
+
+/// Runs a set of lints
+pub struct Linter {
+    lints: Vec<ConfiguredLint>,
+}
+
+impl Linter {
+    /// Creates a linter with the built-in lints at their default severities, see
+    /// `rules::builtin`
+    pub fn new() -> Self {
+        Self {
+            lints: rules::builtin()
+                .into_iter()
+                .map(|(lint, severity)| ConfiguredLint {
+                    lint,
+                    severity: Some(severity),
+                })
+                .collect(),
+        }
+    }
+
+    /// Creates a linter without any lints, to run only the ones added with `add`
+    pub fn empty() -> Self {
+        Self { lints: Vec::new() }
+    }
+
+    /// Adds a lint reporting findings with the given severity
+    pub fn add(&mut self, lint: impl Lint + 'static, severity: Severity) -> &mut Self {
+        self.lints.push(ConfiguredLint {
+            lint: Box::new(lint),
+            severity: Some(severity),
+        });
+        self
+    }
+
+    /// Returns the names of the lints, in the order they were added
+    pub fn rules(&self) -> Vec<&'static str> {
+        self.lints.iter().map(|l| l.lint.name()).collect()
+    }
+
+    /// Changes the severity of a lint, or turns it off with `None`. Returns false if there
+    /// is no lint with that name.
+    pub fn set_severity(&mut self, rule: &str, severity: Option<Severity>) -> bool {
+        let mut found = false;
+        for configured in self.lints.iter_mut().filter(|l| l.lint.name() == rule) {
+            configured.severity = severity;
+            found = true;
+        }
+        found
+    }
+
+    /// Runs the lints over a scene or resource file
+    pub fn lint_scene(&self, scene: &SceneFile) -> Vec<Diagnostic> {
+        self.run(|lint| lint.check_scene(scene))
+    }
+
+    /// Runs the lints over a project file
+    pub fn lint_project(&self, project: &ProjectFile) -> Vec<Diagnostic> {
+        self.run(|lint| lint.check_project(project))
+    }
+
+    fn run(&self, check: impl Fn(&dyn Lint) -> Vec<Finding>) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for configured in &self.lints {
+            let Some(severity) = configured.severity else {
+                continue;
+            };
+            diagnostics.extend(check(configured.lint.as_ref()).into_iter().map(|finding| {
+                Diagnostic {
+                    rule: configured.lint.name().to_string(),
+                    severity,
+                    message: finding.message,
+                    location: finding.location,
+                }
+            }));
+        }
+        diagnostics.sort_by_key(|d| d.location.span.start);
+        diagnostics
+    }
+}
+
+/// A linter with the built-in lints, the same as `Linter::new`
+impl Default for Linter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
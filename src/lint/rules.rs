@@ -0,0 +1,326 @@
+//! The built-in lints.
+
+use super::{Finding, Lint, Severity};
+use crate::parsers::parser_property_file::Section;
+use crate::parsers::parser_scene_file::SceneFile;
+use crate::parsers::parser_value::Value;
+use crate::scene::node_path;
+use crate::scene::resources::ResourceKind;
+
+/// Returns the built-in lints with their default severities
+pub fn builtin() -> Vec<(Box<dyn Lint>, Severity)> {
+    vec![
+        (Box::new(UnusedSubResources), Severity::Warning),
+        (Box::new(UnusedExtResources), Severity::Warning),
+        (Box::new(DefaultValues::default()), Severity::Info),
+        (Box::new(DeprecatedNodeTypes::default()), Severity::Warning),
+        (Box::new(MissingScripts), Severity::Error),
+        (Box::new(DuplicateUniqueNames), Severity::Error),
+        (Box::new(EmptyConnections), Severity::Error),
+    ]
+}
+
+/// Resources of `kind` that no node, connection or `[resource]` section uses, directly or
+/// through other sub-resources
+fn unused_resources(scene: &SceneFile, kind: ResourceKind) -> Vec<Finding> {
+    let users: Vec<&Section> = scene
+        .all_sections
+        .iter()
+        .filter(|s| ResourceKind::of(s).is_none())
+        .collect();
+    let used = scene.resources_used_by(&users);
+    scene
+        .all_sections
+        .iter()
+        .filter(|s| ResourceKind::of(s) == Some(kind))
+        .filter_map(|s| Some((s, s.get("id")?)))
+        .filter(|(_, id)| !used.contains(&(kind, id.to_string())))
+        .map(|(section, id)| {
+            let message = match kind {
+                ResourceKind::Ext => format!(
+                    "ext_resource {} ({}) is not used",
+                    id,
+                    section.get("path").unwrap_or_default()
+                ),
+                ResourceKind::Sub => format!("sub_resource {} is not used", id),
+            };
+            Finding::new(message, section.location)
+        })
+        .collect()
+}
+
+/// `unused-sub-resource`: a `[sub_resource]` nothing refers to, which Godot still loads
+pub struct UnusedSubResources;
+
+impl Lint for UnusedSubResources {
+    fn name(&self) -> &'static str {
+        "unused-sub-resource"
+    }
+
+    fn check_scene(&self, scene: &SceneFile) -> Vec<Finding> {
+        unused_resources(scene, ResourceKind::Sub)
+    }
+}
+
+/// `unused-ext-resource`: an `[ext_resource]` nothing refers to, which Godot still loads
+pub struct UnusedExtResources;
+
+impl Lint for UnusedExtResources {
+    fn name(&self) -> &'static str {
+        "unused-ext-resource"
+    }
+
+    fn check_scene(&self, scene: &SceneFile) -> Vec<Finding> {
+        unused_resources(scene, ResourceKind::Ext)
+    }
+}
+
+/// `default-value`: a node property set to its default value, which the Godot editor
+/// never saves. Nodes without a `type` are skipped, since there the value overrides the
+/// instanced scene.
+pub struct DefaultValues {
+    /// Properties and the values that are their default on every node type that has them
+    pub defaults: Vec<(String, Vec<String>)>,
+}
+
+impl Default for DefaultValues {
+    fn default() -> Self {
+        let defaults: &[(&str, &[&str])] = &[
+            ("visible", &["true"]),
+            ("modulate", &["Color(1, 1, 1, 1)"]),
+            ("self_modulate", &["Color(1, 1, 1, 1)"]),
+            ("position", &["Vector2(0, 0)", "Vector3(0, 0, 0)"]),
+            ("rotation", &["0.0", "Vector3(0, 0, 0)"]),
+            ("scale", &["Vector2(1, 1)", "Vector3(1, 1, 1)"]),
+            ("skew", &["0.0"]),
+            ("z_index", &["0"]),
+            ("process_mode", &["0"]),
+            ("unique_name_in_owner", &["false"]),
+        ];
+        let defaults = defaults
+            .iter()
+            .map(|(key, values)| {
+                (
+                    key.to_string(),
+                    values.iter().map(|v| v.to_string()).collect(),
+                )
+            })
+            .collect();
+        Self { defaults }
+    }
+}
+
+/// Converts integers to floats, so that `Vector2(0, 0)` equals `Vector2(0.0, 0.0)`
+fn numbers_as_floats(mut value: Value) -> Value {
+    value.visit_mut(&mut |value| {
+        if let Value::Int(i) = *value {
+            *value = Value::Float(i as f64);
+        }
+    });
+    value
+}
+
+impl Lint for DefaultValues {
+    fn name(&self) -> &'static str {
+        "default-value"
+    }
+
+    fn check_scene(&self, scene: &SceneFile) -> Vec<Finding> {
+        let parse = |text: &str| Value::parse(text).map(numbers_as_floats);
+        let mut findings = Vec::new();
+        for node in scene
+            .nodes()
+            .into_iter()
+            .filter(|n| n.get("type").is_some())
+        {
+            for property in node.body().iter().filter(|p| !p.quoted) {
+                let Some((_, defaults)) =
+                    self.defaults.iter().find(|(key, _)| *key == property.key)
+                else {
+                    continue;
+                };
+                let value = parse(&property.value);
+                if value.is_some() && defaults.iter().any(|d| parse(d) == value) {
+                    findings.push(Finding::new(
+                        format!(
+                            "{} of node {} is set to its default value {}",
+                            property.key,
+                            node_path(node).unwrap_or_default(),
+                            property.value
+                        ),
+                        property.location(),
+                    ));
+                }
+            }
+        }
+        findings
+    }
+}
+
+/// `deprecated-node-type`: a node whose type was renamed in Godot 4 or is deprecated
+pub struct DeprecatedNodeTypes {
+    /// Deprecated types and what to use instead
+    pub types: Vec<(String, String)>,
+}
+
+impl Default for DeprecatedNodeTypes {
+    fn default() -> Self {
+        let types = [
+            ("Spatial", "Node3D"),
+            ("KinematicBody2D", "CharacterBody2D"),
+            ("KinematicBody", "CharacterBody3D"),
+            ("RigidBody", "RigidBody3D"),
+            ("StaticBody", "StaticBody3D"),
+            ("Area", "Area3D"),
+            ("CollisionShape", "CollisionShape3D"),
+            ("MeshInstance", "MeshInstance3D"),
+            ("Camera", "Camera3D"),
+            ("Sprite", "Sprite2D"),
+            ("AnimatedSprite", "AnimatedSprite2D"),
+            ("Position2D", "Marker2D"),
+            ("Position3D", "Marker3D"),
+            ("Particles2D", "GPUParticles2D"),
+            ("Particles", "GPUParticles3D"),
+            ("Light2D", "PointLight2D"),
+            ("YSort", "Node2D with y_sort_enabled"),
+            ("ViewportContainer", "SubViewportContainer"),
+            ("VisibilityNotifier2D", "VisibleOnScreenNotifier2D"),
+            ("VisibilityEnabler2D", "VisibleOnScreenEnabler2D"),
+            ("TextureProgress", "TextureProgressBar"),
+            ("ToolButton", "Button with flat"),
+            ("TileMap", "TileMapLayer"),
+            ("ParallaxBackground", "Parallax2D"),
+            ("ParallaxLayer", "Parallax2D"),
+        ];
+        Self {
+            types: types
+                .iter()
+                .map(|(old, new)| (old.to_string(), new.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl Lint for DeprecatedNodeTypes {
+    fn name(&self) -> &'static str {
+        "deprecated-node-type"
+    }
+
+    fn check_scene(&self, scene: &SceneFile) -> Vec<Finding> {
+        scene
+            .nodes()
+            .into_iter()
+            .filter_map(|node| {
+                let node_type = node.get("type")?;
+                let (_, replacement) = self.types.iter().find(|(old, _)| old == node_type)?;
+                Some(Finding::new(
+                    format!(
+                        "node {} uses deprecated type {}, use {} instead",
+                        node_path(node).unwrap_or_default(),
+                        node_type,
+                        replacement
+                    ),
+                    node.location,
+                ))
+            })
+            .collect()
+    }
+}
+
+/// `missing-script`: a `script` property referring to a resource the file does not declare
+pub struct MissingScripts;
+
+impl Lint for MissingScripts {
+    fn name(&self) -> &'static str {
+        "missing-script"
+    }
+
+    fn check_scene(&self, scene: &SceneFile) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        let sections = scene
+            .all_sections
+            .iter()
+            .filter(|s| matches!(s.header_type.as_ref(), "node" | "resource" | "sub_resource"));
+        for section in sections {
+            let Some(property) = section.body().iter().rfind(|p| p.key == "script") else {
+                continue;
+            };
+            let Some(value) = Value::parse(&property.value_text()) else {
+                continue;
+            };
+            let reference = match (value.as_ext_resource(), value.as_sub_resource()) {
+                (Some(id), _) => (ResourceKind::Ext, id.to_string()),
+                (_, Some(id)) => (ResourceKind::Sub, id.to_string()),
+                _ => continue,
+            };
+            if scene.resource_section(reference.0, &reference.1).is_none() {
+                let owner = match section.header_type.as_ref() {
+                    "node" => format!("node {}", node_path(section).unwrap_or_default()),
+                    other => other.to_string(),
+                };
+                findings.push(Finding::new(
+                    format!("script of {} refers to undeclared {}", owner, value),
+                    property.location(),
+                ));
+            }
+        }
+        findings
+    }
+}
+
+/// `duplicate-unique-name`: a `%Name` used by more than one node with the same owner
+pub struct DuplicateUniqueNames;
+
+impl Lint for DuplicateUniqueNames {
+    fn name(&self) -> &'static str {
+        "duplicate-unique-name"
+    }
+
+    fn check_scene(&self, scene: &SceneFile) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for duplicate in scene.duplicate_unique_names() {
+            for path in &duplicate.paths[1..] {
+                let Some(node) = scene.find_node(path) else {
+                    continue;
+                };
+                findings.push(Finding::new(
+                    format!(
+                        "unique name %{} of node {} is already used by {}",
+                        duplicate.name, path, duplicate.paths[0]
+                    ),
+                    node.location,
+                ));
+            }
+        }
+        findings
+    }
+}
+
+/// `empty-connection`: a `[connection]` without a signal, source, target or method
+pub struct EmptyConnections;
+
+impl Lint for EmptyConnections {
+    fn name(&self) -> &'static str {
+        "empty-connection"
+    }
+
+    fn check_scene(&self, scene: &SceneFile) -> Vec<Finding> {
+        scene
+            .connections()
+            .into_iter()
+            .filter_map(|connection| {
+                let missing: Vec<&str> = ["signal", "from", "to", "method"]
+                    .into_iter()
+                    .filter(|key| connection.get(key).is_none_or(str::is_empty))
+                    .collect();
+                if missing.is_empty() {
+                    return None;
+                }
+                Some(Finding::new(
+                    format!("connection has no {}", missing.join(", ")),
+                    connection.location,
+                ))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,746 @@
+//! Three-way merging of scenes, resources and project files.
+//!
+//! Sections are matched by what they describe rather than by line: nodes by their path,
+//! external resources by their `path`, sub-resources by their id, connections by signal,
+//! source, target and method, and other sections by their header. Within matched sections
+//! each property is merged on its own, so a conflict is only reported when both sides
+//! changed the same property differently, or one side changed a section the other removed.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::parsers::parser_project_file::ProjectFile;
+use crate::parsers::parser_property::UntypedProperty;
+use crate::parsers::parser_property_file::Section;
+use crate::parsers::parser_scene_file::SceneFile;
+use crate::scene::edit::set_attribute;
+use crate::scene::resources::{ResourceKind, id_number, id_suffix, rename_resource_refs};
+use crate::scene::{node_path, parent_path};
+
+/// The result of a three-way merge: the merged file, and the places where both sides made
+/// incompatible changes. Where there is a conflict, `merged` holds our side.
+///
+/// `Display` writes the merged file with Git-style conflict markers around each conflict.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeResult<T> {
+    pub merged: T,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl<T> MergeResult<T> {
+    /// Returns true if the merge had no conflicts
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// A change made on both sides that could not be merged
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeConflict {
+    /// The header of the section as merged, e.g. `[node name="Panel" parent="UI"]`, or an
+    /// empty string for properties before the first section
+    pub section: String,
+    /// The conflicting property, or `None` if one side removed the section and the other
+    /// changed it or added a node below it
+    pub key: Option<String>,
+    /// The value in the common ancestor, or the whole section text if `key` is `None`.
+    /// `None` where the property or section is missing.
+    pub base: Option<String>,
+    pub ours: Option<String>,
+    pub theirs: Option<String>,
+    /// Index into the merged sections, or `None` for properties before the first section
+    index: Option<usize>,
+    /// Whether the property is an attribute of the section header
+    attribute: bool,
+    /// Which of the properties named `key` in the section conflicts, counting from 0
+    occurrence: usize,
+}
+
+impl fmt::Display for MergeConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let section = if self.section.is_empty() {
+            "the top of the file"
+        } else {
+            &self.section
+        };
+        match &self.key {
+            Some(key) => write!(f, "{} changed on both sides in {}", key, section),
+            None if self.ours.is_none() => {
+                write!(
+                    f,
+                    "{} was removed on our side and changed on theirs",
+                    section
+                )
+            }
+            None => write!(
+                f,
+                "{} was changed on our side and removed on theirs",
+                section
+            ),
+        }
+    }
+}
+
+/// Merges two versions of a scene or resource file with their common ancestor.
+///
+/// Resource ids are reconciled first: an external resource both sides load is given our id,
+/// and resources added on their side whose ids collide with ours get new ids. `load_steps`
+/// is recomputed for the merged file rather than merged.
+///
+/// # Example
+///
+/// ```
+/// use godot_properties_parser::merge::merge_scenes;
+/// use godot_properties_parser::parse_scene_file;
+///
+/// let base = "[node name=\"Main\" type=\"Node2D\"]\n";
+/// let ours = "[node name=\"Main\" type=\"Node2D\"]\nposition = Vector2(1, 2)\n";
+/// let theirs = "[node name=\"Main\" type=\"Node2D\"]\nvisible = false\n";
+///
+/// let result = merge_scenes(
+///     &parse_scene_file(base).unwrap().1,
+///     &parse_scene_file(ours).unwrap().1,
+///     &parse_scene_file(theirs).unwrap().1,
+/// );
+/// assert!(result.is_clean());
+/// assert_eq!(
+///     result.merged.to_string(),
+///     "[node name=\"Main\" type=\"Node2D\"]\nposition = Vector2(1, 2)\nvisible = false\n"
+/// );
+/// ```
+pub fn merge_scenes(
+    base: &SceneFile,
+    ours: &SceneFile,
+    theirs: &SceneFile,
+) -> MergeResult<SceneFile<'static>> {
+    let ours = owned(&ours.all_sections);
+    let mut base = owned(&base.all_sections);
+    let mut theirs = owned(&theirs.all_sections);
+    align_ext_resources(&ours, &mut [&mut theirs, &mut base]);
+    align_sub_resources(&ours, &base, &mut theirs);
+    for sections in [&mut base, &mut theirs] {
+        for header in sections.iter_mut().filter(|s| is_file_header(s)) {
+            remove_load_steps(header);
+        }
+    }
+
+    let (sections, conflicts) = merge_sections(&base, &ours, &theirs, Style::Scene);
+    let mut merged = SceneFile {
+        all_sections: sections,
+    };
+    if merged
+        .header()
+        .is_some_and(|header| header.get("load_steps").is_some())
+    {
+        merged.update_load_steps();
+    }
+    MergeResult { merged, conflicts }
+}
+
+/// Merges two versions of a `project.godot` file with their common ancestor.
+///
+/// Properties before the first section, such as `config_version`, are merged like the
+/// properties of a section.
+pub fn merge_projects(
+    base: &ProjectFile,
+    ours: &ProjectFile,
+    theirs: &ProjectFile,
+) -> MergeResult<ProjectFile<'static>> {
+    let with_preamble = |file: &ProjectFile| {
+        let mut preamble = Section::new("");
+        preamble.properties = file.preamble_properties.clone();
+        std::iter::once(preamble.into_owned())
+            .chain(owned(&file.all_sections))
+            .collect::<Vec<_>>()
+    };
+    let (mut sections, mut conflicts) = merge_sections(
+        &with_preamble(base),
+        &with_preamble(ours),
+        &with_preamble(theirs),
+        Style::Project,
+    );
+
+    // The preamble always comes first, as all three files start with it
+    let preamble = sections.remove(0);
+    for conflict in &mut conflicts {
+        if conflict.index == Some(0) {
+            conflict.section.clear();
+        }
+        conflict.index = conflict.index.and_then(|i| i.checked_sub(1));
+    }
+    let merged = ProjectFile {
+        preamble_properties: preamble.properties,
+        all_sections: sections,
+    };
+    MergeResult { merged, conflicts }
+}
+
+fn owned(sections: &[Section]) -> Vec<Section<'static>> {
+    sections.iter().cloned().map(Section::into_owned).collect()
+}
+
+fn is_file_header(section: &Section) -> bool {
+    matches!(section.header_type.as_ref(), "gd_scene" | "gd_resource")
+}
+
+fn remove_load_steps(header: &mut Section) {
+    if let Some(index) = header
+        .attributes()
+        .iter()
+        .position(|p| p.key == "load_steps")
+    {
+        header.properties.remove(index);
+        header.attribute_count -= 1;
+    }
+}
+
+/// Gives external resources the same id in every file: our id where we load the same path,
+/// otherwise the file's own id unless another path already uses it
+fn align_ext_resources(ours: &[Section], others: &mut [&mut Vec<Section<'static>>]) {
+    let mut ids: BTreeMap<String, String> = BTreeMap::new();
+    let mut taken = BTreeSet::new();
+    for ext in ours.iter().filter(|s| s.header_type == "ext_resource") {
+        if let (Some(path), Some(id)) = (ext.get("path"), ext.get("id")) {
+            ids.entry(path.to_string())
+                .or_insert_with(|| id.to_string());
+            taken.insert(id.to_string());
+        }
+    }
+
+    for sections in others.iter_mut() {
+        let mut renamed = BTreeMap::new();
+        for ext in sections.iter().filter(|s| s.header_type == "ext_resource") {
+            let (Some(path), Some(id)) = (ext.get("path"), ext.get("id")) else {
+                continue;
+            };
+            let new_id = ids.entry(path.to_string()).or_insert_with(|| {
+                let new_id = if taken.contains(id) {
+                    let number = taken.iter().filter_map(|id| id_number(id)).max();
+                    format!("{}_{}", number.unwrap_or(0) + 1, id_suffix(path))
+                } else {
+                    id.to_string()
+                };
+                taken.insert(new_id.clone());
+                new_id
+            });
+            if new_id != id {
+                renamed.insert(id.to_string(), new_id.clone());
+            }
+        }
+        rename_resources(sections, ResourceKind::Ext, &renamed);
+    }
+}
+
+/// Gives sub-resources that both sides added under the same id, with different contents,
+/// a new id on their side
+fn align_sub_resources(ours: &[Section], base: &[Section], theirs: &mut Vec<Section<'static>>) {
+    let subs = |sections: &[Section]| -> BTreeMap<String, usize> {
+        sections
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.header_type == "sub_resource")
+            .filter_map(|(i, s)| Some((s.get("id")?.to_string(), i)))
+            .collect()
+    };
+    let (our_subs, base_subs) = (subs(ours), subs(base));
+    let mut taken: BTreeSet<String> = our_subs.keys().cloned().collect();
+    taken.extend(subs(theirs).into_keys());
+
+    let mut renamed = BTreeMap::new();
+    for (id, i) in subs(theirs) {
+        let collides = our_subs
+            .get(&id)
+            .is_some_and(|&j| !base_subs.contains_key(&id) && ours[j] != theirs[i]);
+        if !collides {
+            continue;
+        }
+        let resource_type = theirs[i].get("type").unwrap_or("Resource");
+        let mut seed = format!("theirs/{}", id);
+        let new_id = loop {
+            let new_id = format!("{}_{}", resource_type, id_suffix(&seed));
+            if !taken.contains(&new_id) {
+                break new_id;
+            }
+            seed.push('/');
+        };
+        taken.insert(new_id.clone());
+        renamed.insert(id, new_id);
+    }
+    rename_resources(theirs, ResourceKind::Sub, &renamed);
+}
+
+/// Renames the resources of one kind, both their `id` attributes and the references to them
+fn rename_resources(
+    sections: &mut [Section],
+    kind: ResourceKind,
+    renamed: &BTreeMap<String, String>,
+) {
+    if renamed.is_empty() {
+        return;
+    }
+    for section in sections.iter_mut() {
+        rename_resource_refs(section, &|k, id| {
+            if k == kind {
+                renamed.get(id).cloned()
+            } else {
+                None
+            }
+        });
+        if ResourceKind::of(section) == Some(kind)
+            && let Some(new_id) = section.get("id").and_then(|id| renamed.get(id)).cloned()
+        {
+            set_attribute(section, "id", &new_id);
+        }
+    }
+}
+
+/// What a section describes, used to match it across the three files
+pub(crate) fn identity(section: &Section) -> String {
+    let attribute = |key| section.get(key).unwrap_or_default();
+    match section.header_type.as_ref() {
+        "ext_resource" => format!("ext_resource {}", attribute("path")),
+        "sub_resource" => format!("sub_resource {}", attribute("id")),
+        "node" => format!("node {}", node_path(section).unwrap_or_default()),
+        "connection" => format!(
+            "connection {} {} {} {}",
+            attribute("signal"),
+            attribute("from"),
+            attribute("to"),
+            attribute("method")
+        ),
+        "editable" => format!("editable {}", attribute("path")),
+        other => other.to_string(),
+    }
+}
+
+/// Pairs each item with a key that is unique within the list, numbering repeated keys
+fn keyed<T>(items: &[T], key: impl Fn(&T) -> String) -> Vec<(String, &T)> {
+    let mut seen: BTreeMap<String, usize> = BTreeMap::new();
+    items
+        .iter()
+        .map(|item| {
+            let key = key(item);
+            let count = seen.entry(key.clone()).or_default();
+            *count += 1;
+            match *count {
+                1 => (key, item),
+                n => (format!("{}#{}", key, n), item),
+            }
+        })
+        .collect()
+}
+
+fn keys<T>(items: &[(String, &T)]) -> Vec<String> {
+    items.iter().map(|(key, _)| key.clone()).collect()
+}
+
+/// Indexes keyed items by their key
+fn index<'k, 's, T>(items: &'k [(String, &'s T)]) -> BTreeMap<&'k str, &'s T> {
+    items
+        .iter()
+        .map(|(key, item)| (key.as_str(), *item))
+        .collect()
+}
+
+/// Orders the keys of both sides: ours as they are, with each key only theirs have placed
+/// after the key that precedes it on their side and after the keys only we have there
+fn merged_order(ours: &[String], theirs: &[String]) -> Vec<String> {
+    let our_keys: BTreeSet<&str> = ours.iter().map(String::as_str).collect();
+    let their_keys: BTreeSet<&str> = theirs.iter().map(String::as_str).collect();
+
+    // The keys only theirs have, by the last key both have before them on their side
+    let mut added: BTreeMap<Option<&str>, Vec<&String>> = BTreeMap::new();
+    let mut previous = None;
+    for key in theirs {
+        if our_keys.contains(key.as_str()) {
+            previous = Some(key.as_str());
+        } else {
+            added.entry(previous).or_default().push(key);
+        }
+    }
+
+    let mut order = Vec::with_capacity(ours.len() + theirs.len());
+    let mut pending = added.remove(&None);
+    for key in ours {
+        // After anything we added in the same place
+        if their_keys.contains(key.as_str()) {
+            order.extend(pending.take().into_iter().flatten().cloned());
+        }
+        order.push(key.clone());
+        if let Some(keys) = added.remove(&Some(key.as_str())) {
+            pending = Some(keys);
+        }
+    }
+    order.extend(pending.into_iter().flatten().cloned());
+    order
+}
+
+/// Picks the merged version of something that may have changed on either side, or `None`
+/// if both sides changed it differently
+fn resolve<'v, T: PartialEq>(
+    base: Option<&'v T>,
+    ours: Option<&'v T>,
+    theirs: Option<&'v T>,
+) -> Option<Option<&'v T>> {
+    if ours == theirs || theirs == base {
+        Some(ours)
+    } else if ours == base {
+        Some(theirs)
+    } else {
+        None
+    }
+}
+
+fn merge_sections(
+    base: &[Section<'static>],
+    ours: &[Section<'static>],
+    theirs: &[Section<'static>],
+    style: Style,
+) -> (Vec<Section<'static>>, Vec<MergeConflict>) {
+    let (base, ours, theirs) = (
+        keyed(base, identity),
+        keyed(ours, identity),
+        keyed(theirs, identity),
+    );
+    let order = merged_order(&keys(&ours), &keys(&theirs));
+    let base_index = index(&base);
+
+    // The paths of the nodes that `side` added a node below, which counts as changing them
+    let added_below = |side: &[(String, &Section)]| {
+        let mut paths: BTreeSet<String> = BTreeSet::new();
+        for (key, added) in side {
+            if base_index.contains_key(key.as_str()) || added.header_type != "node" {
+                continue;
+            }
+            let Some(path) = node_path(added) else {
+                continue;
+            };
+            let mut current = path.as_str();
+            // Stop at an ancestor that is already known, and so are all of its ancestors
+            while let Some(parent) = parent_path(current)
+                && paths.insert(parent.to_string())
+            {
+                current = parent;
+            }
+        }
+        paths
+    };
+    let (added_below_ours, added_below_theirs) = (added_below(&ours), added_below(&theirs));
+    let (our_index, their_index) = (index(&ours), index(&theirs));
+
+    let mut merged = Vec::new();
+    let mut conflicts = Vec::new();
+    for key in order {
+        let key = key.as_str();
+        let (b, o, t) = (
+            base_index.get(key).copied(),
+            our_index.get(key).copied(),
+            their_index.get(key).copied(),
+        );
+        let index = merged.len();
+        let section = match (b, o, t) {
+            (_, Some(o), Some(t)) => {
+                let empty = Section::new(o.header_type.clone());
+                merge_section(b.unwrap_or(&empty), o, t, index, &mut conflicts)
+            }
+            (Some(b), Some(kept), None) | (Some(b), None, Some(kept)) => {
+                let added_below = if o.is_some() {
+                    &added_below_ours
+                } else {
+                    &added_below_theirs
+                };
+                let has_added_below = kept.header_type == "node"
+                    && node_path(kept).is_some_and(|path| added_below.contains(&path));
+                if kept == b && !has_added_below {
+                    continue;
+                }
+                conflicts.push(MergeConflict {
+                    section: header_line(kept),
+                    key: None,
+                    base: Some(style.section(b)),
+                    ours: o.map(|o| style.section(o)),
+                    theirs: t.map(|t| style.section(t)),
+                    index: Some(index),
+                    attribute: false,
+                    occurrence: 0,
+                });
+                kept.clone()
+            }
+            (None, Some(added), None) | (None, None, Some(added)) => added.clone(),
+            _ => continue,
+        };
+        merged.push(section);
+    }
+    (merged, conflicts)
+}
+
+fn merge_section(
+    base: &Section<'static>,
+    ours: &Section<'static>,
+    theirs: &Section<'static>,
+    index: usize,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Section<'static> {
+    let mut pending = Vec::new();
+    let mut attributes = merge_properties(
+        base.attributes(),
+        ours.attributes(),
+        theirs.attributes(),
+        true,
+        &mut pending,
+    );
+    let body = merge_properties(base.body(), ours.body(), theirs.body(), false, &mut pending);
+
+    let mut section = Section::new(ours.header_type.clone());
+    section.attribute_count = attributes.len();
+    attributes.extend(body);
+    section.properties = attributes;
+    section.location = ours.location;
+
+    let header = header_line(&section);
+    conflicts.extend(pending.into_iter().map(|mut conflict| {
+        conflict.section = header.clone();
+        conflict.index = Some(index);
+        conflict
+    }));
+    section
+}
+
+fn merge_properties(
+    base: &[UntypedProperty<'static>],
+    ours: &[UntypedProperty<'static>],
+    theirs: &[UntypedProperty<'static>],
+    attribute: bool,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Vec<UntypedProperty<'static>> {
+    let key = |p: &UntypedProperty| p.key.to_string();
+    let (base, ours, theirs) = (keyed(base, key), keyed(ours, key), keyed(theirs, key));
+    let order = merged_order(&keys(&ours), &keys(&theirs));
+    let (base, ours, theirs) = (index(&base), index(&ours), index(&theirs));
+    let value = |property: Option<&UntypedProperty>| property.map(|p| p.value_text().into_owned());
+
+    let mut merged = Vec::new();
+    for key in order {
+        let (b, o, t) = (
+            base.get(key.as_str()).copied(),
+            ours.get(key.as_str()).copied(),
+            theirs.get(key.as_str()).copied(),
+        );
+        let (vb, vo, vt) = (value(b), value(o), value(t));
+        match resolve(vb.as_ref(), vo.as_ref(), vt.as_ref()) {
+            Some(chosen) => {
+                let property = if chosen == vo.as_ref() { o } else { t };
+                merged.extend(property.cloned());
+            }
+            None => {
+                let name = o.or(t).map_or(key.clone(), |p| p.key.to_string());
+                // Repeated keys are numbered `key#2`, `key#3` and so on by `keyed`
+                let occurrence = key[name.len()..]
+                    .strip_prefix('#')
+                    .and_then(|n| n.parse::<usize>().ok())
+                    .map_or(0, |n| n - 1);
+                conflicts.push(MergeConflict {
+                    section: String::new(),
+                    key: Some(name),
+                    base: vb,
+                    ours: vo,
+                    theirs: vt,
+                    index: None,
+                    attribute,
+                    occurrence,
+                });
+                merged.extend(o.cloned());
+            }
+        }
+    }
+    merged
+}
+
+/// The first line of a section as written in a scene
+fn header_line(section: &Section) -> String {
+    let text = section.to_string();
+    text.lines().next().unwrap_or_default().to_string()
+}
+
+/// How the sections and properties of a file are written
+#[derive(Clone, Copy, PartialEq)]
+enum Style {
+    /// `key = value` lines, `[type attributes]` headers
+    Scene,
+    /// `key=value` lines, `[name]` headers followed by a blank line
+    Project,
+}
+
+impl Style {
+    fn property(self, key: &str, value: &str) -> String {
+        match self {
+            Style::Scene => format!("{} = {}\n", key, value),
+            Style::Project => format!("{}={}\n", key, value),
+        }
+    }
+
+    fn header(self, section: &Section) -> String {
+        match self {
+            Style::Scene => format!("{}\n", header_line(section)),
+            Style::Project => format!("[{}]\n\n", section.header_type),
+        }
+    }
+
+    fn section(self, section: &Section) -> String {
+        let body = section.body().iter();
+        body.fold(self.header(section), |text, property| {
+            text + &self.property(&property.key, &property.value_text())
+        })
+    }
+}
+
+fn write_conflict(f: &mut fmt::Formatter<'_>, ours: &str, theirs: &str) -> fmt::Result {
+    write!(
+        f,
+        "<<<<<<< ours\n{}=======\n{}>>>>>>> theirs\n",
+        ours, theirs
+    )
+}
+
+/// Writes the properties of a section, surrounding conflicting ones with markers
+fn write_properties(
+    f: &mut fmt::Formatter<'_>,
+    properties: &[UntypedProperty],
+    conflicts: &[&MergeConflict],
+    style: Style,
+) -> fmt::Result {
+    let line = |key: &str, value: &Option<String>| {
+        value
+            .as_ref()
+            .map_or(String::new(), |value| style.property(key, value))
+    };
+    let mut seen: BTreeMap<&str, usize> = BTreeMap::new();
+    for property in properties {
+        let occurrence = seen.entry(&property.key).or_default();
+        let conflict = conflicts
+            .iter()
+            .find(|c| c.key.as_deref() == Some(&property.key) && c.occurrence == *occurrence);
+        *occurrence += 1;
+        match conflict {
+            Some(conflict) => write_conflict(
+                f,
+                &line(&property.key, &conflict.ours),
+                &line(&property.key, &conflict.theirs),
+            )?,
+            None => write!(
+                f,
+                "{}",
+                style.property(&property.key, &property.value_text())
+            )?,
+        }
+    }
+    // Properties we removed and they changed
+    for conflict in conflicts {
+        let key = conflict.key.as_deref().unwrap_or_default();
+        if seen
+            .get(key)
+            .is_none_or(|&count| count <= conflict.occurrence)
+        {
+            write_conflict(f, "", &line(key, &conflict.theirs))?;
+        }
+    }
+    Ok(())
+}
+
+fn write_section(
+    f: &mut fmt::Formatter<'_>,
+    section: &Section,
+    conflicts: &[&MergeConflict],
+    style: Style,
+) -> fmt::Result {
+    if let Some(conflict) = conflicts.iter().find(|c| c.key.is_none()) {
+        let text = |side: &Option<String>| side.clone().unwrap_or_default();
+        return write_conflict(f, &text(&conflict.ours), &text(&conflict.theirs));
+    }
+
+    let (attribute_conflicts, body_conflicts): (Vec<&MergeConflict>, Vec<&MergeConflict>) =
+        conflicts.iter().partition(|c| c.attribute);
+    if attribute_conflicts.is_empty() {
+        f.write_str(&style.header(section))?;
+    } else {
+        let mut theirs = section.clone();
+        theirs.properties.truncate(section.attribute_count);
+        for conflict in &attribute_conflicts {
+            let key = conflict.key.as_deref().unwrap_or_default();
+            let position = theirs
+                .properties
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| p.key == key)
+                .nth(conflict.occurrence)
+                .map(|(i, _)| i);
+            match (&conflict.theirs, position) {
+                (Some(value), Some(i)) => theirs.properties[i] = theirs_property(key, value),
+                (Some(value), None) => theirs.properties.push(theirs_property(key, value)),
+                (None, Some(i)) => {
+                    theirs.properties.remove(i);
+                }
+                (None, None) => {}
+            }
+        }
+        theirs.attribute_count = theirs.properties.len();
+        write_conflict(f, &style.header(section), &style.header(&theirs))?;
+    }
+    write_properties(f, section.body(), &body_conflicts, style)
+}
+
+/// Rebuilds a property from its written value text
+fn theirs_property(key: &str, value: &str) -> UntypedProperty<'static> {
+    match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(inner) => UntypedProperty::new_quoted(key.to_string(), inner.to_string()),
+        None => UntypedProperty::new(key.to_string(), value.to_string()),
+    }
+}
+
+fn conflicts_at(conflicts: &[MergeConflict], index: Option<usize>) -> Vec<&MergeConflict> {
+    conflicts.iter().filter(|c| c.index == index).collect()
+}
+
+/// Writes the merged scene with conflict markers
+impl fmt::Display for MergeResult<SceneFile<'_>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, section) in self.merged.all_sections.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write_section(
+                f,
+                section,
+                &conflicts_at(&self.conflicts, Some(i)),
+                Style::Scene,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the merged project file with conflict markers
+impl fmt::Display for MergeResult<ProjectFile<'_>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let preamble = &self.merged.preamble_properties;
+        write_properties(
+            f,
+            preamble,
+            &conflicts_at(&self.conflicts, None),
+            Style::Project,
+        )?;
+        let has_preamble = !preamble.is_empty() || self.conflicts.iter().any(|c| c.index.is_none());
+        for (i, section) in self.merged.all_sections.iter().enumerate() {
+            if i > 0 || has_preamble {
+                writeln!(f)?;
+            }
+            write_section(
+                f,
+                section,
+                &conflicts_at(&self.conflicts, Some(i)),
+                Style::Project,
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,584 @@
+use std::fmt;
+
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::{
+    self, DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+use serde::{Deserialize, forward_to_deserialize_any};
+
+use super::parser_project_file::ProjectFile;
+use super::parser_property::UntypedProperty;
+use super::parser_property_file::Section;
+use super::parser_value::{Value, unescape};
+
+/// Error returned when a section or value does not match the type it is deserialized into
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeserializeError {
+    /// The key of the property that failed, with `/` between nested keys
+    pub key: Option<String>,
+    pub message: String,
+}
+
+impl DeserializeError {
+    /// Records that the error happened below `key`
+    fn within(mut self, key: &str) -> Self {
+        self.key = Some(match self.key {
+            Some(inner) => format!("{}/{}", key, inner),
+            None => key.to_string(),
+        });
+        self
+    }
+}
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.key {
+            Some(key) => write!(f, "{}: {}", key, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for DeserializeError {}
+
+impl de::Error for DeserializeError {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        DeserializeError {
+            key: None,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Deserializes a type from the properties of a section.
+///
+/// Each property is a field. Values are read as Godot values, so `1920` fills an integer,
+/// `Vector2(1, 2)` fills a tuple, array or `struct { x, y }`, and `"text"` a string. Keys
+/// with `/` can be matched whole with `#[serde(rename = "window/size/viewport_width")]`,
+/// or as nested structs: a field `window` collects every key starting with `window/`.
+///
+/// # Example
+///
+/// ```
+/// use godot_properties_parser::parse_project_file;
+/// use godot_properties_parser::parsers::de::from_section;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct WindowSettings {
+///     #[serde(rename = "window/size/viewport_width")]
+///     viewport_width: i32,
+/// }
+///
+/// let (_, project) = parse_project_file("[display]\nwindow/size/viewport_width=1920\n").unwrap();
+/// let settings: WindowSettings = from_section(project.section("display").unwrap()).unwrap();
+/// assert_eq!(settings.viewport_width, 1920);
+/// ```
+pub fn from_section<'de, T>(section: &'de Section<'_>) -> Result<T, DeserializeError>
+where
+    T: Deserialize<'de>,
+{
+    T::deserialize(Properties::from_sections([section]))
+}
+
+/// Deserializes a type from a project file, with a field for each section.
+///
+/// Sections that appear more than once are merged the way Godot does, and properties
+/// before the first section (such as `config_version`) are fields as well.
+pub fn from_project_file<'de, T>(project: &'de ProjectFile<'_>) -> Result<T, DeserializeError>
+where
+    T: Deserialize<'de>,
+{
+    let mut properties = Properties::from_properties(
+        project
+            .preamble_properties
+            .iter()
+            .map(|p| (p.key.as_ref(), RawValueDeserializer::of(p))),
+    );
+
+    for section in &project.all_sections {
+        let name = section.header_type.as_ref();
+        if properties.entries.iter().any(|(key, _)| *key == name) {
+            continue;
+        }
+        let section = Properties::from_sections(project.sections(name));
+        properties.entries.push((name, Entry::Group(section)));
+    }
+
+    T::deserialize(properties)
+}
+
+/// Deserializes a type from a single Godot value, such as `Vector2(1, 2)` or `[1, 2, 3]`
+pub fn from_str<'de, T>(input: &str) -> Result<T, DeserializeError>
+where
+    T: Deserialize<'de>,
+{
+    let value = Value::parse(input).ok_or_else(|| {
+        <DeserializeError as de::Error>::custom(format!("invalid value `{}`", input))
+    })?;
+    T::deserialize(ValueDeserializer(&value))
+}
+
+/// A property value, or the properties grouped below a key
+enum Entry<'de> {
+    Raw(RawValueDeserializer<'de>),
+    Group(Properties<'de>),
+}
+
+/// Key-value pairs read as a map or struct
+struct Properties<'de> {
+    entries: Vec<(&'de str, Entry<'de>)>,
+}
+
+impl<'de> Properties<'de> {
+    fn from_sections<'s: 'de>(sections: impl IntoIterator<Item = &'de Section<'s>>) -> Self {
+        Self::from_properties(
+            sections
+                .into_iter()
+                .flat_map(|section| &section.properties)
+                .map(|p| (p.key.as_ref(), RawValueDeserializer::of(p))),
+        )
+    }
+
+    /// Collects properties the way Godot's `ConfigFile` does: a repeated key keeps its
+    /// position and takes the later value
+    fn from_properties(
+        properties: impl Iterator<Item = (&'de str, RawValueDeserializer<'de>)>,
+    ) -> Self {
+        let mut entries: Vec<(&'de str, Entry<'de>)> = Vec::new();
+        for (key, value) in properties {
+            match entries.iter_mut().find(|(existing, _)| *existing == key) {
+                Some(entry) => entry.1 = Entry::Raw(value),
+                None => entries.push((key, Entry::Raw(value))),
+            }
+        }
+        Self { entries }
+    }
+
+    /// Adds a group for every field that is not a key itself but prefixes other keys
+    fn group_fields(&mut self, fields: &'static [&'static str]) {
+        for field in fields {
+            if self.entries.iter().any(|(key, _)| key == field) {
+                continue;
+            }
+            let nested: Vec<_> = self
+                .entries
+                .iter()
+                .filter_map(|(key, entry)| match entry {
+                    Entry::Raw(value) => {
+                        Some((key.strip_prefix(field)?.strip_prefix('/')?, *value))
+                    }
+                    Entry::Group(_) => None,
+                })
+                .collect();
+            if !nested.is_empty() {
+                let group = Properties::from_properties(nested.into_iter());
+                self.entries.push((field, Entry::Group(group)));
+            }
+        }
+    }
+}
+
+impl<'de> Deserializer<'de> for Properties<'de> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(EntriesAccess {
+            entries: self.entries.into_iter(),
+            value: None,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        mut self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.group_fields(fields);
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map enum identifier ignored_any
+    }
+}
+
+struct EntriesAccess<'de> {
+    entries: std::vec::IntoIter<(&'de str, Entry<'de>)>,
+    value: Option<(&'de str, Entry<'de>)>,
+}
+
+impl<'de> MapAccess<'de> for EntriesAccess<'de> {
+    type Error = DeserializeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some((key, entry)) = self.entries.next() else {
+            return Ok(None);
+        };
+        self.value = Some((key, entry));
+        seed.deserialize(BorrowedStrDeserializer::new(key))
+            .map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (key, entry) = self
+            .value
+            .take()
+            .ok_or_else(|| de::Error::custom("value requested before key"))?;
+        let result = match entry {
+            Entry::Raw(raw) => seed.deserialize(raw),
+            Entry::Group(group) => seed.deserialize(group),
+        };
+        result.map_err(|e| e.within(key))
+    }
+}
+
+/// The text of a property value. Unquoted text is read as a Godot value when it is one and
+/// as a string otherwise; quoted text is always a string, so `"42"` does not fill a number.
+#[derive(Clone, Copy)]
+struct RawValueDeserializer<'de> {
+    text: &'de str,
+    quoted: bool,
+}
+
+impl<'de> RawValueDeserializer<'de> {
+    fn of(property: &'de UntypedProperty<'_>) -> Self {
+        Self {
+            text: &property.value,
+            quoted: property.quoted,
+        }
+    }
+
+    fn parse(&self) -> Result<Value, DeserializeError> {
+        if self.quoted {
+            return Ok(Value::String(unescape(self.text)));
+        }
+        Value::parse(self.text)
+            .ok_or_else(|| de::Error::custom(format!("invalid value `{}`", self.text)))
+    }
+}
+
+impl<'de> Deserializer<'de> for RawValueDeserializer<'de> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.parse() {
+            Ok(value) if !self.quoted => ValueDeserializer(&value).deserialize_any(visitor),
+            _ => self.deserialize_str(visitor),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if !self.quoted
+            && let Some(Value::String(s) | Value::StringName(s) | Value::NodePath(s)) =
+                Value::parse(self.text)
+        {
+            return visitor.visit_string(s);
+        }
+        if self.text.contains('\\') {
+            return visitor.visit_string(unescape(self.text));
+        }
+        visitor.visit_borrowed_str(self.text)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.text {
+            "null" if !self.quoted => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.parse() {
+            Ok(value) => ValueDeserializer(&value).deserialize_enum(name, variants, visitor),
+            Err(_) => visitor.visit_enum(self.text.into_deserializer()),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        ValueDeserializer(&self.parse()?).deserialize_struct(name, fields, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map ignored_any
+    }
+}
+
+/// A parsed Godot value
+struct ValueDeserializer<'v>(&'v Value);
+
+impl<'de> Deserializer<'de> for ValueDeserializer<'_> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if let Some(items) = self.0.as_array() {
+            return visitor.visit_seq(ItemsAccess(items.iter()));
+        }
+        match self.0 {
+            Value::Nil => visitor.visit_unit(),
+            Value::Bool(b) => visitor.visit_bool(*b),
+            Value::Int(i) => visitor.visit_i64(*i),
+            Value::Float(f) => visitor.visit_f64(*f),
+            Value::String(s) | Value::StringName(s) | Value::NodePath(s) => visitor.visit_str(s),
+            Value::Dictionary(entries) => visitor.visit_map(PairsAccess {
+                pairs: entries
+                    .iter()
+                    .map(|(k, v)| (Key::Value(k), v))
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+                value: None,
+            }),
+            Value::Object { properties, .. } => visitor.visit_map(PairsAccess {
+                pairs: properties
+                    .iter()
+                    .map(|(k, v)| (Key::Str(k), v))
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+                value: None,
+            }),
+            Value::Constructor { args, .. } => visitor.visit_seq(ItemsAccess(args.iter())),
+            Value::Array(_) => unreachable!("arrays are handled by as_array"),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::Nil => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        // Constructor arguments fill the fields in order, e.g. `Vector2(1, 2)` as `{ x, y }`
+        match self.0 {
+            Value::Constructor { args, .. } => visitor.visit_map(PairsAccess {
+                pairs: fields
+                    .iter()
+                    .zip(args)
+                    .map(|(field, arg)| (Key::Str(field), arg))
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+                value: None,
+            }),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::String(s) | Value::StringName(s) => {
+                visitor.visit_enum(s.as_str().into_deserializer())
+            }
+            Value::Constructor { name, args } => {
+                visitor.visit_enum(ConstructorAccess { name, args })
+            }
+            other => Err(de::Error::custom(format!(
+                "expected an enum, found {:?}",
+                other
+            ))),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map identifier ignored_any
+    }
+}
+
+struct ItemsAccess<'v>(std::slice::Iter<'v, Value>);
+
+impl<'de> SeqAccess<'de> for ItemsAccess<'_> {
+    type Error = DeserializeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        self.0
+            .next()
+            .map(|item| seed.deserialize(ValueDeserializer(item)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+/// The key of a dictionary entry, object property or constructor argument
+enum Key<'v> {
+    Value(&'v Value),
+    Str(&'v str),
+}
+
+struct PairsAccess<'v> {
+    pairs: std::vec::IntoIter<(Key<'v>, &'v Value)>,
+    value: Option<&'v Value>,
+}
+
+impl<'de> MapAccess<'de> for PairsAccess<'_> {
+    type Error = DeserializeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some((key, value)) = self.pairs.next() else {
+            return Ok(None);
+        };
+        self.value = Some(value);
+        match key {
+            Key::Value(key) => seed.deserialize(ValueDeserializer(key)).map(Some),
+            Key::Str(key) => seed.deserialize(key.into_deserializer()).map(Some),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| de::Error::custom("value requested before key"))?;
+        seed.deserialize(ValueDeserializer(value))
+    }
+}
+
+/// A constructor read as an enum variant: its name selects the variant and its arguments
+/// are the variant's content
+struct ConstructorAccess<'v> {
+    name: &'v str,
+    args: &'v [Value],
+}
+
+impl<'de, 'v> EnumAccess<'de> for ConstructorAccess<'v> {
+    type Error = DeserializeError;
+    type Variant = Arguments<'v>;
+
+    fn variant_seed<S: DeserializeSeed<'de>>(
+        self,
+        seed: S,
+    ) -> Result<(S::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(self.name.into_deserializer())?;
+        Ok((variant, Arguments(self.args)))
+    }
+}
+
+/// The arguments of a constructor read as an enum variant
+struct Arguments<'v>(&'v [Value]);
+
+impl<'de> VariantAccess<'de> for Arguments<'_> {
+    type Error = DeserializeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        match self.0 {
+            [] => Ok(()),
+            _ => Err(de::Error::invalid_length(self.0.len(), &"no arguments")),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        match self.0 {
+            [arg] => seed.deserialize(ValueDeserializer(arg)),
+            _ => Err(de::Error::invalid_length(self.0.len(), &"one argument")),
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(ItemsAccess(self.0.iter()))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(PairsAccess {
+            pairs: fields
+                .iter()
+                .zip(self.0)
+                .map(|(field, arg)| (Key::Str(field), arg))
+                .collect::<Vec<_>>()
+                .into_iter(),
+            value: None,
+        })
+    }
+}
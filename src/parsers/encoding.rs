@@ -0,0 +1,154 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use super::parser_property_file::ParseError;
+
+/// The UTF-8 byte order mark some Windows editors write at the start of a file
+pub const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// A run of invalid UTF-8 bytes found while decoding raw input.
+///
+/// The sequence is replaced with U+FFFD in the decoded text so the rest of the file
+/// still parses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodingDiagnostic {
+    /// Byte offset of the sequence in the raw input
+    pub offset: usize,
+    /// 1-based line of the sequence
+    pub line: usize,
+    /// 1-based column of the sequence, counted in characters
+    pub column: usize,
+    /// The bytes that are not valid UTF-8
+    pub bytes: Vec<u8>,
+}
+
+impl fmt::Display for EncodingDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: invalid UTF-8 sequence", self.line, self.column)?;
+        for byte in &self.bytes {
+            write!(f, " {byte:#04x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Raw input decoded into text that the parsers accept
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedInput<'a> {
+    /// The decoded text. Borrowed from the input when it is valid UTF-8 without a byte
+    /// order mark or carriage returns.
+    pub text: Cow<'a, str>,
+    /// Runs of invalid UTF-8, in input order
+    pub diagnostics: Vec<EncodingDiagnostic>,
+}
+
+/// Decodes raw file contents into text.
+///
+/// A leading UTF-8 byte order mark is removed, `\r\n` and lone `\r` line endings become
+/// `\n`, and each run of invalid UTF-8 is replaced with a single U+FFFD and reported as an
+/// `EncodingDiagnostic` instead of failing the whole file.
+pub fn decode(input: &[u8]) -> DecodedInput<'_> {
+    decode_from(input, 0, 1)
+}
+
+/// Decodes `input` that starts at byte `offset` and on line `line` of a larger stream
+pub(crate) fn decode_from(input: &[u8], offset: usize, line: usize) -> DecodedInput<'_> {
+    let (bytes, bom_len) = match input.strip_prefix(UTF8_BOM) {
+        Some(rest) if offset == 0 => (rest, UTF8_BOM.len()),
+        _ => (input, 0),
+    };
+
+    if !bytes.contains(&b'\r')
+        && let Ok(text) = std::str::from_utf8(bytes)
+    {
+        return DecodedInput {
+            text: Cow::Borrowed(text),
+            diagnostics: Vec::new(),
+        };
+    }
+
+    let mut text = String::with_capacity(bytes.len());
+    let mut diagnostics: Vec<EncodingDiagnostic> = Vec::new();
+    let mut line = line;
+    let mut line_start = 0;
+    let mut position = offset + bom_len;
+
+    for chunk in bytes.utf8_chunks() {
+        let valid = chunk.valid();
+        push_normalized(&mut text, valid);
+        position += valid.len();
+
+        let invalid = chunk.invalid();
+        if invalid.is_empty() {
+            continue;
+        }
+
+        // Adjacent invalid sequences are reported and replaced together
+        if valid.is_empty()
+            && let Some(last) = diagnostics.last_mut()
+            && last.offset + last.bytes.len() == position
+        {
+            last.bytes.extend_from_slice(invalid);
+            position += invalid.len();
+            continue;
+        }
+
+        let newlines = text[line_start..].matches('\n').count();
+        if newlines > 0 {
+            line += newlines;
+            line_start = text.rfind('\n').map_or(0, |idx| idx + 1);
+        }
+        diagnostics.push(EncodingDiagnostic {
+            offset: position,
+            line,
+            column: text[line_start..].chars().count() + 1,
+            bytes: invalid.to_vec(),
+        });
+        text.push(char::REPLACEMENT_CHARACTER);
+        position += invalid.len();
+    }
+
+    DecodedInput {
+        text: Cow::Owned(text),
+        diagnostics,
+    }
+}
+
+/// Appends `valid` to `text`, turning `\r\n` and lone `\r` into `\n`
+fn push_normalized(text: &mut String, valid: &str) {
+    let mut rest = valid;
+    while let Some(idx) = rest.find('\r') {
+        text.push_str(&rest[..idx]);
+        text.push('\n');
+        rest = &rest[idx + 1..];
+        if let Some(after) = rest.strip_prefix('\n') {
+            rest = after;
+        }
+    }
+    text.push_str(rest);
+}
+
+/// The result of parsing raw bytes with one of the `_bytes` entry points
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedBytes<T> {
+    /// The parsed file
+    pub value: T,
+    /// Decoded input that was left unparsed, empty if the whole file was read
+    pub remaining: String,
+    /// Why parsing stopped before the end of the input
+    pub error: Option<ParseError>,
+    /// Runs of invalid UTF-8 that were replaced with U+FFFD
+    pub diagnostics: Vec<EncodingDiagnostic>,
+}
+
+impl<T> ParsedBytes<T> {
+    /// Converts the parsed value, keeping the remaining input, error and diagnostics
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ParsedBytes<U> {
+        ParsedBytes {
+            value: f(self.value),
+            remaining: self.remaining,
+            error: self.error,
+            diagnostics: self.diagnostics,
+        }
+    }
+}
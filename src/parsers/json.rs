@@ -0,0 +1,418 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use serde_json::{Map, Number, Value as Json, json};
+
+use super::parser_project_file::ProjectFile;
+use super::parser_property::UntypedProperty;
+use super::parser_property_file::{PropertyFile, Section};
+use super::parser_scene_file::SceneFile;
+use super::parser_value::{Value, escape};
+
+/// Error returned when JSON does not follow the schema written by the `*_to_json` functions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonError {
+    /// Where the problem is, e.g. `sections[2].properties[0].value`
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+fn error(path: &str, message: impl Into<String>) -> JsonError {
+    JsonError {
+        path: path.to_string(),
+        message: message.into(),
+    }
+}
+
+/// Converts a scene to JSON.
+///
+/// The document has a `sections` array. Each section has a `header` with its header type,
+/// and `attributes` and `properties` arrays for the header attributes and the body. Every
+/// property is `{"key": ..., "value": ...}`, where the value is tagged with its Godot
+/// type as described in `value_to_json`.
+///
+/// # Example
+///
+/// ```
+/// use godot_properties_parser::parse_scene_file;
+/// use godot_properties_parser::parsers::json::scene_to_json;
+///
+/// let (_, scene) = parse_scene_file("[node name=\"Icon\" type=\"Sprite2D\"]\nposition = Vector2(263, 14)\n").unwrap();
+/// let json = scene_to_json(&scene);
+///
+/// let position = &json["sections"][0]["properties"][0];
+/// assert_eq!(position["value"], serde_json::json!({"type": "Vector2", "value": [263, 14]}));
+/// ```
+pub fn scene_to_json(scene: &SceneFile) -> Json {
+    json!({ "sections": sections_to_json(&scene.all_sections) })
+}
+
+/// Converts a project file to JSON, like `scene_to_json` with an additional `preamble`
+/// array for the properties before the first section
+pub fn project_to_json(project: &ProjectFile) -> Json {
+    json!({
+        "preamble": properties_to_json(&project.preamble_properties),
+        "sections": sections_to_json(&project.all_sections),
+    })
+}
+
+/// Converts any property file to JSON, in the format of `project_to_json`
+pub fn property_file_to_json(file: &PropertyFile) -> Json {
+    json!({
+        "preamble": properties_to_json(&file.preamble_properties),
+        "sections": sections_to_json(&file.sections),
+    })
+}
+
+/// Reads a scene from JSON written by `scene_to_json`
+pub fn scene_from_json(json: &Json) -> Result<SceneFile<'static>, JsonError> {
+    let file = property_file_from_json(json)?;
+    if !file.preamble_properties.is_empty() {
+        return Err(error(
+            "preamble",
+            "scenes have no properties before the first section",
+        ));
+    }
+    Ok(SceneFile::from_property_file(file))
+}
+
+/// Reads a project file from JSON written by `project_to_json`
+pub fn project_from_json(json: &Json) -> Result<ProjectFile<'static>, JsonError> {
+    property_file_from_json(json).map(ProjectFile::from_property_file)
+}
+
+/// Reads a property file from JSON written by `property_file_to_json`. A missing
+/// `preamble` is read as empty.
+pub fn property_file_from_json(json: &Json) -> Result<PropertyFile<'static>, JsonError> {
+    let object = json
+        .as_object()
+        .ok_or_else(|| error("", "expected an object"))?;
+
+    let preamble_properties = match object.get("preamble") {
+        Some(preamble) => properties_from_json(preamble, "preamble")?,
+        None => Vec::new(),
+    };
+
+    let sections = array(object.get("sections"), "sections")?
+        .iter()
+        .enumerate()
+        .map(|(i, section)| section_from_json(section, &format!("sections[{}]", i)))
+        .collect::<Result<_, _>>()?;
+
+    Ok(PropertyFile {
+        preamble_properties,
+        sections,
+    })
+}
+
+/// Converts a value to JSON, tagged with its Godot type as `{"type": ..., "value": ...}`.
+///
+/// The type is `Nil`, `bool`, `int`, `float`, `String`, `StringName`, `NodePath`, `Array`,
+/// `Dictionary` or `Object`, or the name of any other constructor such as `Vector2`,
+/// `ExtResource` or `Array[int]`, whose `value` holds the arguments. `inf`, `-inf` and
+/// `nan` floats are written as strings. Dictionary entries and object properties are
+/// arrays of `{"key": ..., "value": ...}` to keep their order, and objects also have a
+/// `class`.
+///
+/// Values nested in arrays, dictionaries, objects and constructors are written as plain
+/// JSON when JSON has a matching type: `null`, booleans, strings, and numbers, which are
+/// floats when written with a fraction or exponent. All other nested values are tagged.
+pub fn value_to_json(value: &Value) -> Json {
+    match value {
+        Value::Nil => json!({ "type": "Nil", "value": null }),
+        Value::Bool(b) => json!({ "type": "bool", "value": b }),
+        Value::Int(i) => json!({ "type": "int", "value": i }),
+        Value::Float(x) => json!({ "type": "float", "value": float_to_json(*x) }),
+        Value::String(s) => json!({ "type": "String", "value": s }),
+        Value::StringName(s) => json!({ "type": "StringName", "value": s }),
+        Value::NodePath(s) => json!({ "type": "NodePath", "value": s }),
+        Value::Array(items) => json!({ "type": "Array", "value": items_to_json(items) }),
+        Value::Dictionary(entries) => {
+            let entries: Vec<_> = entries
+                .iter()
+                .map(|(key, value)| {
+                    json!({ "key": item_to_json(key), "value": item_to_json(value) })
+                })
+                .collect();
+            json!({ "type": "Dictionary", "value": entries })
+        }
+        Value::Object { class, properties } => {
+            let properties: Vec<_> = properties
+                .iter()
+                .map(|(key, value)| json!({ "key": key, "value": item_to_json(value) }))
+                .collect();
+            json!({ "type": "Object", "class": class, "value": properties })
+        }
+        Value::Constructor { name, args } => json!({ "type": name, "value": items_to_json(args) }),
+    }
+}
+
+/// Reads a value from JSON written by `value_to_json`. Plain JSON values are accepted as
+/// well, as they are for nested values.
+pub fn value_from_json(json: &Json) -> Result<Value, JsonError> {
+    value_at(json, "")
+}
+
+fn sections_to_json(sections: &[Section]) -> Json {
+    sections
+        .iter()
+        .map(|section| {
+            json!({
+                "header": section.header_type,
+                "attributes": properties_to_json(section.attributes()),
+                "properties": properties_to_json(section.body()),
+            })
+        })
+        .collect()
+}
+
+fn properties_to_json(properties: &[UntypedProperty]) -> Json {
+    properties
+        .iter()
+        .map(|property| json!({ "key": property.key, "value": property_value_to_json(property) }))
+        .collect()
+}
+
+/// Tags the value of a property, or writes it as `{"type": "raw", "value": text}` with the
+/// text as written when it is not a valid Godot value
+fn property_value_to_json(property: &UntypedProperty) -> Json {
+    let text = property.value_text();
+    match Value::parse(&text) {
+        Some(value) => value_to_json(&value),
+        None => json!({ "type": "raw", "value": text }),
+    }
+}
+
+fn items_to_json(items: &[Value]) -> Json {
+    items.iter().map(item_to_json).collect()
+}
+
+/// Writes a nested value, as plain JSON where JSON has a matching type
+fn item_to_json(value: &Value) -> Json {
+    match value {
+        Value::Nil => Json::Null,
+        Value::Bool(b) => Json::Bool(*b),
+        Value::Int(i) => Json::from(*i),
+        Value::Float(x) if x.is_finite() => Json::from(*x),
+        Value::String(s) => Json::String(s.clone()),
+        _ => value_to_json(value),
+    }
+}
+
+fn float_to_json(x: f64) -> Json {
+    match Number::from_f64(x) {
+        Some(number) => Json::Number(number),
+        None if x.is_nan() => json!("nan"),
+        None if x > 0.0 => json!("inf"),
+        None => json!("-inf"),
+    }
+}
+
+fn section_from_json(json: &Json, path: &str) -> Result<Section<'static>, JsonError> {
+    let object = json
+        .as_object()
+        .ok_or_else(|| error(path, "expected an object"))?;
+
+    let header_type = object
+        .get("header")
+        .and_then(Json::as_str)
+        .ok_or_else(|| error(&format!("{}.header", path), "expected a string"))?;
+
+    let mut section = Section::new(header_type.to_string());
+    if let Some(attributes) = object.get("attributes") {
+        section.properties = properties_from_json(attributes, &format!("{}.attributes", path))?;
+        section.attribute_count = section.properties.len();
+    }
+    if let Some(properties) = object.get("properties") {
+        let body = properties_from_json(properties, &format!("{}.properties", path))?;
+        section.properties.extend(body);
+    }
+    Ok(section)
+}
+
+fn properties_from_json(
+    json: &Json,
+    path: &str,
+) -> Result<Vec<UntypedProperty<'static>>, JsonError> {
+    array(Some(json), path)?
+        .iter()
+        .enumerate()
+        .map(|(i, property)| property_from_json(property, &format!("{}[{}]", path, i)))
+        .collect()
+}
+
+fn property_from_json(json: &Json, path: &str) -> Result<UntypedProperty<'static>, JsonError> {
+    let key = json
+        .get("key")
+        .and_then(Json::as_str)
+        .ok_or_else(|| error(&format!("{}.key", path), "expected a string"))?
+        .to_string();
+
+    let path = format!("{}.value", path);
+    let json = json
+        .get("value")
+        .ok_or_else(|| error(&path, "missing value"))?;
+
+    if json.get("type").and_then(Json::as_str) == Some("raw") {
+        let text = json
+            .get("value")
+            .and_then(Json::as_str)
+            .ok_or_else(|| error(&format!("{}.value", path), "expected a string"))?;
+        return Ok(
+            match text.strip_prefix('"').and_then(|t| t.strip_suffix('"')) {
+                Some(content) => UntypedProperty::new_quoted(key, content.to_string()),
+                None => UntypedProperty::new(key, text.to_string()),
+            },
+        );
+    }
+
+    Ok(match value_at(json, &path)? {
+        Value::String(s) => UntypedProperty::new_quoted(key, Cow::Owned(escape(&s))),
+        value => UntypedProperty::new(key, value.to_string()),
+    })
+}
+
+fn value_at(json: &Json, path: &str) -> Result<Value, JsonError> {
+    let object = match json {
+        Json::Null => return Ok(Value::Nil),
+        Json::Bool(b) => return Ok(Value::Bool(*b)),
+        Json::Number(number) => return Ok(number_from_json(number)),
+        Json::String(s) => return Ok(Value::String(s.clone())),
+        Json::Array(_) => return Err(error(path, "expected a tagged value, found an array")),
+        Json::Object(object) => object,
+    };
+
+    let ty = object
+        .get("type")
+        .and_then(Json::as_str)
+        .ok_or_else(|| error(&format!("{}.type", path), "expected a string"))?;
+    let value_path = format!("{}.value", path);
+    let value = object
+        .get("value")
+        .ok_or_else(|| error(&value_path, "missing value"))?;
+    let string = || {
+        value
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| error(&value_path, "expected a string"))
+    };
+
+    match ty {
+        "Nil" => Ok(Value::Nil),
+        "bool" => value
+            .as_bool()
+            .map(Value::Bool)
+            .ok_or_else(|| error(&value_path, "expected a boolean")),
+        "int" => value
+            .as_i64()
+            .map(Value::Int)
+            .ok_or_else(|| error(&value_path, "expected an integer")),
+        "float" => match value {
+            Json::Number(number) => Ok(Value::Float(number.as_f64().unwrap_or(f64::NAN))),
+            Json::String(s) => match s.as_str() {
+                "inf" => Ok(Value::Float(f64::INFINITY)),
+                "-inf" => Ok(Value::Float(f64::NEG_INFINITY)),
+                "nan" => Ok(Value::Float(f64::NAN)),
+                _ => Err(error(
+                    &value_path,
+                    "expected a number, \"inf\", \"-inf\" or \"nan\"",
+                )),
+            },
+            _ => Err(error(&value_path, "expected a number")),
+        },
+        "String" => string().map(Value::String),
+        "StringName" => string().map(Value::StringName),
+        "NodePath" => string().map(Value::NodePath),
+        "Array" => items_from_json(value, &value_path).map(Value::Array),
+        "Dictionary" => {
+            let entries = pairs_from_json(value, &value_path)?;
+            entries
+                .into_iter()
+                .map(|(key, value, path)| {
+                    Ok((
+                        value_at(key, &format!("{}.key", path))?,
+                        value_at(value, &format!("{}.value", path))?,
+                    ))
+                })
+                .collect::<Result<_, _>>()
+                .map(Value::Dictionary)
+        }
+        "Object" => {
+            let class = object
+                .get("class")
+                .and_then(Json::as_str)
+                .ok_or_else(|| error(&format!("{}.class", path), "expected a string"))?
+                .to_string();
+            let properties = pairs_from_json(value, &value_path)?
+                .into_iter()
+                .map(|(key, value, path)| {
+                    let key = key
+                        .as_str()
+                        .ok_or_else(|| error(&format!("{}.key", path), "expected a string"))?;
+                    Ok((
+                        key.to_string(),
+                        value_at(value, &format!("{}.value", path))?,
+                    ))
+                })
+                .collect::<Result<_, _>>()?;
+            Ok(Value::Object { class, properties })
+        }
+        "raw" => Err(error(
+            path,
+            "raw values are only allowed as property values",
+        )),
+        name => Ok(Value::Constructor {
+            name: name.to_string(),
+            args: items_from_json(value, &value_path)?,
+        }),
+    }
+}
+
+fn number_from_json(number: &Number) -> Value {
+    match number.as_i64() {
+        Some(i) => Value::Int(i),
+        None => Value::Float(number.as_f64().unwrap_or(f64::NAN)),
+    }
+}
+
+fn items_from_json(json: &Json, path: &str) -> Result<Vec<Value>, JsonError> {
+    array(Some(json), path)?
+        .iter()
+        .enumerate()
+        .map(|(i, item)| value_at(item, &format!("{}[{}]", path, i)))
+        .collect()
+}
+
+/// Reads an array of `{"key": ..., "value": ...}` objects, with the path of each entry
+fn pairs_from_json<'j>(
+    json: &'j Json,
+    path: &str,
+) -> Result<Vec<(&'j Json, &'j Json, String)>, JsonError> {
+    array(Some(json), path)?
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let path = format!("{}[{}]", path, i);
+            let entry: &Map<String, Json> = entry
+                .as_object()
+                .ok_or_else(|| error(&path, "expected an object with a key and a value"))?;
+            match (entry.get("key"), entry.get("value")) {
+                (Some(key), Some(value)) => Ok((key, value, path)),
+                _ => Err(error(&path, "expected an object with a key and a value")),
+            }
+        })
+        .collect()
+}
+
+fn array<'j>(json: Option<&'j Json>, path: &str) -> Result<&'j Vec<Json>, JsonError> {
+    json.and_then(Json::as_array)
+        .ok_or_else(|| error(path, "expected an array"))
+}
@@ -0,0 +1,386 @@
+use super::source_map::Location;
+
+/// A byte range within the parsed input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    /// Creates a span covering `start..end`
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// Number of bytes covered by the span
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Whether the span covers no bytes
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Returns the smallest span covering both spans
+    pub fn to(self, other: Span) -> Span {
+        Span::new(self.start.min(other.start), self.end.max(other.end))
+    }
+
+    /// Returns the text covered by the span
+    pub fn slice<'a>(&self, input: &'a str) -> &'a str {
+        &input[self.start..self.end]
+    }
+}
+
+/// The kind of a token produced by `Lexer`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// `[`
+    OpenBracket,
+    /// `]`
+    CloseBracket,
+    /// `{`
+    OpenBrace,
+    /// `}`
+    CloseBrace,
+    /// `(`
+    OpenParen,
+    /// `)`
+    CloseParen,
+    /// `,`
+    Comma,
+    /// `:`
+    Colon,
+    /// `=`
+    Equals,
+    /// A quoted string including its quotes, possibly spanning several lines
+    String,
+    /// A string that is not closed before the end of the input
+    UnterminatedString,
+    /// An integer or float such as `42`, `-1.5` or `1e-05`
+    Number,
+    /// A name made of letters, digits, `_` and `/`, such as `Vector2` or `config/name`
+    Identifier,
+    /// A `;` comment up to the end of the line
+    Comment,
+    /// A line break
+    Newline,
+    /// Any other single character, such as `&`, `^`, `-` or `.`
+    Symbol,
+}
+
+/// A token and the part of the input it was read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+    pub span: Span,
+}
+
+impl<'a> Token<'a> {
+    /// Whether this token directly follows `previous` without whitespace in between
+    pub fn follows(&self, previous: &Token) -> bool {
+        previous.span.end == self.span.start
+    }
+
+    /// The contents of a string token without the surrounding quotes. Escape sequences are
+    /// left as written.
+    pub fn string_content(&self) -> &'a str {
+        match self.kind {
+            TokenKind::String => &self.text[1..self.text.len() - 1],
+            TokenKind::UnterminatedString => &self.text[1..],
+            _ => self.text,
+        }
+    }
+}
+
+/// Splits Godot's text format into tokens in a single pass.
+///
+/// Whitespace other than line breaks is skipped; every token records its span so that
+/// callers can slice the original input instead of copying it. The lexer never fails:
+/// characters it does not recognize become `TokenKind::Symbol` tokens.
+#[derive(Debug, Clone)]
+pub struct Lexer<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    /// Creates a lexer positioned at the start of `input`
+    pub fn new(input: &'a str) -> Self {
+        Self::at(input, 0)
+    }
+
+    /// Creates a lexer positioned at byte `offset` of `input`
+    pub fn at(input: &'a str, offset: usize) -> Self {
+        Self { input, pos: offset }
+    }
+
+    /// The complete input being tokenized
+    pub fn input(&self) -> &'a str {
+        self.input
+    }
+
+    /// Byte offset of the next character to be read
+    pub fn offset(&self) -> usize {
+        self.pos
+    }
+
+    fn token(&mut self, kind: TokenKind, start: usize, end: usize) -> Token<'a> {
+        self.pos = end;
+        Token {
+            kind,
+            text: &self.input[start..end],
+            span: Span::new(start, end),
+        }
+    }
+
+    fn string(&mut self, start: usize) -> Token<'a> {
+        let bytes = self.input.as_bytes();
+        let mut i = start + 1;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\\' => i += 2,
+                b'"' => return self.token(TokenKind::String, start, i + 1),
+                _ => i += 1,
+            }
+        }
+        self.token(TokenKind::UnterminatedString, start, bytes.len())
+    }
+
+    fn number(&mut self, start: usize) -> Token<'a> {
+        let bytes = self.input.as_bytes();
+        let mut i = start;
+        if matches!(bytes[i], b'-' | b'+') {
+            i += 1;
+        }
+        i = skip_digits(bytes, i);
+        if bytes.get(i) == Some(&b'.') {
+            i = skip_digits(bytes, i + 1);
+        }
+        if matches!(bytes.get(i), Some(b'e' | b'E')) {
+            let mut exponent = i + 1;
+            if matches!(bytes.get(exponent), Some(b'-' | b'+')) {
+                exponent += 1;
+            }
+            if bytes.get(exponent).is_some_and(u8::is_ascii_digit) {
+                i = skip_digits(bytes, exponent);
+            }
+        }
+
+        // Keys such as `0/position` start like a number but continue as a name
+        if self.input[i..]
+            .chars()
+            .next()
+            .is_some_and(is_identifier_char)
+        {
+            return self.identifier(start, i);
+        }
+        self.token(TokenKind::Number, start, i)
+    }
+
+    fn identifier(&mut self, start: usize, from: usize) -> Token<'a> {
+        let end = self.input[from..]
+            .char_indices()
+            .find(|(_, c)| !is_identifier_char(*c))
+            .map_or(self.input.len(), |(idx, _)| from + idx);
+        self.token(TokenKind::Identifier, start, end)
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let bytes = self.input.as_bytes();
+        let mut start = self.pos;
+        while start < bytes.len() && matches!(bytes[start], b' ' | b'\t' | b'\r' | b'\x0c') {
+            start += 1;
+        }
+        self.pos = start;
+
+        let byte = *bytes.get(start)?;
+        let single = |kind| (kind, start + 1);
+        let (kind, end) = match byte {
+            b'\n' => single(TokenKind::Newline),
+            b'[' => single(TokenKind::OpenBracket),
+            b']' => single(TokenKind::CloseBracket),
+            b'{' => single(TokenKind::OpenBrace),
+            b'}' => single(TokenKind::CloseBrace),
+            b'(' => single(TokenKind::OpenParen),
+            b')' => single(TokenKind::CloseParen),
+            b',' => single(TokenKind::Comma),
+            b':' => single(TokenKind::Colon),
+            b'=' => single(TokenKind::Equals),
+            b'"' => return Some(self.string(start)),
+            b';' => {
+                let end = self.input[start..]
+                    .find('\n')
+                    .map_or(bytes.len(), |idx| start + idx);
+                (TokenKind::Comment, end)
+            }
+            b'0'..=b'9' => return Some(self.number(start)),
+            b'-' | b'+' | b'.' if starts_number(&bytes[start..]) => {
+                return Some(self.number(start));
+            }
+            _ => {
+                let c = self.input[start..].chars().next()?;
+                if is_identifier_char(c) {
+                    return Some(self.identifier(start, start));
+                }
+                (TokenKind::Symbol, start + c.len_utf8())
+            }
+        };
+        Some(self.token(kind, start, end))
+    }
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '/'
+}
+
+fn skip_digits(bytes: &[u8], mut i: usize) -> usize {
+    while bytes.get(i).is_some_and(u8::is_ascii_digit) {
+        i += 1;
+    }
+    i
+}
+
+/// Whether `bytes` starts with a sign or decimal point that begins a number, e.g. `-1` or `.5`
+fn starts_number(bytes: &[u8]) -> bool {
+    match bytes {
+        [b'-' | b'+', b'.', digit, ..] => digit.is_ascii_digit(),
+        [b'-' | b'+' | b'.', digit, ..] => digit.is_ascii_digit(),
+        _ => false,
+    }
+}
+
+/// Tokenizes the complete input
+pub fn tokenize(input: &str) -> Vec<Token<'_>> {
+    Lexer::new(input).collect()
+}
+
+/// A lexer with one token of lookahead, shared by the untyped and typed parsers
+#[derive(Debug, Clone)]
+pub(crate) struct TokenCursor<'a> {
+    lexer: Lexer<'a>,
+    peeked: Option<Option<Token<'a>>>,
+    consumed: usize,
+    /// The offset of the last location computed with its line and column, so that
+    /// positions are counted onwards instead of from the start of the input
+    located: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> TokenCursor<'a> {
+    pub(crate) fn new(input: &'a str) -> Self {
+        Self {
+            lexer: Lexer::new(input),
+            peeked: None,
+            consumed: 0,
+            located: 0,
+            line: 1,
+            column: 0,
+        }
+    }
+
+    pub(crate) fn input(&self) -> &'a str {
+        self.lexer.input()
+    }
+
+    /// The location from the start of `first` to the end of `last`.
+    ///
+    /// Lines and columns are counted onwards from the previous location, which is cheap as
+    /// long as locations are requested in input order.
+    pub(crate) fn location(&mut self, first: &Token, last: &Token) -> Location {
+        self.span_location(first.span.to(last.span))
+    }
+
+    /// The empty location at `offset`, such as the end of a line where a token is missing
+    pub(crate) fn location_at(&mut self, offset: usize) -> Location {
+        self.span_location(Span::new(offset, offset))
+    }
+
+    fn span_location(&mut self, span: Span) -> Location {
+        let start = span.start;
+        if start < self.located {
+            self.located = 0;
+            self.line = 1;
+            self.column = 0;
+        }
+
+        // Characters are the bytes that do not continue a UTF-8 sequence
+        let (mut line, mut column) = (self.line, self.column);
+        for &byte in &self.input().as_bytes()[self.located..start] {
+            if byte == b'\n' {
+                line += 1;
+                column = 0;
+            } else {
+                column += usize::from(byte & 0xC0 != 0x80);
+            }
+        }
+        (self.line, self.column) = (line, column);
+        self.located = start;
+
+        Location::new(span, self.line, self.column + 1)
+    }
+
+    /// Byte offset of the next token, or of the end of the consumed input
+    pub(crate) fn offset(&mut self) -> usize {
+        match self.peek() {
+            Some(token) => token.span.start,
+            None => self.lexer.offset(),
+        }
+    }
+
+    pub(crate) fn peek(&mut self) -> Option<Token<'a>> {
+        *self.peeked.get_or_insert_with(|| self.lexer.next())
+    }
+
+    /// Byte offset just past the last consumed token
+    pub(crate) fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    pub(crate) fn next(&mut self) -> Option<Token<'a>> {
+        let token = match self.peeked.take() {
+            Some(token) => token,
+            None => self.lexer.next(),
+        };
+        if let Some(token) = &token {
+            self.consumed = token.span.end;
+        }
+        token
+    }
+
+    /// Consumes the next token if it has the given kind
+    pub(crate) fn eat(&mut self, kind: TokenKind) -> Option<Token<'a>> {
+        if self.peek()?.kind == kind {
+            self.next()
+        } else {
+            None
+        }
+    }
+
+    /// Skips line breaks
+    pub(crate) fn skip_newlines(&mut self) {
+        while self.eat(TokenKind::Newline).is_some() {}
+    }
+
+    /// Skips tokens up to and including the next line break. Returns the unterminated
+    /// string that ran to the end of the input instead, if there was one.
+    pub(crate) fn skip_line(&mut self) -> Option<Token<'a>> {
+        while let Some(token) = self.next() {
+            match token.kind {
+                TokenKind::Newline => break,
+                TokenKind::UnterminatedString => return Some(token),
+                _ => {}
+            }
+        }
+        None
+    }
+}
@@ -1,5 +1,14 @@
+#[cfg(feature = "serde")]
+pub mod de;
+pub mod encoding;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod lexer;
 pub mod parser_header;
 pub mod parser_project_file;
 pub mod parser_property;
 pub mod parser_property_file;
 pub mod parser_scene_file;
+pub mod parser_section_iter;
+pub mod parser_value;
+pub mod source_map;
@@ -1,118 +1,360 @@
-use super::parser_property::UntypedProperty;
-use super::parser_property_file::{PropertyFile, Section, parse_property_file};
+use std::borrow::Cow;
+use std::fmt;
+
 use nom::IResult;
 
+use super::encoding::ParsedBytes;
+use super::parser_property::UntypedProperty;
+use super::parser_property_file::{
+    ParseError, PropertyFile, Section, parse_property_file, parse_property_file_bytes,
+    try_parse_property_file,
+};
+
+/// Section names defined by the engine itself. Any other section in a project file is
+/// defined by a plugin or by the project, see `ProjectFile::custom_sections`.
+pub const STANDARD_SECTIONS: &[&str] = &[
+    "accessibility",
+    "addons",
+    "animation",
+    "application",
+    "audio",
+    "autoload",
+    "compression",
+    "debug",
+    "display",
+    "dotnet",
+    "editor",
+    "editor_plugins",
+    "filesystem",
+    "global_group",
+    "gui",
+    "importer_defaults",
+    "input",
+    "input_devices",
+    "internationalization",
+    "layer_names",
+    "memory",
+    "navigation",
+    "network",
+    "physics",
+    "rendering",
+    "shader_globals",
+    "text_server",
+    "threading",
+    "xr",
+];
+
 #[derive(Debug, PartialEq, Clone)]
-pub struct ProjectFile {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProjectFile<'a> {
     /// Properties that appear before the first section (e.g., config_version=5)
-    pub preamble_properties: Vec<UntypedProperty>,
+    pub preamble_properties: Vec<UntypedProperty<'a>>,
+    /// All sections in file order. The categorized accessors such as `application` are
+    /// views into this list.
+    pub all_sections: Vec<Section<'a>>,
+}
+
+impl<'a> ProjectFile<'a> {
+    /// Create a new empty ProjectFile
+    pub fn new() -> Self {
+        Self {
+            preamble_properties: Vec::new(),
+            all_sections: Vec::new(),
+        }
+    }
+
+    /// Create a ProjectFile from a PropertyFile. Sections are kept as written; the
+    /// categorized accessors look them up by name.
+    pub fn from_property_file(property_file: PropertyFile<'a>) -> Self {
+        Self {
+            preamble_properties: property_file.preamble_properties,
+            all_sections: property_file.sections,
+        }
+    }
+
+    /// Converts the project file into one that owns its data and no longer borrows the input
+    pub fn into_owned(self) -> ProjectFile<'static> {
+        ProjectFile {
+            preamble_properties: self
+                .preamble_properties
+                .into_iter()
+                .map(UntypedProperty::into_owned)
+                .collect(),
+            all_sections: self
+                .all_sections
+                .into_iter()
+                .map(Section::into_owned)
+                .collect(),
+        }
+    }
+
+    /// Plugin settings section
+    pub fn addons(&self) -> Option<Cow<'_, Section<'a>>> {
+        self.merged_section("addons")
+    }
+
     /// Application configuration section
-    pub application: Option<Section>,
+    pub fn application(&self) -> Option<Cow<'_, Section<'a>>> {
+        self.merged_section("application")
+    }
+
     /// Audio configuration section
-    pub audio: Option<Section>,
+    pub fn audio(&self) -> Option<Cow<'_, Section<'a>>> {
+        self.merged_section("audio")
+    }
+
     /// Autoload section (singleton nodes)
-    pub autoload: Option<Section>,
+    pub fn autoload(&self) -> Option<Cow<'_, Section<'a>>> {
+        self.merged_section("autoload")
+    }
+
     /// Debug settings section
-    pub debug: Option<Section>,
+    pub fn debug(&self) -> Option<Cow<'_, Section<'a>>> {
+        self.merged_section("debug")
+    }
+
     /// Display settings section
-    pub display: Option<Section>,
+    pub fn display(&self) -> Option<Cow<'_, Section<'a>>> {
+        self.merged_section("display")
+    }
+
+    /// C# (.NET) settings section
+    pub fn dotnet(&self) -> Option<Cow<'_, Section<'a>>> {
+        self.merged_section("dotnet")
+    }
+
+    /// Editor settings section
+    pub fn editor(&self) -> Option<Cow<'_, Section<'a>>> {
+        self.merged_section("editor")
+    }
+
     /// Editor plugins section
-    pub editor_plugins: Option<Section>,
+    pub fn editor_plugins(&self) -> Option<Cow<'_, Section<'a>>> {
+        self.merged_section("editor_plugins")
+    }
+
+    /// Filesystem settings section
+    pub fn filesystem(&self) -> Option<Cow<'_, Section<'a>>> {
+        self.merged_section("filesystem")
+    }
+
+    /// Global groups section (Godot 4.3+)
+    pub fn global_group(&self) -> Option<Cow<'_, Section<'a>>> {
+        self.merged_section("global_group")
+    }
+
+    /// GUI settings section (themes, fonts)
+    pub fn gui(&self) -> Option<Cow<'_, Section<'a>>> {
+        self.merged_section("gui")
+    }
+
     /// Input mappings section
-    pub input: Option<Section>,
+    pub fn input(&self) -> Option<Cow<'_, Section<'a>>> {
+        self.merged_section("input")
+    }
+
     /// Input devices settings section
-    pub input_devices: Option<Section>,
+    pub fn input_devices(&self) -> Option<Cow<'_, Section<'a>>> {
+        self.merged_section("input_devices")
+    }
+
     /// Internationalization settings section
-    pub internationalization: Option<Section>,
+    pub fn internationalization(&self) -> Option<Cow<'_, Section<'a>>> {
+        self.merged_section("internationalization")
+    }
+
     /// Layer names section
-    pub layer_names: Option<Section>,
+    pub fn layer_names(&self) -> Option<Cow<'_, Section<'a>>> {
+        self.merged_section("layer_names")
+    }
+
+    /// Navigation settings section
+    pub fn navigation(&self) -> Option<Cow<'_, Section<'a>>> {
+        self.merged_section("navigation")
+    }
+
     /// Physics settings section
-    pub physics: Option<Section>,
+    pub fn physics(&self) -> Option<Cow<'_, Section<'a>>> {
+        self.merged_section("physics")
+    }
+
     /// Rendering settings section
-    pub rendering: Option<Section>,
-    /// All sections in order, including those not categorized above
-    pub all_sections: Vec<Section>,
-}
+    pub fn rendering(&self) -> Option<Cow<'_, Section<'a>>> {
+        self.merged_section("rendering")
+    }
 
-impl ProjectFile {
-    /// Create a new empty ProjectFile
-    pub fn new() -> Self {
-        Self {
-            preamble_properties: Vec::new(),
-            application: None,
-            audio: None,
-            autoload: None,
-            debug: None,
-            display: None,
-            editor_plugins: None,
-            input: None,
-            input_devices: None,
-            internationalization: None,
-            layer_names: None,
-            physics: None,
-            rendering: None,
-            all_sections: Vec::new(),
+    /// Global shader uniforms section
+    pub fn shader_globals(&self) -> Option<Cow<'_, Section<'a>>> {
+        self.merged_section("shader_globals")
+    }
+
+    /// XR settings section
+    pub fn xr(&self) -> Option<Cow<'_, Section<'a>>> {
+        self.merged_section("xr")
+    }
+
+    /// Returns the first section with the given name from `all_sections`
+    pub fn section(&self, name: &str) -> Option<&Section<'a>> {
+        self.all_sections.iter().find(|s| s.header_type == name)
+    }
+
+    /// Returns every section with the given name, in file order
+    pub fn sections<'s>(&'s self, name: &str) -> impl Iterator<Item = &'s Section<'a>> {
+        self.all_sections
+            .iter()
+            .filter(move |s| s.header_type == name)
+    }
+
+    /// Returns the value of `key` in the section `name`. If the section or key is declared
+    /// more than once, the last value wins, as in `merged_section`.
+    pub fn get(&self, name: &str, key: &str) -> Option<&str> {
+        self.property(name, key).map(|p| p.value.as_ref())
+    }
+
+    /// Returns the property `key` in the section `name`, the last one if it is declared
+    /// more than once, as in `get`
+    pub fn property(&self, name: &str, key: &str) -> Option<&UntypedProperty<'a>> {
+        self.sections(name)
+            .flat_map(|s| s.properties.iter())
+            .filter(|p| p.key == key)
+            .last()
+    }
+
+    /// Returns every section with the given name merged into one, the way Godot's
+    /// `ConfigFile` reads them: later keys override earlier ones.
+    ///
+    /// A section that appears once without repeated keys is borrowed from `all_sections`;
+    /// only duplicates are merged into a new section.
+    pub fn merged_section(&self, name: &str) -> Option<Cow<'_, Section<'a>>> {
+        let mut sections = self.sections(name);
+        let first = sections.next()?;
+        let rest: Vec<&Section<'a>> = sections.collect();
+
+        if rest.is_empty() && !has_duplicate_keys(first) {
+            return Some(Cow::Borrowed(first));
+        }
+
+        let mut merged = Section::new(first.header_type.clone());
+        for section in std::iter::once(first).chain(rest) {
+            merge_into(&mut merged, section);
         }
+        Some(Cow::Owned(merged))
     }
 
-    /// Create a ProjectFile from a PropertyFile by categorizing sections
-    pub fn from_property_file(property_file: PropertyFile) -> Self {
-        let mut project_file = ProjectFile::new();
-        project_file.preamble_properties = property_file.preamble_properties;
+    /// Reports sections that appear more than once and keys that appear more than once
+    /// within the same section, in file order
+    pub fn duplicates(&self) -> Vec<Duplicate> {
+        let mut duplicates = Vec::new();
 
-        for section in property_file.sections {
-            // Store in all_sections
-            project_file.all_sections.push(section.clone());
+        let mut section_counts: Vec<(&str, usize)> = Vec::new();
+        for section in &self.all_sections {
+            match section_counts
+                .iter_mut()
+                .find(|(name, _)| *name == section.header_type)
+            {
+                Some((_, count)) => *count += 1,
+                None => section_counts.push((&section.header_type, 1)),
+            }
+        }
+        for (name, count) in section_counts {
+            if count > 1 {
+                duplicates.push(Duplicate::Section {
+                    section: name.to_string(),
+                    count,
+                });
+            }
+        }
 
-            // Categorize by header type
-            match section.header_type.as_str() {
-                "application" => {
-                    project_file.application = Some(section);
-                }
-                "audio" => {
-                    project_file.audio = Some(section);
-                }
-                "autoload" => {
-                    project_file.autoload = Some(section);
-                }
-                "debug" => {
-                    project_file.debug = Some(section);
-                }
-                "display" => {
-                    project_file.display = Some(section);
+        for section in &self.all_sections {
+            let mut key_counts: Vec<(&str, usize)> = Vec::new();
+            for property in &section.properties {
+                match key_counts.iter_mut().find(|(key, _)| *key == property.key) {
+                    Some((_, count)) => *count += 1,
+                    None => key_counts.push((&property.key, 1)),
                 }
-                "editor_plugins" => {
-                    project_file.editor_plugins = Some(section);
-                }
-                "input" => {
-                    project_file.input = Some(section);
-                }
-                "input_devices" => {
-                    project_file.input_devices = Some(section);
-                }
-                "internationalization" => {
-                    project_file.internationalization = Some(section);
-                }
-                "layer_names" => {
-                    project_file.layer_names = Some(section);
-                }
-                "physics" => {
-                    project_file.physics = Some(section);
-                }
-                "rendering" => {
-                    project_file.rendering = Some(section);
-                }
-                _ => {
-                    // Other types stay only in all_sections
+            }
+            for (key, count) in key_counts {
+                if count > 1 {
+                    duplicates.push(Duplicate::Key {
+                        section: section.header_type.to_string(),
+                        key: key.to_string(),
+                        count,
+                    });
                 }
             }
         }
 
-        project_file
+        duplicates
+    }
+
+    /// Returns the sections that are not defined by the engine, such as `[gdunit4]` or
+    /// `[steam]` added by plugins, in file order
+    pub fn custom_sections(&self) -> Vec<&Section<'a>> {
+        self.all_sections
+            .iter()
+            .filter(|s| !STANDARD_SECTIONS.contains(&s.header_type.as_ref()))
+            .collect()
+    }
+}
+
+/// A section or key that is declared more than once in a project file
+#[derive(Debug, PartialEq, Clone)]
+pub enum Duplicate {
+    /// A section header that appears `count` times
+    Section { section: String, count: usize },
+    /// A key that appears `count` times within one occurrence of `section`
+    Key {
+        section: String,
+        key: String,
+        count: usize,
+    },
+}
+
+/// Merges `section` into `target` the way Godot's `ConfigFile` does: keys that already
+/// exist keep their position and take the later value, new keys are appended
+fn merge_into<'a>(target: &mut Section<'a>, section: &Section<'a>) {
+    for property in &section.properties {
+        match target.properties.iter_mut().find(|p| p.key == property.key) {
+            Some(existing) => {
+                existing.value = property.value.clone();
+                existing.quoted = property.quoted;
+                existing.value_location = property.value_location;
+            }
+            None => target.properties.push(property.clone()),
+        }
+    }
+}
+
+fn has_duplicate_keys(section: &Section) -> bool {
+    section
+        .properties
+        .iter()
+        .enumerate()
+        .any(|(i, p)| section.properties[..i].iter().any(|q| q.key == p.key))
+}
+
+/// Writes the file the way Godot saves `project.godot`: `key=value` lines, with a blank line
+/// around every section header
+impl fmt::Display for ProjectFile<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for property in &self.preamble_properties {
+            writeln!(f, "{}", property)?;
+        }
+        for (i, section) in self.all_sections.iter().enumerate() {
+            if i > 0 || !self.preamble_properties.is_empty() {
+                writeln!(f)?;
+            }
+            writeln!(f, "[{}]", section.header_type)?;
+            writeln!(f)?;
+            for property in &section.properties {
+                writeln!(f, "{}", property)?;
+            }
+        }
+        Ok(())
     }
 }
 
-impl Default for ProjectFile {
+impl Default for ProjectFile<'_> {
     fn default() -> Self {
         Self::new()
     }
@@ -121,9 +363,10 @@ impl Default for ProjectFile {
 /// Parses a Godot project file (`.godot`) into a structured `ProjectFile`.
 ///
 /// Project files contain global configuration for a Godot project, including application
-/// settings, autoloads, input mappings, and rendering options. This parser categorizes
-/// known sections (`application`, `audio`, `autoload`, etc.) while preserving all sections
-/// including custom ones in `all_sections`.
+/// settings, autoloads, input mappings, and rendering options. `ProjectFile` has
+/// accessors for known sections (`application`, `audio`, `autoload`, etc.) and keeps all
+/// sections including custom ones in `all_sections`. Use `section` to look up any section by name
+/// and `custom_sections` for the ones added by plugins.
 ///
 /// # Arguments
 ///
@@ -143,14 +386,40 @@ impl Default for ProjectFile {
 /// let content = fs::read_to_string("project.godot").unwrap();
 /// let (remaining, project) = parse_project_file(&content).unwrap();
 ///
-/// if let Some(app) = project.application {
+/// if let Some(app) = project.application() {
 ///     for prop in &app.properties {
 ///         println!("{}: {}", prop.key, prop.value);
 ///     }
 /// }
 /// ```
-pub fn parse_project_file(input: &str) -> IResult<&str, ProjectFile> {
+pub fn parse_project_file(input: &str) -> IResult<&str, ProjectFile<'_>> {
     let (remaining, property_file) = parse_property_file(input)?;
     let project_file = ProjectFile::from_property_file(property_file);
     Ok((remaining, project_file))
 }
+
+/// Parses a Godot project file (`project.godot`), failing on the first malformed section
+/// header or unterminated string. See `try_parse_property_file`.
+pub fn try_parse_project_file(input: &str) -> Result<ProjectFile<'_>, ParseError> {
+    try_parse_property_file(input).map(ProjectFile::from_property_file)
+}
+
+/// Parses the raw bytes of a project file (`.godot`) into a `ProjectFile`.
+///
+/// Byte order marks, Windows line endings and invalid UTF-8 are handled as described in
+/// `parse_property_file_bytes`.
+///
+/// # Example
+///
+/// ```no_run
+/// use godot_properties_parser::parse_project_file_bytes;
+/// use std::fs;
+///
+/// let bytes = fs::read("project.godot").unwrap();
+/// let project = parse_project_file_bytes(&bytes).value;
+///
+/// println!("{:?}", project.get("application", "config/name"));
+/// ```
+pub fn parse_project_file_bytes(input: &[u8]) -> ParsedBytes<ProjectFile<'_>> {
+    parse_property_file_bytes(input).map(ProjectFile::from_property_file)
+}
@@ -1,160 +1,209 @@
-use nom::{
-    IResult, Parser,
-    branch::alt,
-    bytes::complete::take_while1,
-    character::complete::{char, multispace0, multispace1},
-    multi::separated_list0,
-};
-
-#[derive(Debug, PartialEq, Clone)]
-pub struct UntypedProperty {
-    pub key: String,
-    pub value: String,
+use std::borrow::Cow;
+use std::fmt;
+
+use nom::IResult;
+
+use super::lexer::{TokenCursor, TokenKind};
+use super::source_map::Location;
+
+/// A `key=value` pair whose value has not been interpreted.
+///
+/// Key and value borrow from the parsed input where possible. Use `into_owned` to detach a
+/// property from the input buffer.
+///
+/// Quoted values are stored without their quotes and with escape sequences as written, so
+/// `quoted` tells `"42"` apart from `42`. Properties compare equal when their keys and values
+/// are equal, wherever they were read and whether or not the value was quoted.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UntypedProperty<'a> {
+    pub key: Cow<'a, str>,
+    pub value: Cow<'a, str>,
+    /// Whether the value was written in double quotes
+    pub quoted: bool,
+    /// Where the key was read
+    pub key_location: Location,
+    /// Where the value was read, including the quotes of a quoted value
+    pub value_location: Location,
 }
 
-#[doc(hidden)]
-pub fn properties0(input: &str) -> IResult<&str, Vec<UntypedProperty>> {
-    let (input, _) = multispace0(input)?;
-
-    let mut parser = separated_list0(multispace1, parse_property);
-    parser.parse(input)
-}
+impl<'a> UntypedProperty<'a> {
+    /// Creates a property from a key and an unquoted value, with default locations
+    pub fn new(key: impl Into<Cow<'a, str>>, value: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+            quoted: false,
+            key_location: Location::default(),
+            value_location: Location::default(),
+        }
+    }
 
-fn parse_property(input: &str) -> IResult<&str, UntypedProperty> {
-    let (input, key) = parse_key(input)?;
-    let (input, _) = multispace0(input)?;
-    let (input, _) = char('=')(input)?;
-    let (input, _) = multispace0(input)?;
-    let (input, value) = parse_value(input)?;
+    /// Creates a property whose value is written in double quotes. The value must already
+    /// have its `\` and `"` escaped, as in the file.
+    pub fn new_quoted(key: impl Into<Cow<'a, str>>, value: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            quoted: true,
+            ..Self::new(key, value)
+        }
+    }
 
-    Ok((
-        input,
+    /// Converts the property into one that owns its data and no longer borrows the input
+    pub fn into_owned(self) -> UntypedProperty<'static> {
         UntypedProperty {
-            key: key.to_string(),
-            value: value.to_string(),
-        },
-    ))
-}
+            key: Cow::Owned(self.key.into_owned()),
+            value: Cow::Owned(self.value.into_owned()),
+            quoted: self.quoted,
+            key_location: self.key_location,
+            value_location: self.value_location,
+        }
+    }
 
-fn parse_key(input: &str) -> IResult<&str, &str> {
-    take_while1(|c: char| c.is_alphanumeric() || c == '_' || c == '/')(input)
-}
+    /// The value as written in the file, with its quotes if it was quoted
+    pub fn value_text(&self) -> Cow<'_, str> {
+        if self.quoted {
+            Cow::Owned(format!("\"{}\"", self.value))
+        } else {
+            Cow::Borrowed(&self.value)
+        }
+    }
 
-fn parse_value(input: &str) -> IResult<&str, &str> {
-    let mut parser = alt((
-        parse_dict_value,
-        parse_array_value,
-        parse_quoted_string,
-        parse_unquoted_value,
-    ));
-    parser.parse(input)
-}
+    /// Where the whole `key = value` pair was read
+    pub fn location(&self) -> Location {
+        self.key_location.to(self.value_location)
+    }
 
-fn parse_dict_value(input: &str) -> IResult<&str, &str> {
-    let start = input;
-    let (input, _) = char('{')(input)?;
-    let (input, _) = parse_dict_content(input)?;
-    let (input, _) = char('}')(input)?;
-    let consumed_len = start.len() - input.len();
-    Ok((input, &start[..consumed_len]))
+    /// Moves the locations `offset` bytes and `lines` lines further into the input
+    pub(crate) fn shift(&mut self, offset: usize, lines: usize) {
+        self.key_location = self.key_location.shifted(offset, lines);
+        self.value_location = self.value_location.shifted(offset, lines);
+    }
 }
 
-fn parse_dict_content(input: &str) -> IResult<&str, ()> {
-    let mut depth = 0i32;
-    let mut in_string = false;
-    let mut escape_next = false;
+impl PartialEq for UntypedProperty<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key && self.value == other.value
+    }
+}
 
-    for (idx, ch) in input.char_indices() {
-        if escape_next {
-            escape_next = false;
-            continue;
-        }
+/// Writes the property as `key=value`, the way header attributes and project settings are
+/// written
+impl fmt::Display for UntypedProperty<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.key, self.value_text())
+    }
+}
 
-        match ch {
-            '\\' if in_string => escape_next = true,
-            '"' => in_string = !in_string,
-            '{' if !in_string => depth += 1,
-            '}' if !in_string => {
-                if depth == 0 {
-                    return Ok((&input[idx..], ()));
-                }
-                depth -= 1;
-            }
-            _ => {}
+#[doc(hidden)]
+pub fn properties0(input: &str) -> IResult<&str, Vec<UntypedProperty<'_>>> {
+    let mut cursor = TokenCursor::new(input);
+    let mut properties = Vec::new();
+
+    loop {
+        cursor.skip_newlines();
+        let start = cursor.offset();
+        match property(&mut cursor) {
+            Some(property) => properties.push(property),
+            None => return Ok((&input[start..], properties)),
         }
     }
-
-    Ok((input, ()))
 }
 
-fn parse_array_value(input: &str) -> IResult<&str, &str> {
-    let start = input;
-    let (input, _) = char('[')(input)?;
-    let (input, _) = parse_array_content(input)?;
-    let (input, _) = char(']')(input)?;
-    let consumed_len = start.len() - input.len();
-    Ok((input, &start[..consumed_len]))
+/// Reads a `key = value` property from the tokens at the cursor, returning `None` if they
+/// do not form one.
+///
+/// Keys are made of adjacent names, numbers and symbols, e.g. `config/name`, `0:0/0` or
+/// `path.s3tc`. Quoted values are returned without their quotes; other values span
+/// balanced brackets, braces and parentheses, so `Vector2(578, 325)` or a dictionary
+/// spanning several lines is a single value.
+pub(crate) fn property<'a>(cursor: &mut TokenCursor<'a>) -> Option<UntypedProperty<'a>> {
+    let first = cursor.peek()?;
+    if !matches!(first.kind, TokenKind::Identifier | TokenKind::Number) {
+        return None;
+    }
+    cursor.next();
+
+    let mut last = first;
+    while let Some(token) = cursor.peek() {
+        let continues_key = matches!(
+            token.kind,
+            TokenKind::Identifier | TokenKind::Number | TokenKind::Symbol | TokenKind::Colon
+        );
+        if !continues_key || !token.follows(&last) {
+            break;
+        }
+        last = token;
+        cursor.next();
+    }
+    let key_location = cursor.location(&first, &last);
+    let key = key_location.span.slice(cursor.input());
+
+    cursor.eat(TokenKind::Equals)?;
+    let quoted = cursor.peek()?.kind == TokenKind::String;
+    let (value, value_location) = raw_value(cursor)?;
+
+    Some(UntypedProperty {
+        key: Cow::Borrowed(key),
+        value: Cow::Borrowed(value),
+        quoted,
+        key_location,
+        value_location,
+    })
 }
 
-fn parse_array_content(input: &str) -> IResult<&str, ()> {
-    let mut depth = 0i32;
-    let mut in_string = false;
-    let mut escape_next = false;
-
-    for (idx, ch) in input.char_indices() {
-        if escape_next {
-            escape_next = false;
-            continue;
+/// Reads the text of a value without interpreting it
+fn raw_value<'a>(cursor: &mut TokenCursor<'a>) -> Option<(&'a str, Location)> {
+    let first = cursor.peek()?;
+    // Locate the value before consuming it, as strings and brackets may span lines
+    let mut location = cursor.location(&first, &first);
+    match first.kind {
+        TokenKind::String => {
+            cursor.next();
+            return Some((first.string_content(), location));
+        }
+        TokenKind::UnterminatedString => {
+            cursor.next();
+            return Some((first.text, location));
         }
+        TokenKind::Newline
+        | TokenKind::Comment
+        | TokenKind::Comma
+        | TokenKind::Equals
+        | TokenKind::CloseBracket
+        | TokenKind::CloseBrace
+        | TokenKind::CloseParen => return None,
+        _ => {}
+    }
 
-        match ch {
-            '\\' if in_string => escape_next = true,
-            '"' => in_string = !in_string,
-            '[' if !in_string => depth += 1,
-            ']' if !in_string => {
-                if depth == 0 {
-                    return Ok((&input[idx..], ()));
-                }
-                depth -= 1;
+    let mut depth = 0usize;
+    let mut last = first;
+    while let Some(token) = cursor.next() {
+        last = token;
+        match token.kind {
+            TokenKind::OpenBracket | TokenKind::OpenBrace | TokenKind::OpenParen => depth += 1,
+            TokenKind::CloseBracket | TokenKind::CloseBrace | TokenKind::CloseParen => {
+                depth = depth.saturating_sub(1)
             }
             _ => {}
         }
-    }
-
-    Ok((input, ()))
-}
-
-fn parse_quoted_string(input: &str) -> IResult<&str, &str> {
-    let (input, _) = char('"')(input)?;
-    let content_start = input;
-    let (input, _) = parse_string_content(input)?;
-    let content_len = content_start.len() - input.len();
-    let (input, _) = char('"')(input)?;
-    Ok((input, &content_start[..content_len]))
-}
-
-fn parse_string_content(input: &str) -> IResult<&str, ()> {
-    let mut escape_next = false;
 
-    for (idx, ch) in input.char_indices() {
-        if escape_next {
-            escape_next = false;
-            continue;
+        // A bracketed literal such as `[1, 2]` or `{ "a": 1 }` ends with its closing token
+        if depth == 0 && matches!(first.kind, TokenKind::OpenBracket | TokenKind::OpenBrace) {
+            break;
         }
 
-        match ch {
-            '\\' => escape_next = true,
-            '"' => return Ok((&input[idx..], ())),
-            _ => {}
+        let Some(next) = cursor.peek() else {
+            break;
+        };
+        let ends_value = matches!(
+            next.kind,
+            TokenKind::Comma | TokenKind::CloseBracket | TokenKind::Newline | TokenKind::Comment
+        );
+        if depth == 0 && (ends_value || !next.follows(&token)) {
+            break;
         }
     }
 
-    Err(nom::Err::Error(nom::error::Error::new(
-        input,
-        nom::error::ErrorKind::Char,
-    )))
-}
-
-fn parse_unquoted_value(input: &str) -> IResult<&str, &str> {
-    take_while1(|c: char| !c.is_whitespace() && c != ',' && c != ']')(input)
+    location.span.end = last.span.end;
+    Some((location.span.slice(cursor.input()), location))
 }
@@ -1,34 +1,211 @@
-use nom::{
-    IResult, Parser,
-    character::complete::{line_ending, multispace0, not_line_ending},
-    combinator::opt,
-    multi::many0,
-    sequence::terminated,
-};
+use std::borrow::Cow;
+use std::fmt;
 
-use super::parser_property::{UntypedProperty, properties0};
+use nom::IResult;
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct Section {
-    pub header_type: String,
-    pub properties: Vec<UntypedProperty>,
+use super::encoding::{DecodedInput, ParsedBytes, decode};
+use super::lexer::{TokenCursor, TokenKind};
+use super::parser_property::{UntypedProperty, property};
+use super::parser_section_iter::SectionIter;
+use super::source_map::Location;
+
+/// A `[header_type ...]` section with the properties of its header and body.
+///
+/// Header attributes come first, followed by the body properties in file order. Sections
+/// compare equal when their header types and properties are equal, wherever they were read.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Section<'a> {
+    pub header_type: Cow<'a, str>,
+    pub properties: Vec<UntypedProperty<'a>>,
+    /// How many of the `properties` are attributes of the header, e.g. `name` and `type`
+    /// in `[node name="Main" type="Node2D"]`
+    pub attribute_count: usize,
+    /// Where the section was read, from the opening `[` of the header to the end of the
+    /// last property
+    pub location: Location,
+}
+
+impl<'a> Section<'a> {
+    /// Creates a section without properties, with a default location
+    pub fn new(header_type: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            header_type: header_type.into(),
+            properties: Vec::new(),
+            attribute_count: 0,
+            location: Location::default(),
+        }
+    }
+
+    /// Converts the section into one that owns its data and no longer borrows the input
+    pub fn into_owned(self) -> Section<'static> {
+        Section {
+            header_type: Cow::Owned(self.header_type.into_owned()),
+            properties: self
+                .properties
+                .into_iter()
+                .map(UntypedProperty::into_owned)
+                .collect(),
+            attribute_count: self.attribute_count,
+            location: self.location,
+        }
+    }
+
+    /// The properties written in the header
+    pub fn attributes(&self) -> &[UntypedProperty<'a>] {
+        &self.properties[..self.attribute_count.min(self.properties.len())]
+    }
+
+    /// The properties written on the lines below the header
+    pub fn body(&self) -> &[UntypedProperty<'a>] {
+        &self.properties[self.attribute_count.min(self.properties.len())..]
+    }
+
+    /// Returns the first property with the given key
+    pub fn property(&self, key: &str) -> Option<&UntypedProperty<'a>> {
+        self.properties.iter().find(|p| p.key == key)
+    }
+
+    /// Moves the locations `offset` bytes and `lines` lines further into the input
+    pub(crate) fn shift(&mut self, offset: usize, lines: usize) {
+        self.location = self.location.shifted(offset, lines);
+        for property in &mut self.properties {
+            property.shift(offset, lines);
+        }
+    }
+
+    /// Returns the value of the first property with the given key
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.property(key).map(|p| p.value.as_ref())
+    }
+}
+
+/// Writes the section the way scenes and resources are saved: the header with its
+/// attributes, followed by a `key = value` line for every body property
+impl fmt::Display for Section<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}", self.header_type)?;
+        for attribute in self.attributes() {
+            write!(f, " {}", attribute)?;
+        }
+        writeln!(f, "]")?;
+        for property in self.body() {
+            writeln!(f, "{} = {}", property.key, property.value_text())?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for Section<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.header_type == other.header_type && self.properties == other.properties
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
-pub struct PropertyFile {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PropertyFile<'a> {
     /// Properties that appear before the first section (e.g., config_version in .godot files)
-    pub preamble_properties: Vec<UntypedProperty>,
-    pub sections: Vec<Section>,
+    pub preamble_properties: Vec<UntypedProperty<'a>>,
+    pub sections: Vec<Section<'a>>,
+}
+
+impl PropertyFile<'_> {
+    /// Converts the file into one that owns its data and no longer borrows the input
+    pub fn into_owned(self) -> PropertyFile<'static> {
+        PropertyFile {
+            preamble_properties: self
+                .preamble_properties
+                .into_iter()
+                .map(UntypedProperty::into_owned)
+                .collect(),
+            sections: self.sections.into_iter().map(Section::into_owned).collect(),
+        }
+    }
+}
+
+/// Writes the file the way scenes and resources are saved, with a blank line between sections
+impl fmt::Display for PropertyFile<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for property in &self.preamble_properties {
+            writeln!(f, "{} = {}", property.key, property.value_text())?;
+        }
+        write_sections(f, &self.sections, !self.preamble_properties.is_empty())
+    }
+}
+
+/// Writes `sections` separated by blank lines, starting with one if `separate_first` is set
+pub(crate) fn write_sections(
+    f: &mut fmt::Formatter<'_>,
+    sections: &[Section],
+    separate_first: bool,
+) -> fmt::Result {
+    for (i, section) in sections.iter().enumerate() {
+        if i > 0 || separate_first {
+            writeln!(f)?;
+        }
+        write!(f, "{}", section)?;
+    }
+    Ok(())
+}
+
+/// Why a property file could not be parsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseErrorKind {
+    /// A `[` at the start of a line is not followed by a section name
+    MissingSectionName,
+    /// A section header ends before its closing `]`
+    UnclosedHeader,
+    /// A string is not closed before the end of the input
+    UnterminatedString,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ParseErrorKind::MissingSectionName => "section header has no name",
+            ParseErrorKind::UnclosedHeader => "section header is not closed with `]`",
+            ParseErrorKind::UnterminatedString => "string is not closed before the end of the file",
+        })
+    }
+}
+
+/// A malformed section header or unterminated string. Parsing stops there, as the
+/// sections that follow cannot be told apart reliably.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    /// The token that breaks the file, or the empty location at the end of the line or
+    /// input where `]` was expected
+    pub location: Location,
+}
+
+impl ParseError {
+    pub(crate) fn new(kind: ParseErrorKind, location: Location) -> Self {
+        Self { kind, location }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.kind)
+    }
 }
 
+impl std::error::Error for ParseError {}
+
 /// Parses any Godot property file into generic sections with key-value pairs.
 ///
 /// This is a low-level parser that returns untyped sections and properties without
 /// categorization. Use `parse_scene_file` or `parse_project_file` for structured
 /// access to specific file types.
 ///
+/// The result borrows from `input` and does not copy keys or values; call
+/// `PropertyFile::into_owned` to keep it beyond the lifetime of the input.
+///
 /// Each section starts with `[header_type ...]` and contains key-value properties.
-/// This parser handles `.tscn`, `.godot`, `.tres`, and similar Godot file formats.
+/// This parser handles `.tscn`, `.godot`, `.tres`, and similar Godot file formats. Use
+/// `SectionIter` to read sections one at a time instead.
 ///
 /// # Arguments
 ///
@@ -39,6 +216,10 @@ pub struct PropertyFile {
 /// * `Ok((remaining, PropertyFile))` - Successfully parsed file with sections
 /// * `Err(nom::Err)` - Parse error if the file format is invalid
 ///
+/// Parsing stops at the first malformed section header or unterminated string and the
+/// rest of the input is returned in `remaining`. Use `try_parse_property_file` to get
+/// the reason and location as a `ParseError` instead.
+///
 /// # Example
 ///
 /// ```no_run
@@ -55,264 +236,264 @@ pub struct PropertyFile {
 ///     }
 /// }
 /// ```
-pub fn parse_property_file(input: &str) -> IResult<&str, PropertyFile> {
-    let (input, _) = multispace0(input)?;
-
-    // Parse preamble properties (before first section)
-    let (input, preamble_properties) = parse_preamble(input)?;
-
-    let (input, sections) = many0(parse_section).parse(input)?;
-    let (input, _) = multispace0(input)?;
-
-    Ok((
-        input,
-        PropertyFile {
-            preamble_properties,
-            sections,
-        },
-    ))
+pub fn parse_property_file(input: &str) -> IResult<&str, PropertyFile<'_>> {
+    let (file, remaining, _) = property_file(input);
+    Ok((remaining, file))
 }
 
-/// Parses properties and comments that appear before the first section
-fn parse_preamble(input: &str) -> IResult<&str, Vec<UntypedProperty>> {
-    let mut remaining = input;
-    let mut properties = Vec::new();
-
-    loop {
-        // Skip whitespace
-        let (next_input, _) = multispace0(remaining)?;
-
-        // Check if we hit a section start or EOF
-        if next_input.is_empty() || next_input.starts_with('[') {
-            return Ok((next_input, properties));
-        }
-
-        // Try to parse a comment line (skip it)
-        if let Ok((next_input, _)) = comment_line(next_input) {
-            let (next_input, _) = opt(line_ending).parse(next_input)?;
-            remaining = next_input;
-            continue;
-        }
-
-        // Try to parse a property line
-        let (next_input, line) = parse_property_line(next_input)?;
+/// Parses any Godot property file like `parse_property_file`, failing on the first
+/// malformed section header or unterminated string.
+///
+/// # Returns
+///
+/// * `Ok(PropertyFile)` - The whole input was parsed
+/// * `Err(ParseError)` - The reason parsing stopped and where
+///
+/// # Example
+///
+/// ```no_run
+/// use godot_properties_parser::try_parse_property_file;
+/// use std::fs;
+///
+/// let content = fs::read_to_string("file.tscn").unwrap();
+/// match try_parse_property_file(&content) {
+///     Ok(file) => println!("Sections: {}", file.sections.len()),
+///     Err(error) => eprintln!("file.tscn:{}", error),
+/// }
+/// ```
+pub fn try_parse_property_file(input: &str) -> Result<PropertyFile<'_>, ParseError> {
+    match property_file(input) {
+        (file, _, None) => Ok(file),
+        (_, _, Some(error)) => Err(error),
+    }
+}
 
-        // Skip empty lines
-        if line.trim().is_empty() {
-            remaining = next_input;
-            continue;
+/// Parses the raw bytes of a property file, such as the contents read with `fs::read`.
+///
+/// A UTF-8 byte order mark is skipped and `\r\n` and lone `\r` line endings are read as
+/// `\n`, so files saved by Windows editors parse like any other. Invalid UTF-8 does not
+/// fail the file: each bad sequence is replaced with U+FFFD and reported with its line
+/// and column in `ParsedBytes::diagnostics`. Parse errors do not fail the file either: the
+/// sections before the error are returned, with the error in `ParsedBytes::error`.
+///
+/// The result borrows from `input` when it is valid UTF-8 without a byte order mark or
+/// carriage returns; otherwise it owns the decoded text.
+///
+/// # Example
+///
+/// ```no_run
+/// use godot_properties_parser::parse_property_file_bytes;
+/// use std::fs;
+///
+/// let bytes = fs::read("file.tscn").unwrap();
+/// let parsed = parse_property_file_bytes(&bytes);
+///
+/// for diagnostic in &parsed.diagnostics {
+///     eprintln!("file.tscn:{}", diagnostic);
+/// }
+/// println!("Sections: {}", parsed.value.sections.len());
+/// ```
+pub fn parse_property_file_bytes(input: &[u8]) -> ParsedBytes<PropertyFile<'_>> {
+    let DecodedInput { text, diagnostics } = decode(input);
+    let (value, remaining, error) = match text {
+        Cow::Borrowed(text) => {
+            let (file, remaining, error) = property_file(text);
+            (file, remaining.to_string(), error)
         }
-
-        // Try to parse properties from this line
-        match properties0(&line) {
-            Ok((_, props)) => {
-                properties.extend(props);
-            }
-            Err(_) => {
-                // Not a valid property line, stop preamble parsing
-                return Ok((remaining, properties));
-            }
+        Cow::Owned(text) => {
+            let (file, remaining, error) = property_file(&text);
+            (file.into_owned(), remaining.to_string(), error)
         }
+    };
 
-        remaining = next_input;
+    ParsedBytes {
+        value,
+        remaining,
+        error,
+        diagnostics,
     }
 }
 
-fn parse_section(input: &str) -> IResult<&str, Section> {
-    // Skip any empty lines or comments before the section
-    let (input, _) = many0(terminated(opt(comment_line), line_ending)).parse(input)?;
-
-    // Parse the header line: [header_type key=value key=value ...]
-    let (input, header_line) = not_line_ending(input)?;
-    let (input, _) = opt(line_ending).parse(input)?;
-
-    // Parse using a custom approach to extract header_type and properties from header
-    let (header_type, mut properties) = parse_header_line(header_line)?;
+/// Parses the sections up to the first error, returning them with the input that was not
+/// parsed and the error
+fn property_file(input: &str) -> (PropertyFile<'_>, &str, Option<ParseError>) {
+    let mut iter = SectionIter::new(input);
+    let preamble_properties = std::mem::take(&mut iter.preamble_properties);
+    let sections = iter.by_ref().collect();
 
-    // Parse additional property lines that follow the header (until next [ or EOF)
-    let (input, body_properties) = parse_section_body(input)?;
-    properties.extend(body_properties);
-
-    Ok((
-        input,
-        Section {
-            header_type,
-            properties,
+    (
+        PropertyFile {
+            preamble_properties,
+            sections,
         },
-    ))
+        iter.remaining(),
+        iter.error(),
+    )
 }
 
-fn parse_section_body(input: &str) -> IResult<&str, Vec<UntypedProperty>> {
-    let mut remaining = input;
-    let mut all_properties = Vec::new();
+/// Parses property lines into `properties`, skipping blank lines and `;` comments, until
+/// the next section header or the end of the input.
+///
+/// Lines that do not hold a property are skipped, as is anything after the last property
+/// of a line. A value with an unterminated string is an error, as it takes up the rest of
+/// the input.
+pub(crate) fn parse_properties<'a>(
+    cursor: &mut TokenCursor<'a>,
+    properties: &mut Vec<UntypedProperty<'a>>,
+) -> Result<(), ParseError> {
+    let mut line_start = true;
 
     loop {
-        // Skip empty lines
-        let (next_input, _) = multispace0(remaining)?;
-
-        // Check if we hit another section or EOF
-        if next_input.is_empty() || next_input.starts_with('[') {
-            return Ok((next_input, all_properties));
+        while let Some(token) = cursor.peek() {
+            match token.kind {
+                TokenKind::Newline => line_start = true,
+                TokenKind::Comment => {}
+                _ => break,
+            }
+            cursor.next();
         }
 
-        // Try to parse a property line (potentially multi-line)
-        let (next_input, line) = parse_property_line(next_input)?;
-
-        // Skip empty or comment lines
-        if line.trim().is_empty() || line.trim().starts_with(';') {
-            remaining = next_input;
-            continue;
+        match cursor.peek() {
+            None => return Ok(()),
+            Some(token) if token.kind == TokenKind::OpenBracket && line_start => {
+                return Ok(());
+            }
+            Some(_) => {}
         }
 
-        // Try to parse properties from this line
-        match properties0(&line) {
-            Ok((_, props)) => {
-                all_properties.extend(props);
+        match property(cursor) {
+            Some(property) => {
+                if is_unterminated(&property) {
+                    return Err(ParseError::new(
+                        ParseErrorKind::UnterminatedString,
+                        property.value_location,
+                    ));
+                }
+                properties.push(property);
+                line_start = false;
             }
-            Err(_) => {
-                // Not a valid property line, might be end of section
-                return Ok((remaining, all_properties));
+            None => {
+                skip_line(cursor)?;
+                line_start = true;
             }
         }
-
-        remaining = next_input;
     }
 }
 
-/// Parses a property line, which may span multiple lines if it contains:
-/// - Multi-line quoted strings
-/// - Multi-line brace literals {...}
-/// - Multi-line bracket literals [...]
-fn parse_property_line(input: &str) -> IResult<&str, String> {
-    let mut accumulated = String::new();
-    let mut remaining = input;
-    let mut in_string = false;
-    let mut escape_next = false;
-    let mut brace_depth: i32 = 0;
-    let mut bracket_depth: i32 = 0;
-    let mut line_count = 0;
+/// Skips the rest of the line, failing if it holds a string that is never closed
+fn skip_line(cursor: &mut TokenCursor) -> Result<(), ParseError> {
+    match cursor.skip_line() {
+        Some(string) => {
+            let location = cursor.location(&string, &string);
+            Err(ParseError::new(
+                ParseErrorKind::UnterminatedString,
+                location,
+            ))
+        }
+        None => Ok(()),
+    }
+}
 
-    loop {
-        // Parse one line
-        let (next_input, line) = not_line_ending(remaining)?;
-        let (next_input, _) = opt(line_ending).parse(next_input)?;
+/// Returns true if the value of `property` is a string that runs to the end of the input
+fn is_unterminated(property: &UntypedProperty) -> bool {
+    // Terminated strings are stored without their quotes
+    !property.quoted && property.value.starts_with('"')
+}
 
-        line_count += 1;
-        if line_count > 1 {
-            accumulated.push('\n');
-        }
-        accumulated.push_str(line);
+/// Parses the header line of a section into a section holding its type and attributes
+pub(crate) fn parse_header<'a>(cursor: &mut TokenCursor<'a>) -> Result<Section<'a>, ParseError> {
+    let open = cursor
+        .eat(TokenKind::OpenBracket)
+        .ok_or_else(|| missing(cursor, ParseErrorKind::MissingSectionName))?;
+    let mut location = cursor.location(&open, &open);
+    let first = cursor
+        .eat(TokenKind::Identifier)
+        .ok_or_else(|| missing(cursor, ParseErrorKind::MissingSectionName))?;
+    // Names such as `preset.0.options` are read as several tokens without space in between
+    let mut last = first.span;
+    while let Some(token) = cursor.peek()
+        && token.span.start == last.end
+        && !matches!(token.kind, TokenKind::CloseBracket | TokenKind::Newline)
+    {
+        last = token.span;
+        cursor.next();
+    }
+    let header_type = &cursor.input()[first.span.start..last.end];
 
-        // Track string state, brace depth, and bracket depth in this line
-        for ch in line.chars() {
-            if escape_next {
-                escape_next = false;
-                continue;
+    let mut properties = Vec::new();
+    loop {
+        let Some(token) = cursor.peek() else {
+            return Err(missing(cursor, ParseErrorKind::UnclosedHeader));
+        };
+        match token.kind {
+            TokenKind::CloseBracket => {
+                cursor.next();
+                break;
             }
-
-            if in_string {
-                match ch {
-                    '\\' => escape_next = true,
-                    '"' => in_string = false,
-                    _ => {}
+            TokenKind::Newline => return Err(missing(cursor, ParseErrorKind::UnclosedHeader)),
+            _ => match property(cursor) {
+                Some(property) if is_unterminated(&property) => {
+                    return Err(ParseError::new(
+                        ParseErrorKind::UnterminatedString,
+                        property.value_location,
+                    ));
                 }
-            } else {
-                match ch {
-                    '"' => in_string = true,
-                    '{' => brace_depth += 1,
-                    '}' => brace_depth = brace_depth.saturating_sub(1),
-                    '[' => bracket_depth += 1,
-                    ']' => bracket_depth = bracket_depth.saturating_sub(1),
-                    _ => {}
+                Some(property) => properties.push(property),
+                None => {
+                    // Keep the attributes read so far and skip to the closing bracket
+                    skip_to_closing_bracket(cursor)?;
+                    break;
                 }
-            }
-        }
-
-        // If we're not in a string and all braces/brackets are closed, we're done
-        if !in_string && brace_depth == 0 && bracket_depth == 0 {
-            return Ok((next_input, accumulated));
-        }
-
-        // Continue to next line if we're still inside a structure
-        remaining = next_input;
-
-        // Safety check: don't consume entire file
-        if remaining.is_empty() {
-            // Unclosed structure - return what we have
-            return Ok((remaining, accumulated));
+            },
         }
     }
-}
 
-fn parse_header_line(
-    line: &str,
-) -> Result<(String, Vec<UntypedProperty>), nom::Err<nom::error::Error<&str>>> {
-    use nom::character::complete::char;
-
-    // Start parsing: expect '['
-    let (line, _) = char('[')(line)?;
-
-    // Parse the header type (alphanumeric + underscore)
-    let (line, header_type) =
-        nom::bytes::complete::take_while1(|c: char| c.is_alphanumeric() || c == '_')(line)?;
-
-    // Consume optional whitespace
-    let (line, _) = multispace0(line)?;
-
-    // Parse properties until we hit ']'
-    let properties = if line.starts_with(']') {
-        Vec::new()
-    } else {
-        // Find the closing bracket, accounting for nested brackets and strings
-        let content = extract_until_closing_bracket(line)?;
-        match properties0(content) {
-            Ok((_, props)) => props,
-            Err(_) => Vec::new(),
-        }
-    };
+    location.span.end = cursor.consumed();
 
-    Ok((header_type.to_string(), properties))
+    // Ignore anything else on the header line
+    skip_line(cursor)?;
+    Ok(Section {
+        header_type: Cow::Borrowed(header_type),
+        attribute_count: properties.len(),
+        properties,
+        location,
+    })
 }
 
-fn extract_until_closing_bracket(input: &str) -> Result<&str, nom::Err<nom::error::Error<&str>>> {
-    let mut depth = 0i32;
-    let mut in_string = false;
-    let mut escape_next = false;
-
-    for (idx, ch) in input.char_indices() {
-        if escape_next {
-            escape_next = false;
-            continue;
-        }
+/// An error for a token missing at the cursor, located at the next token or the end of
+/// the input
+fn missing(cursor: &mut TokenCursor, kind: ParseErrorKind) -> ParseError {
+    let offset = cursor.offset();
+    ParseError::new(kind, cursor.location_at(offset))
+}
 
-        match ch {
-            '\\' if in_string => escape_next = true,
-            '"' => in_string = !in_string,
-            '[' if !in_string => depth += 1,
-            ']' if !in_string => {
-                if depth == 0 {
-                    return Ok(&input[..idx]);
-                }
-                depth -= 1;
+/// Consumes tokens up to and including the `]` that closes the current header, accounting
+/// for nested brackets. Fails if the line ends first.
+fn skip_to_closing_bracket(cursor: &mut TokenCursor) -> Result<(), ParseError> {
+    let mut depth = 0usize;
+    loop {
+        let Some(token) = cursor.peek() else {
+            return Err(missing(cursor, ParseErrorKind::UnclosedHeader));
+        };
+        match token.kind {
+            TokenKind::OpenBracket => depth += 1,
+            TokenKind::CloseBracket if depth == 0 => {
+                cursor.next();
+                return Ok(());
+            }
+            TokenKind::CloseBracket => depth -= 1,
+            TokenKind::Newline if depth == 0 => {
+                return Err(missing(cursor, ParseErrorKind::UnclosedHeader));
+            }
+            TokenKind::UnterminatedString => {
+                let location = cursor.location(&token, &token);
+                return Err(ParseError::new(
+                    ParseErrorKind::UnterminatedString,
+                    location,
+                ));
             }
             _ => {}
         }
-    }
-
-    Err(nom::Err::Error(nom::error::Error::new(
-        input,
-        nom::error::ErrorKind::Eof,
-    )))
-}
-
-fn comment_line(input: &str) -> IResult<&str, ()> {
-    let (input, line) = not_line_ending(input)?;
-    if line.trim().is_empty() || line.trim().starts_with(';') {
-        Ok((input, ()))
-    } else {
-        Err(nom::Err::Error(nom::error::Error::new(
-            input,
-            nom::error::ErrorKind::Tag,
-        )))
+        cursor.next();
     }
 }
@@ -1,77 +1,96 @@
-use super::parser_property_file::{PropertyFile, Section, parse_property_file};
+use std::fmt;
+
 use nom::IResult;
 
+use super::encoding::ParsedBytes;
+use super::parser_property_file::{
+    ParseError, PropertyFile, Section, parse_property_file, parse_property_file_bytes,
+    try_parse_property_file, write_sections,
+};
+
 #[derive(Debug, PartialEq, Clone)]
-pub struct SceneFile {
-    /// The main scene header (usually gd_scene or gd_resource)
-    pub header: Option<Section>,
-    /// External resource sections
-    pub ext_resources: Vec<Section>,
-    /// Sub-resource sections
-    pub sub_resources: Vec<Section>,
-    /// Node sections
-    pub nodes: Vec<Section>,
-    /// Connection sections (signals)
-    pub connections: Vec<Section>,
-    /// Editable sections
-    pub editables: Vec<Section>,
-    /// All sections in order, including those not categorized above
-    pub all_sections: Vec<Section>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SceneFile<'a> {
+    /// All sections in file order. The categorized accessors such as `nodes` are views
+    /// into this list.
+    pub all_sections: Vec<Section<'a>>,
 }
 
-impl SceneFile {
+impl<'a> SceneFile<'a> {
     /// Create a new empty SceneFile
     pub fn new() -> Self {
         Self {
-            header: None,
-            ext_resources: Vec::new(),
-            sub_resources: Vec::new(),
-            nodes: Vec::new(),
-            connections: Vec::new(),
-            editables: Vec::new(),
             all_sections: Vec::new(),
         }
     }
 
-    /// Create a SceneFile from a PropertyFile by categorizing sections
-    pub fn from_property_file(property_file: PropertyFile) -> Self {
-        let mut scene_file = SceneFile::new();
-
-        for section in property_file.sections {
-            // Store in all_sections
-            scene_file.all_sections.push(section.clone());
-
-            // Categorize by header type
-            match section.header_type.as_str() {
-                "gd_scene" | "gd_resource" => {
-                    scene_file.header = Some(section);
-                }
-                "ext_resource" => {
-                    scene_file.ext_resources.push(section);
-                }
-                "sub_resource" => {
-                    scene_file.sub_resources.push(section);
-                }
-                "node" => {
-                    scene_file.nodes.push(section);
-                }
-                "connection" => {
-                    scene_file.connections.push(section);
-                }
-                "editable" => {
-                    scene_file.editables.push(section);
-                }
-                _ => {
-                    // Other types stay only in all_sections
-                }
-            }
+    /// Create a SceneFile from a PropertyFile. Sections are kept as written; the
+    /// categorized accessors select them by header type.
+    pub fn from_property_file(property_file: PropertyFile<'a>) -> Self {
+        Self {
+            all_sections: property_file.sections,
+        }
+    }
+
+    /// Converts the scene into one that owns its data and no longer borrows the input
+    pub fn into_owned(self) -> SceneFile<'static> {
+        SceneFile {
+            all_sections: self
+                .all_sections
+                .into_iter()
+                .map(Section::into_owned)
+                .collect(),
         }
+    }
+
+    /// The main scene header (usually gd_scene or gd_resource)
+    pub fn header(&self) -> Option<&Section<'a>> {
+        self.all_sections
+            .iter()
+            .rfind(|s| matches!(s.header_type.as_ref(), "gd_scene" | "gd_resource"))
+    }
+
+    /// External resource sections
+    pub fn ext_resources(&self) -> Vec<&Section<'a>> {
+        self.sections_of("ext_resource")
+    }
+
+    /// Sub-resource sections
+    pub fn sub_resources(&self) -> Vec<&Section<'a>> {
+        self.sections_of("sub_resource")
+    }
 
-        scene_file
+    /// Node sections
+    pub fn nodes(&self) -> Vec<&Section<'a>> {
+        self.sections_of("node")
+    }
+
+    /// Connection sections (signals)
+    pub fn connections(&self) -> Vec<&Section<'a>> {
+        self.sections_of("connection")
+    }
+
+    /// Editable sections
+    pub fn editables(&self) -> Vec<&Section<'a>> {
+        self.sections_of("editable")
+    }
+
+    fn sections_of(&self, header_type: &str) -> Vec<&Section<'a>> {
+        self.all_sections
+            .iter()
+            .filter(|s| s.header_type == header_type)
+            .collect()
+    }
+}
+
+/// Writes the scene in Godot's text format, with a blank line between sections
+impl fmt::Display for SceneFile<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_sections(f, &self.all_sections, false)
     }
 }
 
-impl Default for SceneFile {
+impl Default for SceneFile<'_> {
     fn default() -> Self {
         Self::new()
     }
@@ -79,9 +98,10 @@ impl Default for SceneFile {
 
 /// Parses a Godot scene file (`.tscn`) into a structured `SceneFile`.
 ///
-/// Scene files define game objects, their components, and relationships. This parser
-/// categorizes sections into `ext_resources`, `sub_resources`, `nodes`, `connections`,
-/// and `editables` for easy access, while preserving all sections in `all_sections`.
+/// Scene files define game objects, their components, and relationships. `SceneFile`
+/// keeps all sections in `all_sections` and selects `ext_resources`, `sub_resources`,
+/// `nodes`, `connections`, and `editables` from it for easy access. The result borrows
+/// from `input`; call `SceneFile::into_owned` to keep it longer.
 ///
 /// # Arguments
 ///
@@ -101,11 +121,40 @@ impl Default for SceneFile {
 /// let content = fs::read_to_string("scene.tscn").unwrap();
 /// let (remaining, scene) = parse_scene_file(&content).unwrap();
 ///
-/// println!("Nodes: {}", scene.nodes.len());
-/// println!("Resources: {}", scene.ext_resources.len());
+/// println!("Nodes: {}", scene.nodes().len());
+/// println!("Resources: {}", scene.ext_resources().len());
 /// ```
-pub fn parse_scene_file(input: &str) -> IResult<&str, SceneFile> {
+pub fn parse_scene_file(input: &str) -> IResult<&str, SceneFile<'_>> {
     let (remaining, property_file) = parse_property_file(input)?;
     let scene_file = SceneFile::from_property_file(property_file);
     Ok((remaining, scene_file))
 }
+
+/// Parses a Godot scene file (`.tscn`), failing on the first malformed section header or
+/// unterminated string. See `try_parse_property_file`.
+pub fn try_parse_scene_file(input: &str) -> Result<SceneFile<'_>, ParseError> {
+    try_parse_property_file(input).map(SceneFile::from_property_file)
+}
+
+/// Parses the raw bytes of a scene file (`.tscn`) into a `SceneFile`.
+///
+/// Byte order marks, Windows line endings and invalid UTF-8 are handled as described in
+/// `parse_property_file_bytes`.
+///
+/// # Example
+///
+/// ```no_run
+/// use godot_properties_parser::parse_scene_file_bytes;
+/// use std::fs;
+///
+/// let bytes = fs::read("scene.tscn").unwrap();
+/// let parsed = parse_scene_file_bytes(&bytes);
+///
+/// for diagnostic in &parsed.diagnostics {
+///     eprintln!("scene.tscn:{}", diagnostic);
+/// }
+/// println!("Nodes: {}", parsed.value.nodes().len());
+/// ```
+pub fn parse_scene_file_bytes(input: &[u8]) -> ParsedBytes<SceneFile<'_>> {
+    parse_property_file_bytes(input).map(SceneFile::from_property_file)
+}
@@ -0,0 +1,399 @@
+use std::collections::VecDeque;
+use std::io::{self, BufRead};
+
+use super::encoding::{EncodingDiagnostic, decode_from};
+use super::lexer::{Lexer, Token, TokenCursor, TokenKind};
+use super::parser_property::UntypedProperty;
+use super::parser_property_file::{
+    ParseError, ParseErrorKind, Section, parse_header, parse_properties,
+};
+
+/// A lazy iterator over the sections of a property file held in memory.
+///
+/// Sections are parsed one at a time as the iterator advances, so stopping early leaves
+/// the rest of the input untouched. With `skip_bodies` only section headers are parsed
+/// and body lines are skipped without reading their values.
+///
+/// # Example
+///
+/// ```
+/// use godot_properties_parser::SectionIter;
+///
+/// let input = "[gd_scene format=3]\n\n[ext_resource type=\"Script\" path=\"res://a.gd\" id=\"1\"]\n\n[node name=\"Root\" type=\"Node\"]\nscript = ExtResource(\"1\")\n";
+///
+/// let paths: Vec<_> = SectionIter::new(input)
+///     .skip_bodies()
+///     .take_while(|s| s.header_type != "node")
+///     .filter(|s| s.header_type == "ext_resource")
+///     .filter_map(|s| s.get("path").map(str::to_string))
+///     .collect();
+/// assert_eq!(paths, vec!["res://a.gd"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SectionIter<'a> {
+    cursor: TokenCursor<'a>,
+    pub(crate) preamble_properties: Vec<UntypedProperty<'a>>,
+    skip_bodies: bool,
+    stopped_at: Option<usize>,
+    error: Option<ParseError>,
+}
+
+impl<'a> SectionIter<'a> {
+    /// Creates an iterator over the sections of `input`. Properties before the first
+    /// section are read immediately, see `preamble_properties`.
+    pub fn new(input: &'a str) -> Self {
+        let mut cursor = TokenCursor::new(input);
+        let mut preamble_properties = Vec::new();
+        let result = parse_properties(&mut cursor, &mut preamble_properties);
+        let mut iter = Self {
+            cursor,
+            preamble_properties,
+            skip_bodies: false,
+            stopped_at: None,
+            error: None,
+        };
+        if let Err(error) = result {
+            iter.stop(error.location.span.start, error);
+        }
+        iter
+    }
+
+    /// Yields sections with their header attributes only, skipping the body lines
+    pub fn skip_bodies(mut self) -> Self {
+        self.skip_bodies = true;
+        self
+    }
+
+    /// Properties that appear before the first section (e.g., config_version in .godot files)
+    pub fn preamble_properties(&self) -> &[UntypedProperty<'a>] {
+        &self.preamble_properties
+    }
+
+    /// The input that was not parsed because of a malformed section header or an
+    /// unterminated string, or an empty string if every section was read
+    pub fn remaining(&self) -> &'a str {
+        match self.stopped_at {
+            Some(offset) => &self.cursor.input()[offset..],
+            None => "",
+        }
+    }
+
+    /// Why the iterator stopped before the end of the input, if it did
+    pub fn error(&self) -> Option<ParseError> {
+        self.error
+    }
+
+    fn stop(&mut self, offset: usize, error: ParseError) {
+        self.stopped_at = Some(offset);
+        self.error = Some(error);
+    }
+}
+
+impl<'a> Iterator for SectionIter<'a> {
+    type Item = Section<'a>;
+
+    fn next(&mut self) -> Option<Section<'a>> {
+        if self.stopped_at.is_some() {
+            return None;
+        }
+        self.cursor.peek()?;
+
+        let start = self.cursor.offset();
+        let mut section = match parse_header(&mut self.cursor) {
+            Ok(section) => section,
+            Err(error) => {
+                // A malformed header ends parsing; the rest is returned unparsed
+                self.stop(start, error);
+                return None;
+            }
+        };
+
+        if self.skip_bodies {
+            if let Some(string) = BodySkip::default().skip(&mut self.cursor) {
+                let location = self.cursor.location(&string, &string);
+                let error = ParseError::new(ParseErrorKind::UnterminatedString, location);
+                self.stop(string.span.start, error);
+            }
+        } else {
+            // Parse the property lines that follow the header (until next [ or EOF)
+            let result = parse_properties(&mut self.cursor, &mut section.properties);
+            if section.properties.len() > section.attribute_count
+                && let Some(last) = section.properties.last()
+            {
+                section.location.span.end = last.value_location.span.end;
+            }
+            if let Err(error) = result {
+                // The section is complete up to the error, which ends parsing
+                self.stop(error.location.span.start, error);
+            }
+        }
+
+        Some(section)
+    }
+}
+
+/// A lazy iterator over the sections of a property file read from any `BufRead`.
+///
+/// Only the current section is held in memory: lines are read up to the next section
+/// header and parsed like `SectionIter` does, and the yielded sections own their data.
+/// With `skip_bodies` body lines are read and discarded without being parsed.
+///
+/// Input is read as bytes and decoded like `parse_property_file_bytes` does: a byte order
+/// mark and Windows line endings are accepted, and invalid UTF-8 is replaced and reported
+/// in `diagnostics` instead of failing the read.
+///
+/// # Example
+///
+/// ```no_run
+/// use godot_properties_parser::SectionReader;
+/// use std::fs::File;
+/// use std::io::BufReader;
+///
+/// let file = BufReader::new(File::open("level.tscn").unwrap());
+/// for section in SectionReader::new(file).skip_bodies() {
+///     let section = section.unwrap();
+///     if section.header_type == "node" {
+///         break;
+///     }
+///     println!("{:?}", section.get("path"));
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SectionReader<R> {
+    reader: R,
+    skip_bodies: bool,
+    preamble_properties: Option<Vec<UntypedProperty<'static>>>,
+    /// The header line of the next section, already read from `reader`, with its offset
+    /// and number of preceding lines in the decoded text
+    next_header: Option<(String, usize, usize)>,
+    /// Decoded lines that have been read from `reader` but not consumed yet
+    lines: VecDeque<String>,
+    /// Byte offset and 1-based line number of the next chunk read from `reader`
+    offset: usize,
+    line: usize,
+    /// Offset in the decoded text and number of lines before the next line in `lines`
+    text_offset: usize,
+    text_lines: usize,
+    diagnostics: Vec<EncodingDiagnostic>,
+    done: bool,
+}
+
+impl<R: BufRead> SectionReader<R> {
+    /// Creates a reader over the sections of `reader`
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            skip_bodies: false,
+            preamble_properties: None,
+            next_header: None,
+            lines: VecDeque::new(),
+            offset: 0,
+            line: 1,
+            text_offset: 0,
+            text_lines: 0,
+            diagnostics: Vec::new(),
+            done: false,
+        }
+    }
+
+    /// Yields sections with their header attributes only, skipping the body lines
+    pub fn skip_bodies(mut self) -> Self {
+        self.skip_bodies = true;
+        self
+    }
+
+    /// Properties that appear before the first section, reading them if needed
+    pub fn preamble_properties(&mut self) -> io::Result<&[UntypedProperty<'static>]> {
+        self.read_preamble()?;
+        Ok(self.preamble_properties.as_deref().unwrap_or_default())
+    }
+
+    /// Invalid UTF-8 sequences found in the input read so far
+    pub fn diagnostics(&self) -> &[EncodingDiagnostic] {
+        &self.diagnostics
+    }
+
+    fn read_preamble(&mut self) -> io::Result<()> {
+        if self.preamble_properties.is_some() {
+            return Ok(());
+        }
+
+        let mut preamble = String::new();
+        let mut scanner = BodyLines::default();
+        while let Some(line) = self.read_line()? {
+            if scanner.starts_header(&line) {
+                self.next_header = Some(self.located(line));
+                break;
+            }
+            scanner.feed(&line);
+            preamble.push_str(&line);
+        }
+
+        let mut cursor = TokenCursor::new(&preamble);
+        let mut properties = Vec::new();
+        let result = parse_properties(&mut cursor, &mut properties);
+        let properties = properties
+            .into_iter()
+            .map(UntypedProperty::into_owned)
+            .collect();
+        self.preamble_properties = Some(properties);
+        result.map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        while self.lines.is_empty() {
+            let mut bytes = Vec::new();
+            if self.reader.read_until(b'\n', &mut bytes)? == 0 {
+                return Ok(None);
+            }
+
+            // A chunk ends at `\n`, so it holds several lines only with lone `\r` endings
+            let decoded = decode_from(&bytes, self.offset, self.line);
+            self.offset += bytes.len();
+            self.line += decoded.text.matches('\n').count();
+            self.diagnostics.extend(decoded.diagnostics);
+            self.lines
+                .extend(decoded.text.split_inclusive('\n').map(str::to_string));
+        }
+        let line = self.lines.pop_front();
+        if let Some(line) = &line {
+            self.text_offset += line.len();
+            self.text_lines += usize::from(line.ends_with('\n'));
+        }
+        Ok(line)
+    }
+
+    /// Pairs a line just returned by `read_line` with its offset and number of preceding
+    /// lines in the decoded text
+    fn located(&self, line: String) -> (String, usize, usize) {
+        let lines = self.text_lines - usize::from(line.ends_with('\n'));
+        let offset = self.text_offset - line.len();
+        (line, offset, lines)
+    }
+
+    fn read_section(&mut self) -> io::Result<Option<Section<'static>>> {
+        self.read_preamble()?;
+        let Some((mut text, offset, lines)) = self.next_header.take() else {
+            return Ok(None);
+        };
+
+        let mut scanner = BodyLines::default();
+        scanner.feed(&text);
+        while let Some(line) = self.read_line()? {
+            if scanner.starts_header(&line) {
+                self.next_header = Some(self.located(line));
+                break;
+            }
+            scanner.feed(&line);
+            if !self.skip_bodies {
+                text.push_str(&line);
+            }
+        }
+
+        let mut sections = SectionIter::new(&text);
+        let section = sections.next();
+        if let Some(mut error) = sections.error() {
+            error.location = error.location.shifted(offset, lines);
+            return Err(io::Error::new(io::ErrorKind::InvalidData, error));
+        }
+        let mut section = section.map(Section::into_owned);
+        if let Some(section) = &mut section {
+            section.shift(offset, lines);
+        }
+        Ok(section)
+    }
+}
+
+impl<R: BufRead> Iterator for SectionReader<R> {
+    type Item = io::Result<Section<'static>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = self.read_section().transpose();
+        if !matches!(result, Some(Ok(_))) {
+            self.done = true;
+        }
+        result
+    }
+}
+
+/// Bracket nesting and line position while skipping the tokens of a section body
+#[derive(Debug, Clone, Copy)]
+struct BodySkip {
+    depth: usize,
+    line_start: bool,
+}
+
+impl Default for BodySkip {
+    fn default() -> Self {
+        Self {
+            depth: 0,
+            line_start: true,
+        }
+    }
+}
+
+impl BodySkip {
+    /// Consumes tokens up to the next section header, a `[` that starts a line outside of
+    /// any value, or the end of the input. Stops before a string that is never closed and
+    /// returns it.
+    fn skip<'a>(&mut self, cursor: &mut TokenCursor<'a>) -> Option<Token<'a>> {
+        while let Some(token) = cursor.peek() {
+            match token.kind {
+                TokenKind::OpenBracket if self.depth == 0 && self.line_start => return None,
+                TokenKind::UnterminatedString => return Some(token),
+                TokenKind::OpenBracket | TokenKind::OpenBrace | TokenKind::OpenParen => {
+                    self.depth += 1
+                }
+                TokenKind::CloseBracket | TokenKind::CloseBrace | TokenKind::CloseParen => {
+                    self.depth = self.depth.saturating_sub(1)
+                }
+                _ => {}
+            }
+            self.line_start = match token.kind {
+                TokenKind::Newline => true,
+                TokenKind::Comment => self.line_start,
+                _ => false,
+            };
+            cursor.next();
+        }
+        None
+    }
+}
+
+/// Skips the tokens of a section body one line at a time as lines are read, keeping only
+/// the text of a string that is still open at the end of the last line
+#[derive(Debug, Default)]
+struct BodyLines {
+    body: BodySkip,
+    open_string: String,
+}
+
+impl BodyLines {
+    /// Returns true if `line` starts a new section rather than continuing the body
+    fn starts_header(&self, line: &str) -> bool {
+        self.open_string.is_empty()
+            && self.body.depth == 0
+            && self.body.line_start
+            && Lexer::new(line)
+                .next()
+                .is_some_and(|token| token.kind == TokenKind::OpenBracket)
+    }
+
+    /// Skips the tokens of `line`, which is part of the current section
+    fn feed(&mut self, line: &str) {
+        if self.open_string.is_empty() {
+            self.body.line_start = false;
+        }
+        self.open_string.push_str(line);
+        let mut cursor = TokenCursor::new(&self.open_string);
+        match self.body.skip(&mut cursor).map(|string| string.span.start) {
+            Some(start) => {
+                self.open_string.drain(..start);
+            }
+            None => self.open_string.clear(),
+        }
+    }
+}
@@ -0,0 +1,473 @@
+use std::fmt;
+
+use super::lexer::{Lexer, Span, TokenCursor, TokenKind};
+use nom::IResult;
+
+/// A typed Godot variant value as written in text scenes, resources and project files.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Value {
+    /// `null`
+    Nil,
+    /// `true` or `false`
+    Bool(bool),
+    /// An integer without a fractional part or exponent, e.g. `42`
+    Int(i64),
+    /// A floating point number, including `inf`, `-inf` and `nan`
+    Float(f64),
+    /// A quoted string, with escape sequences resolved
+    String(String),
+    /// A string name, written `&"name"`
+    StringName(String),
+    /// A node path, written `NodePath("path")` or `^"path"`
+    NodePath(String),
+    /// An untyped array, written `[a, b]`
+    Array(Vec<Value>),
+    /// A dictionary, written `{ key: value }`, in file order
+    Dictionary(Vec<(Value, Value)>),
+    /// An object literal, written `Object(Class, "property": value, ...)`
+    Object {
+        class: String,
+        properties: Vec<(String, Value)>,
+    },
+    /// Any other constructor call, e.g. `Vector2(1, 2)`, `ExtResource("1_abc")` or
+    /// `Array[int]([1, 2])`. The name includes type parameters such as `[int]`.
+    Constructor { name: String, args: Vec<Value> },
+}
+
+impl Value {
+    /// Parses a complete value, returning `None` if the input is not a single valid value
+    pub fn parse(input: &str) -> Option<Value> {
+        let mut cursor = TokenCursor::new(input);
+        let value = value(&mut cursor)?;
+        cursor.skip_newlines();
+        match cursor.peek() {
+            None => Some(value),
+            Some(_) => None,
+        }
+    }
+
+    /// Returns the contents of a string, string name or node path
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) | Value::StringName(s) | Value::NodePath(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the items of an array, packed array or typed array
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) => Some(items),
+            Value::Constructor { name, args } if name.starts_with("Packed") => Some(args),
+            Value::Constructor { name, args } if name.starts_with("Array[") => {
+                match args.as_slice() {
+                    [Value::Array(items)] => Some(items),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the items of an array as strings, skipping items that are not strings
+    pub fn as_string_array(&self) -> Option<Vec<&str>> {
+        self.as_array()
+            .map(|items| items.iter().filter_map(Value::as_str).collect())
+    }
+
+    /// Returns the id of an `ExtResource(...)` reference
+    pub fn as_ext_resource(&self) -> Option<&str> {
+        self.constructor_id("ExtResource")
+    }
+
+    /// Returns the id of a `SubResource(...)` reference
+    pub fn as_sub_resource(&self) -> Option<&str> {
+        self.constructor_id("SubResource")
+    }
+
+    /// Returns every node path contained in this value, including nested ones
+    pub fn node_paths(&self) -> Vec<&str> {
+        let mut paths = Vec::new();
+        self.visit(&mut |value| {
+            if let Value::NodePath(path) = value {
+                paths.push(path.as_str());
+            }
+        });
+        paths
+    }
+
+    /// Calls `f` for this value and every value nested inside it
+    pub fn visit<'a>(&'a self, f: &mut impl FnMut(&'a Value)) {
+        f(self);
+        match self {
+            Value::Array(items) | Value::Constructor { args: items, .. } => {
+                for item in items {
+                    item.visit(f);
+                }
+            }
+            Value::Dictionary(entries) => {
+                for (key, value) in entries {
+                    key.visit(f);
+                    value.visit(f);
+                }
+            }
+            Value::Object { properties, .. } => {
+                for (_, value) in properties {
+                    value.visit(f);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Calls `f` for this value and every value nested inside it, allowing them to be changed
+    pub fn visit_mut(&mut self, f: &mut impl FnMut(&mut Value)) {
+        f(self);
+        match self {
+            Value::Array(items) | Value::Constructor { args: items, .. } => {
+                for item in items {
+                    item.visit_mut(f);
+                }
+            }
+            Value::Dictionary(entries) => {
+                for (key, value) in entries {
+                    key.visit_mut(f);
+                    value.visit_mut(f);
+                }
+            }
+            Value::Object { properties, .. } => {
+                for (_, value) in properties {
+                    value.visit_mut(f);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn constructor_id(&self, constructor: &str) -> Option<&str> {
+        match self {
+            Value::Constructor { name, args } if name == constructor => match args.as_slice() {
+                [Value::String(id)] => Some(id),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Writes the value in Godot's text format, the way the editor saves it
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Nil => f.write_str("null"),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Int(i) => write!(f, "{}", i),
+            Value::Float(x) if x.is_nan() => f.write_str("nan"),
+            Value::Float(x) if x.is_infinite() => {
+                f.write_str(if *x > 0.0 { "inf" } else { "-inf" })
+            }
+            Value::Float(x) => write_float(f, *x),
+            Value::String(s) => write!(f, "\"{}\"", escape(s)),
+            Value::StringName(s) => write!(f, "&\"{}\"", escape(s)),
+            Value::NodePath(s) => write!(f, "NodePath(\"{}\")", escape(s)),
+            Value::Array(items) => {
+                f.write_str("[")?;
+                write_list(f, items)?;
+                f.write_str("]")
+            }
+            Value::Dictionary(entries) if entries.is_empty() => f.write_str("{}"),
+            Value::Dictionary(entries) => {
+                f.write_str("{\n")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",\n")?;
+                    }
+                    write_item(f, key)?;
+                    f.write_str(": ")?;
+                    write_item(f, value)?;
+                }
+                f.write_str("\n}")
+            }
+            Value::Object { class, properties } => {
+                write!(f, "Object({}", class)?;
+                for (key, value) in properties {
+                    write!(f, ",\"{}\":", escape(key))?;
+                    write_item(f, value)?;
+                }
+                f.write_str(")")
+            }
+            Value::Constructor { name, args } => {
+                write!(f, "{}(", name)?;
+                write_list(f, args)?;
+                f.write_str(")")
+            }
+        }
+    }
+}
+
+/// Writes a finite float, with Godot's signed two-digit exponents such as `1e-05`
+fn write_float(f: &mut fmt::Formatter<'_>, x: f64) -> fmt::Result {
+    let text = format!("{:?}", x);
+    match text.split_once('e') {
+        Some((mantissa, exponent)) => {
+            let (sign, digits) = match exponent.strip_prefix('-') {
+                Some(digits) => ('-', digits),
+                None => ('+', exponent),
+            };
+            write!(f, "{}e{}{:0>2}", mantissa, sign, digits)
+        }
+        None => f.write_str(&text),
+    }
+}
+
+fn write_list(f: &mut fmt::Formatter<'_>, items: &[Value]) -> fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write_item(f, item)?;
+    }
+    Ok(())
+}
+
+/// Writes a value nested in another. Like Godot, objects are followed by a line break.
+fn write_item(f: &mut fmt::Formatter<'_>, value: &Value) -> fmt::Result {
+    write!(f, "{}", value)?;
+    if matches!(value, Value::Object { .. }) {
+        f.write_str("\n")?;
+    }
+    Ok(())
+}
+
+/// Parses a single value, skipping leading whitespace.
+#[doc(hidden)]
+pub fn parse_value(input: &str) -> IResult<&str, Value> {
+    let mut cursor = TokenCursor::new(input);
+    match value(&mut cursor) {
+        Some(value) => Ok((&input[cursor.consumed()..], value)),
+        None => Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Alt,
+        ))),
+    }
+}
+
+/// Reads a value from the tokens at the cursor
+pub(crate) fn value(cursor: &mut TokenCursor) -> Option<Value> {
+    cursor.skip_newlines();
+    let token = cursor.next()?;
+
+    match token.kind {
+        TokenKind::String => Some(Value::String(unescape(token.string_content()))),
+        TokenKind::OpenBracket => array(cursor).map(Value::Array),
+        TokenKind::OpenBrace => dictionary(cursor).map(Value::Dictionary),
+        TokenKind::Number => number(token.text),
+        TokenKind::Identifier => identifier_value(cursor, token.text, token.span.start),
+        TokenKind::Symbol => {
+            let next = cursor.peek().filter(|next| next.follows(&token))?;
+            match (token.text, next.kind, next.text) {
+                ("&", TokenKind::String, _) => {
+                    cursor.next();
+                    Some(Value::StringName(unescape(next.string_content())))
+                }
+                ("^", TokenKind::String, _) => {
+                    cursor.next();
+                    Some(Value::NodePath(unescape(next.string_content())))
+                }
+                ("-", TokenKind::Identifier, "inf") => {
+                    cursor.next();
+                    Some(Value::Float(f64::NEG_INFINITY))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Escapes `\` and `"` so that `s` can be written between double quotes. Line breaks are
+/// kept as they are, as Godot does.
+pub(crate) fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Returns the spans of the quoted paths in the node paths of a value's text, such as
+/// `"a/b"` in `NodePath("a/b")` or `^"a/b"`, in the order `Value::node_paths` returns them
+pub(crate) fn node_path_literals(text: &str) -> Vec<Span> {
+    let tokens: Vec<_> = Lexer::new(text)
+        .filter(|t| !matches!(t.kind, TokenKind::Newline | TokenKind::Comment))
+        .collect();
+    let mut spans = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        match (token.kind, token.text, &tokens[i + 1..]) {
+            (TokenKind::Symbol, "^", [string, ..])
+                if string.kind == TokenKind::String && string.follows(token) =>
+            {
+                spans.push(string.span);
+            }
+            (TokenKind::Identifier, "NodePath", [open, string, rest @ ..])
+                if open.kind == TokenKind::OpenParen && string.kind == TokenKind::String =>
+            {
+                let kinds: Vec<_> = rest.iter().take(2).map(|t| t.kind).collect();
+                if let [TokenKind::CloseParen, ..] | [TokenKind::Comma, TokenKind::CloseParen] =
+                    kinds.as_slice()
+                {
+                    spans.push(string.span);
+                }
+            }
+            _ => {}
+        }
+    }
+    spans
+}
+
+/// Resolves the escape sequences of a string's contents
+pub(crate) fn unescape(raw: &str) -> String {
+    if !raw.contains('\\') {
+        return raw.to_string();
+    }
+
+    let mut result = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            result.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            Some('b') => result.push('\u{8}'),
+            Some('f') => result.push('\u{c}'),
+            Some('u') => {
+                let hex: String = chars.by_ref().take(4).collect();
+                let decoded = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32);
+                result.push(decoded.unwrap_or(char::REPLACEMENT_CHARACTER));
+            }
+            Some(other) => result.push(other),
+            None => break,
+        }
+    }
+    result
+}
+
+/// Reads comma separated items up to the `close` token, allowing a trailing comma
+fn list<T>(
+    cursor: &mut TokenCursor,
+    close: TokenKind,
+    mut item: impl FnMut(&mut TokenCursor) -> Option<T>,
+) -> Option<Vec<T>> {
+    let mut items = Vec::new();
+    loop {
+        cursor.skip_newlines();
+        if cursor.eat(close).is_some() {
+            return Some(items);
+        }
+        items.push(item(cursor)?);
+        cursor.skip_newlines();
+        if cursor.eat(TokenKind::Comma).is_none() {
+            cursor.skip_newlines();
+            cursor.eat(close)?;
+            return Some(items);
+        }
+    }
+}
+
+fn array(cursor: &mut TokenCursor) -> Option<Vec<Value>> {
+    list(cursor, TokenKind::CloseBracket, value)
+}
+
+fn dictionary(cursor: &mut TokenCursor) -> Option<Vec<(Value, Value)>> {
+    list(cursor, TokenKind::CloseBrace, |cursor| {
+        let key = value(cursor)?;
+        cursor.skip_newlines();
+        cursor.eat(TokenKind::Colon)?;
+        Some((key, value(cursor)?))
+    })
+}
+
+fn number(text: &str) -> Option<Value> {
+    if text.contains(['.', 'e', 'E']) {
+        return text.parse().ok().map(Value::Float);
+    }
+    match text.parse() {
+        Ok(int) => Some(Value::Int(int)),
+        Err(_) => text.parse().ok().map(Value::Float),
+    }
+}
+
+/// Reads keywords (`true`, `null`, `inf`, ...), `Object(...)`, `NodePath(...)` and any
+/// other constructor call whose name starts at byte `start`
+fn identifier_value(cursor: &mut TokenCursor, name: &str, start: usize) -> Option<Value> {
+    let keyword = match name {
+        "null" => Some(Value::Nil),
+        "true" => Some(Value::Bool(true)),
+        "false" => Some(Value::Bool(false)),
+        "inf" => Some(Value::Float(f64::INFINITY)),
+        "inf_neg" => Some(Value::Float(f64::NEG_INFINITY)),
+        "nan" => Some(Value::Float(f64::NAN)),
+        _ => None,
+    };
+    if keyword.is_some() {
+        return keyword;
+    }
+
+    // Type parameters such as `Array[int]` or `Dictionary[String, int]` are part of the name
+    let mut name = name;
+    if cursor
+        .peek()
+        .is_some_and(|t| t.kind == TokenKind::OpenBracket && t.span.start == start + name.len())
+    {
+        let mut depth = 0;
+        while let Some(token) = cursor.next() {
+            match token.kind {
+                TokenKind::OpenBracket => depth += 1,
+                TokenKind::CloseBracket => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                break;
+            }
+        }
+        name = &cursor.input()[start..cursor.consumed()];
+    }
+
+    cursor.skip_newlines();
+    cursor.eat(TokenKind::OpenParen)?;
+
+    if name == "Object" {
+        cursor.skip_newlines();
+        let class = cursor.eat(TokenKind::Identifier)?.text.to_string();
+        let mut properties = Vec::new();
+        loop {
+            cursor.skip_newlines();
+            if cursor.eat(TokenKind::CloseParen).is_some() {
+                break;
+            }
+            cursor.eat(TokenKind::Comma)?;
+            cursor.skip_newlines();
+            if cursor.eat(TokenKind::CloseParen).is_some() {
+                break;
+            }
+            let key = cursor.eat(TokenKind::String)?;
+            cursor.skip_newlines();
+            cursor.eat(TokenKind::Colon)?;
+            properties.push((unescape(key.string_content()), value(cursor)?));
+        }
+        return Some(Value::Object { class, properties });
+    }
+
+    let args = list(cursor, TokenKind::CloseParen, value)?;
+
+    if name == "NodePath"
+        && let [Value::String(path)] = args.as_slice()
+    {
+        return Some(Value::NodePath(path.clone()));
+    }
+
+    Some(Value::Constructor {
+        name: name.to_string(),
+        args,
+    })
+}
@@ -0,0 +1,174 @@
+use std::fmt;
+
+use super::lexer::Span;
+
+/// Where a part of a parsed file was read from: its byte span in the input and the line
+/// and column of its first character.
+///
+/// Sections, keys and values record their location while parsing. For input given as
+/// bytes, spans point into the decoded text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Location {
+    pub span: Span,
+    /// 1-based line of the first character
+    pub line: usize,
+    /// 1-based column of the first character, counted in characters
+    pub column: usize,
+}
+
+impl Location {
+    /// Creates a location from a span and the position of its start
+    pub fn new(span: Span, line: usize, column: usize) -> Self {
+        Self { span, line, column }
+    }
+
+    /// The position of the first character
+    pub fn position(&self) -> Position {
+        Position::new(self.line, self.column)
+    }
+
+    /// Returns the location covering both locations, starting where `self` starts
+    pub fn to(self, other: Location) -> Location {
+        Location {
+            span: self.span.to(other.span),
+            ..self
+        }
+    }
+
+    /// Moves the location `offset` bytes and `lines` lines further into the input
+    pub(crate) fn shifted(self, offset: usize, lines: usize) -> Location {
+        Location {
+            span: Span::new(self.span.start + offset, self.span.end + offset),
+            line: self.line + lines,
+            column: self.column,
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// A 1-based line and column, with columns counted in characters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    /// Creates a position from a 1-based line and column
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Converts between byte offsets and line/column positions of a source text.
+///
+/// The start of every line is recorded once, so each lookup is a binary search. Use it to
+/// find where a span ends, to turn an editor position back into an offset, or to show the
+/// line a diagnostic refers to.
+///
+/// # Example
+///
+/// ```
+/// use godot_properties_parser::parsers::source_map::{Position, SourceMap};
+///
+/// let source = "[node name=\"Root\" type=\"Node\"]\nvisible = false\n";
+/// let map = SourceMap::new(source);
+///
+/// assert_eq!(map.position(41), Position::new(2, 11));
+/// assert_eq!(map.offset(Position::new(2, 11)), Some(41));
+/// assert_eq!(map.line(2), Some("visible = false"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SourceMap<'a> {
+    source: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> SourceMap<'a> {
+    /// Indexes the lines of `source`
+    pub fn new(source: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(idx, _)| idx + 1))
+            .collect();
+        Self {
+            source,
+            line_starts,
+        }
+    }
+
+    /// The indexed source text
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    /// Number of lines, counting the text after the last line break as a line
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// The position of byte `offset`. Offsets past the end map to the end of the source, and
+    /// offsets inside a character to that character.
+    pub fn position(&self, offset: usize) -> Position {
+        let mut offset = offset.min(self.source.len());
+        while !self.source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let index = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let line_start = self.line_starts[index];
+        let column = self.source[line_start..offset].chars().count() + 1;
+        Position::new(index + 1, column)
+    }
+
+    /// The location of `span`, positioned at its start
+    pub fn locate(&self, span: Span) -> Location {
+        let position = self.position(span.start);
+        Location::new(span, position.line, position.column)
+    }
+
+    /// The positions of the first character of `span` and of the character after it
+    pub fn range(&self, span: Span) -> (Position, Position) {
+        (self.position(span.start), self.position(span.end))
+    }
+
+    /// The byte offset of `position`, or `None` if it is not within the source
+    pub fn offset(&self, position: Position) -> Option<usize> {
+        let span = self.line_span(position.line)?;
+        let line = span.slice(self.source);
+        if position.column == 0 {
+            return None;
+        }
+        if position.column == line.chars().count() + 1 {
+            return Some(span.end);
+        }
+        line.char_indices()
+            .nth(position.column - 1)
+            .map(|(idx, _)| span.start + idx)
+    }
+
+    /// The span of a 1-based line without its line break
+    pub fn line_span(&self, line: usize) -> Option<Span> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let end = self
+            .line_starts
+            .get(line)
+            .map_or(self.source.len(), |next| next - 1);
+        Some(Span::new(start, end))
+    }
+
+    /// The text of a 1-based line without its line break
+    pub fn line(&self, line: usize) -> Option<&'a str> {
+        self.line_span(line).map(|span| span.slice(self.source))
+    }
+}
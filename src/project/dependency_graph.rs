@@ -0,0 +1,303 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::Path;
+
+use crate::parsers::parser_value::Value;
+
+use super::{GodotProject, PROJECT_FILE_PATH, ProjectError};
+
+/// Extensions of files that take part in the graph even when nothing references them
+const TRACKED_EXTENSIONS: &[&str] = &["tscn", "tres", "gd", "cs", "gdshader"];
+
+/// How one file came to depend on another
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DependencyKind {
+    /// Declared by an `[ext_resource]` section
+    ExtResource,
+    /// A node instances the scene with `instance=ExtResource(...)`
+    Instance,
+    /// Registered in the `[autoload]` section of `project.godot`
+    Autoload,
+    /// Set as `run/main_scene` in the `[application]` section of `project.godot`
+    MainScene,
+}
+
+/// A directed edge from the file `from` to the file it depends on, `to`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Dependency {
+    pub from: String,
+    pub to: String,
+    pub kind: DependencyKind,
+}
+
+/// Dependency graph between the files of a Godot project.
+///
+/// Nodes are `res://` paths. References are resolved by `uid` first and by `path` second;
+/// references that cannot be resolved keep their raw path (or uid) as the node name, so
+/// they still show up in queries. The project file `res://project.godot` is the root of the
+/// graph, with edges to the main scene and every autoload.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DependencyGraph {
+    nodes: BTreeSet<String>,
+    edges: Vec<Dependency>,
+    /// Index in `edges` of the edge between two files
+    edge_index: BTreeMap<(String, String), usize>,
+    outgoing: BTreeMap<String, BTreeSet<String>>,
+    incoming: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl DependencyGraph {
+    /// Loads the project in `root` and builds its dependency graph
+    pub fn from_project_dir(root: impl AsRef<Path>) -> Result<Self, ProjectError> {
+        let project = GodotProject::open(root)?;
+        Ok(Self::from_project(&project))
+    }
+
+    /// Builds the dependency graph of an already loaded project
+    pub fn from_project(project: &GodotProject) -> Self {
+        let mut graph = DependencyGraph::default();
+
+        for file in &project.files {
+            let tracked = file
+                .rsplit_once('.')
+                .is_some_and(|(_, ext)| TRACKED_EXTENSIONS.contains(&ext));
+            if tracked || file == PROJECT_FILE_PATH {
+                graph.nodes.insert(file.clone());
+            }
+        }
+
+        if let Some(main_scene) = project.main_scene() {
+            let target = project.resolve_reference(main_scene).unwrap_or(main_scene);
+            graph.add_edge(PROJECT_FILE_PATH, target, DependencyKind::MainScene);
+        }
+
+        for (_, reference) in project.autoloads() {
+            let target = project.resolve_reference(reference).unwrap_or(reference);
+            graph.add_edge(PROJECT_FILE_PATH, target, DependencyKind::Autoload);
+        }
+
+        for (path, scene) in &project.scenes {
+            let mut targets_by_id = BTreeMap::new();
+
+            for ext_resource in scene.ext_resources() {
+                let res_path = ext_resource.get("path");
+                let uid = ext_resource.get("uid");
+                let Some(target) = project.resolve(res_path, uid).or(res_path).or(uid) else {
+                    continue;
+                };
+
+                if let Some(id) = ext_resource.get("id") {
+                    targets_by_id.insert(id, target);
+                }
+                graph.add_edge(path, target, DependencyKind::ExtResource);
+            }
+
+            for node in scene.nodes() {
+                let target = node
+                    .get("instance")
+                    .and_then(Value::parse)
+                    .and_then(|value| targets_by_id.get(value.as_ext_resource()?).copied());
+                if let Some(target) = target {
+                    graph.add_edge(path, target, DependencyKind::Instance);
+                }
+            }
+        }
+
+        graph
+    }
+
+    /// Returns every file in the graph, sorted
+    pub fn nodes(&self) -> impl Iterator<Item = &str> {
+        self.nodes.iter().map(String::as_str)
+    }
+
+    /// Returns every edge in the order it was discovered, one per pair of files. A scene that
+    /// instances another has an `Instance` edge to it, even though it also declares it as an
+    /// external resource.
+    pub fn edges(&self) -> &[Dependency] {
+        &self.edges
+    }
+
+    /// Returns the files that `path` depends on directly
+    pub fn dependencies(&self, path: &str) -> Vec<&str> {
+        neighbours(&self.outgoing, path)
+    }
+
+    /// Returns the files that depend on `path` directly
+    pub fn dependents(&self, path: &str) -> Vec<&str> {
+        neighbours(&self.incoming, path)
+    }
+
+    /// Returns every file that `path` depends on, directly or indirectly
+    pub fn transitive_dependencies(&self, path: &str) -> Vec<&str> {
+        reachable(&self.outgoing, path)
+    }
+
+    /// Returns every file that depends on `path`, directly or indirectly
+    pub fn transitive_dependents(&self, path: &str) -> Vec<&str> {
+        reachable(&self.incoming, path)
+    }
+
+    /// Returns the dependency cycles in the graph.
+    ///
+    /// Each cycle is a strongly connected component with more than one file, or a single
+    /// file that depends on itself. Files within a cycle are sorted.
+    pub fn cycles(&self) -> Vec<Vec<&str>> {
+        let mut tarjan = Tarjan::new(&self.outgoing);
+        for node in &self.nodes {
+            if !tarjan.indices.contains_key(node.as_str()) {
+                tarjan.visit(node);
+            }
+        }
+
+        tarjan
+            .components
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1 || self.dependencies(component[0]).contains(&component[0])
+            })
+            .map(|mut component| {
+                component.sort_unstable();
+                component
+            })
+            .collect()
+    }
+
+    /// Returns the tracked files that cannot be reached from the main scene or any autoload
+    pub fn unreachable(&self) -> Vec<&str> {
+        let reached: BTreeSet<&str> = reachable(&self.outgoing, PROJECT_FILE_PATH)
+            .into_iter()
+            .collect();
+
+        self.nodes
+            .iter()
+            .map(String::as_str)
+            .filter(|node| *node != PROJECT_FILE_PATH && !reached.contains(node))
+            .collect()
+    }
+
+    fn add_edge(&mut self, from: &str, to: &str, kind: DependencyKind) {
+        self.nodes.insert(from.to_string());
+        self.nodes.insert(to.to_string());
+        self.outgoing
+            .entry(from.to_string())
+            .or_default()
+            .insert(to.to_string());
+        self.incoming
+            .entry(to.to_string())
+            .or_default()
+            .insert(from.to_string());
+
+        let pair = (from.to_string(), to.to_string());
+        if let Some(&index) = self.edge_index.get(&pair) {
+            if kind == DependencyKind::Instance {
+                self.edges[index].kind = kind;
+            }
+            return;
+        }
+        self.edge_index.insert(pair, self.edges.len());
+        self.edges.push(Dependency {
+            from: from.to_string(),
+            to: to.to_string(),
+            kind,
+        });
+    }
+}
+
+fn neighbours<'a>(adjacency: &'a BTreeMap<String, BTreeSet<String>>, path: &str) -> Vec<&'a str> {
+    adjacency
+        .get(path)
+        .map(|set| set.iter().map(String::as_str).collect())
+        .unwrap_or_default()
+}
+
+/// Breadth-first search from `start`, excluding `start` itself unless it is part of a cycle
+fn reachable<'a>(adjacency: &'a BTreeMap<String, BTreeSet<String>>, start: &str) -> Vec<&'a str> {
+    let mut visited = BTreeSet::new();
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(current) = queue.pop_front() {
+        for next in neighbours(adjacency, current) {
+            if visited.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    visited.into_iter().collect()
+}
+
+/// Tarjan's strongly connected components algorithm
+struct Tarjan<'a> {
+    adjacency: &'a BTreeMap<String, BTreeSet<String>>,
+    index: usize,
+    indices: BTreeMap<&'a str, usize>,
+    low_links: BTreeMap<&'a str, usize>,
+    stack: Vec<&'a str>,
+    on_stack: BTreeSet<&'a str>,
+    components: Vec<Vec<&'a str>>,
+}
+
+impl<'a> Tarjan<'a> {
+    fn new(adjacency: &'a BTreeMap<String, BTreeSet<String>>) -> Self {
+        Self {
+            adjacency,
+            index: 0,
+            indices: BTreeMap::new(),
+            low_links: BTreeMap::new(),
+            stack: Vec::new(),
+            on_stack: BTreeSet::new(),
+            components: Vec::new(),
+        }
+    }
+
+    /// Visits every node reachable from `start` depth first, keeping the path being explored
+    /// on an explicit stack so that long dependency chains cannot overflow the call stack
+    fn visit(&mut self, start: &'a str) {
+        // Each frame is a node being visited, its neighbours and how many have been visited
+        let mut frames = vec![self.open(start)];
+
+        while let Some((node, next_nodes, visited)) = frames.last_mut() {
+            let node = *node;
+            if let Some(&next) = next_nodes.get(*visited) {
+                *visited += 1;
+                if !self.indices.contains_key(next) {
+                    frames.push(self.open(next));
+                } else if self.on_stack.contains(next) {
+                    self.lower(node, self.indices[next]);
+                }
+                continue;
+            }
+
+            frames.pop();
+            if let Some((parent, ..)) = frames.last() {
+                self.lower(parent, self.low_links[node]);
+            }
+            if self.low_links[node] == self.indices[node] {
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack.remove(member);
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                self.components.push(component);
+            }
+        }
+    }
+
+    /// Numbers `node` and pushes it on the stack, returning its frame
+    fn open(&mut self, node: &'a str) -> (&'a str, Vec<&'a str>, usize) {
+        self.indices.insert(node, self.index);
+        self.low_links.insert(node, self.index);
+        self.index += 1;
+        self.stack.push(node);
+        self.on_stack.insert(node);
+        (node, neighbours(self.adjacency, node), 0)
+    }
+
+    fn lower(&mut self, node: &'a str, low: usize) {
+        let current = self.low_links[node];
+        self.low_links.insert(node, current.min(low));
+    }
+}
@@ -0,0 +1,75 @@
+use std::collections::BTreeMap;
+
+use super::GodotProject;
+
+/// A node that belongs to a group
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupMember {
+    /// The `res://` path of the scene declaring the node
+    pub scene: String,
+    /// Path of the node relative to the scene root
+    pub node: String,
+}
+
+/// Group usage across a project, cross-checked against the `[global_group]` section that
+/// Godot 4.3+ writes to `project.godot`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GroupIndex {
+    /// Groups declared in `[global_group]`, mapped to their description
+    pub global_groups: BTreeMap<String, String>,
+    /// Every group assigned to a node in any scene, mapped to its members
+    pub scene_groups: BTreeMap<String, Vec<GroupMember>>,
+}
+
+impl GroupIndex {
+    /// Collects the global groups and the groups of every node in every scene of the project
+    pub fn from_project(project: &GodotProject) -> Self {
+        let mut index = GroupIndex::default();
+
+        if let Some(section) = project.project_file.global_group() {
+            for property in &section.properties {
+                index
+                    .global_groups
+                    .insert(property.key.to_string(), property.value.to_string());
+            }
+        }
+
+        for (scene_path, scene) in &project.scenes {
+            for (group, nodes) in scene.groups() {
+                let members = index.scene_groups.entry(group).or_default();
+                members.extend(nodes.into_iter().map(|node| GroupMember {
+                    scene: scene_path.clone(),
+                    node,
+                }));
+            }
+        }
+
+        index
+    }
+
+    /// Returns the members of a group, or an empty slice if no node uses it
+    pub fn members(&self, group: &str) -> &[GroupMember] {
+        self.scene_groups
+            .get(group)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns the groups used by scenes that are not declared as global groups
+    pub fn undeclared_groups(&self) -> Vec<&str> {
+        self.scene_groups
+            .keys()
+            .filter(|group| !self.global_groups.contains_key(*group))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Returns the global groups that no node in any scene belongs to
+    pub fn unused_global_groups(&self) -> Vec<&str> {
+        self.global_groups
+            .keys()
+            .filter(|group| !self.scene_groups.contains_key(*group))
+            .map(String::as_str)
+            .collect()
+    }
+}
@@ -0,0 +1,309 @@
+pub mod dependency_graph;
+pub mod groups;
+pub mod project_dir;
+pub mod references;
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::parsers::encoding::{EncodingDiagnostic, decode};
+use crate::parsers::parser_project_file::{ProjectFile, try_parse_project_file};
+use crate::parsers::parser_property_file::{ParseError, parse_property_file};
+use crate::parsers::parser_scene_file::{SceneFile, try_parse_scene_file};
+
+pub use dependency_graph::{Dependency, DependencyGraph, DependencyKind};
+pub use groups::{GroupIndex, GroupMember};
+pub use project_dir::{ParsedContent, ParsedFile, ProjectFiles, parse_project_dir};
+pub use references::{BrokenReference, BrokenReferenceKind, find_broken_references};
+
+/// The `res://` path of the project file itself
+pub const PROJECT_FILE_PATH: &str = "res://project.godot";
+
+/// Extensions of text scenes and resources that are parsed when a project is opened
+const SCENE_EXTENSIONS: &[&str] = &["tscn", "tres"];
+
+/// Error returned when a project directory cannot be loaded
+#[derive(Debug)]
+pub enum ProjectError {
+    /// A file or directory could not be read
+    Io { path: PathBuf, source: io::Error },
+    /// A file could not be parsed
+    Parse { path: PathBuf, error: ParseError },
+}
+
+impl fmt::Display for ProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProjectError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            ProjectError::Parse { path, error } => write!(f, "{}:{}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for ProjectError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ProjectError::Io { source, .. } => Some(source),
+            ProjectError::Parse { error, .. } => Some(error),
+        }
+    }
+}
+
+/// A Godot project loaded from a directory containing `project.godot`.
+///
+/// Every file below the root is recorded by its `res://` path. Directories containing a
+/// `.gdignore` file and hidden directories (such as the `.godot/` cache) are skipped, the
+/// same way the Godot editor does. Text scenes and resources are parsed, and `uid://`
+/// identifiers are collected from scene headers, `.uid` files and `.import` files.
+///
+/// A project can also be built in memory with `new` and `add_scene`.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct GodotProject {
+    /// Directory containing `project.godot`
+    pub root: PathBuf,
+    /// The parsed `project.godot`
+    pub project_file: ProjectFile<'static>,
+    /// Every file in the project as a `res://` path, sorted. `.uid` and `.import`
+    /// sidecar files are not listed.
+    pub files: Vec<String>,
+    /// Parsed `.tscn` and `.tres` files keyed by their `res://` path
+    pub scenes: BTreeMap<String, SceneFile<'static>>,
+    /// Known `uid://` identifiers mapped to the `res://` path they belong to
+    pub uids: HashMap<String, String>,
+    /// Source text of `project.godot` and of every parsed scene, keyed by `res://` path.
+    /// Line endings are normalized to `\n` and invalid UTF-8 is replaced with U+FFFD.
+    pub sources: BTreeMap<String, String>,
+    /// Invalid UTF-8 found in `project.godot` and in parsed scenes, keyed by `res://` path.
+    /// Files without problems are not listed.
+    pub encoding_diagnostics: BTreeMap<String, Vec<EncodingDiagnostic>>,
+}
+
+impl GodotProject {
+    /// Loads the project in `root`, which must contain a `project.godot` file
+    pub fn open(root: impl AsRef<Path>) -> Result<Self, ProjectError> {
+        let root = root.as_ref().to_path_buf();
+
+        let project_path = root.join("project.godot");
+        let (content, diagnostics) = read_file(&project_path)?;
+        let project_file = match try_parse_project_file(&content) {
+            Ok(project_file) => project_file.into_owned(),
+            Err(error) => return Err(parse_error(&project_path, error)),
+        };
+
+        let mut paths = Vec::new();
+        collect_files(&root, &mut paths)?;
+
+        let mut project = GodotProject::new(root, project_file);
+        project.record_source(PROJECT_FILE_PATH, content, diagnostics);
+
+        for path in paths {
+            let res_path = res_path(&project.root, &path);
+
+            if let Some(target) = res_path.strip_suffix(".uid") {
+                let (uid, _) = read_file(&path)?;
+                project
+                    .uids
+                    .insert(uid.trim().to_string(), target.to_string());
+                continue;
+            }
+
+            if let Some(target) = res_path.strip_suffix(".import") {
+                let (content, _) = read_file(&path)?;
+                if let Ok((_, import_file)) = parse_property_file(&content) {
+                    let uid = import_file
+                        .sections
+                        .iter()
+                        .filter(|s| s.header_type == "remap")
+                        .find_map(|s| s.get("uid"));
+                    if let Some(uid) = uid {
+                        project.uids.insert(uid.to_string(), target.to_string());
+                    }
+                }
+                continue;
+            }
+
+            let is_scene = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| SCENE_EXTENSIONS.contains(&ext));
+
+            if is_scene {
+                let (content, diagnostics) = read_file(&path)?;
+                let scene = match try_parse_scene_file(&content) {
+                    Ok(scene) => scene.into_owned(),
+                    Err(error) => return Err(parse_error(&path, error)),
+                };
+                if let Some(uid) = scene.header().and_then(|h| h.get("uid")) {
+                    project.uids.insert(uid.to_string(), res_path.clone());
+                }
+                project.scenes.insert(res_path.clone(), scene);
+                project.record_source(&res_path, content, diagnostics);
+            }
+
+            project.files.push(res_path);
+        }
+
+        project.files.sort();
+        Ok(project)
+    }
+
+    /// Creates a project in `root` without any files besides `project_file`
+    pub fn new(root: impl Into<PathBuf>, project_file: ProjectFile<'static>) -> Self {
+        GodotProject {
+            root: root.into(),
+            project_file,
+            files: Vec::new(),
+            scenes: BTreeMap::new(),
+            uids: HashMap::new(),
+            sources: BTreeMap::new(),
+            encoding_diagnostics: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a parsed scene or resource at the given `res://` path, replacing any scene
+    /// already there. The path is added to `files` and the `uid` of the scene header is
+    /// recorded. No source text is kept for it.
+    pub fn add_scene(&mut self, res_path: &str, scene: SceneFile<'static>) {
+        if let Some(uid) = scene.header().and_then(|h| h.get("uid")) {
+            self.uids.insert(uid.to_string(), res_path.to_string());
+        }
+        if let Err(index) = self.files.binary_search_by(|f| f.as_str().cmp(res_path)) {
+            self.files.insert(index, res_path.to_string());
+        }
+        self.scenes.insert(res_path.to_string(), scene);
+    }
+
+    fn record_source(
+        &mut self,
+        res_path: &str,
+        content: String,
+        diagnostics: Vec<EncodingDiagnostic>,
+    ) {
+        if !diagnostics.is_empty() {
+            self.encoding_diagnostics
+                .insert(res_path.to_string(), diagnostics);
+        }
+        self.sources.insert(res_path.to_string(), content);
+    }
+
+    /// Returns true if a file with the given `res://` path exists in the project
+    pub fn contains(&self, res_path: &str) -> bool {
+        self.files
+            .binary_search_by(|f| f.as_str().cmp(res_path))
+            .is_ok()
+    }
+
+    /// Resolves a reference given as `path` and/or `uid` to an existing `res://` path.
+    ///
+    /// Like Godot, the `uid` takes precedence and the `path` is used as a fallback.
+    pub fn resolve(&self, path: Option<&str>, uid: Option<&str>) -> Option<&str> {
+        if let Some(target) = uid.and_then(|uid| self.uids.get(uid)) {
+            return Some(target.as_str());
+        }
+        let path = path?;
+        self.files
+            .binary_search_by(|f| f.as_str().cmp(path))
+            .ok()
+            .map(|idx| self.files[idx].as_str())
+    }
+
+    /// Resolves a single `res://` or `uid://` reference to an existing `res://` path
+    pub fn resolve_reference(&self, reference: &str) -> Option<&str> {
+        if reference.starts_with("uid://") {
+            self.resolve(None, Some(reference))
+        } else {
+            self.resolve(Some(reference), None)
+        }
+    }
+
+    /// Returns the `run/main_scene` reference from `[application]`, if set
+    pub fn main_scene(&self) -> Option<&str> {
+        self.project_file.get("application", "run/main_scene")
+    }
+
+    /// Returns the autoloads as `(name, reference)` pairs, with the `*` singleton
+    /// marker removed from the reference
+    pub fn autoloads(&self) -> Vec<(&str, &str)> {
+        let mut autoloads: Vec<(&str, &str)> = Vec::new();
+        for section in self.project_file.sections("autoload") {
+            for p in &section.properties {
+                let reference = p.value.trim_start_matches('*');
+                match autoloads.iter_mut().find(|(name, _)| *name == p.key) {
+                    Some(existing) => existing.1 = reference,
+                    None => autoloads.push((&p.key, reference)),
+                }
+            }
+        }
+        autoloads
+    }
+}
+
+/// Converts a filesystem path below `root` to a `res://` path
+fn res_path(root: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    let parts: Vec<_> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect();
+    format!("res://{}", parts.join("/"))
+}
+
+/// Recursively collects every file below `dir`, skipping hidden directories and
+/// directories that contain a `.gdignore` file
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), ProjectError> {
+    if dir.join(".gdignore").exists() {
+        return Ok(());
+    }
+
+    let entries = fs::read_dir(dir).map_err(|source| ProjectError::Io {
+        path: dir.to_path_buf(),
+        source,
+    })?;
+
+    let mut entries: Vec<_> =
+        entries
+            .collect::<Result<_, _>>()
+            .map_err(|source| ProjectError::Io {
+                path: dir.to_path_buf(),
+                source,
+            })?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let path = entry.path();
+        let name = entry.file_name();
+        if name.to_string_lossy().starts_with('.') {
+            continue;
+        }
+
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads and decodes a text file. Invalid UTF-8 is replaced and reported rather than
+/// failing the whole project.
+fn read_file(path: &Path) -> Result<(String, Vec<EncodingDiagnostic>), ProjectError> {
+    let bytes = fs::read(path).map_err(|source| ProjectError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let decoded = decode(&bytes);
+    Ok((decoded.text.into_owned(), decoded.diagnostics))
+}
+
+fn parse_error(path: &Path, error: ParseError) -> ProjectError {
+    ProjectError::Parse {
+        path: path.to_path_buf(),
+        error,
+    }
+}
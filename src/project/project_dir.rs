@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::parsers::encoding::EncodingDiagnostic;
+use crate::parsers::parser_project_file::ProjectFile;
+use crate::parsers::parser_property_file::{PropertyFile, try_parse_property_file};
+use crate::parsers::parser_scene_file::SceneFile;
+
+use super::{ProjectError, collect_files, parse_error, read_file, res_path};
+
+/// Extensions of the property files parsed by `parse_project_dir`
+const PROPERTY_FILE_EXTENSIONS: &[&str] = &["tscn", "tres", "godot", "cfg", "import"];
+
+/// The contents of a parsed file, by file type
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedContent {
+    /// A `.tscn` scene or `.tres` resource
+    Scene(SceneFile<'static>),
+    /// A `.godot` project file
+    Project(ProjectFile<'static>),
+    /// A `.cfg` or `.import` file
+    Properties(PropertyFile<'static>),
+}
+
+/// A file parsed by `parse_project_dir`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedFile {
+    /// Path of the file on disk
+    pub path: PathBuf,
+    pub content: ParsedContent,
+    /// Invalid UTF-8 found in the file
+    pub encoding_diagnostics: Vec<EncodingDiagnostic>,
+}
+
+impl ParsedFile {
+    /// Returns the scene if the file is a `.tscn` or `.tres` file
+    pub fn as_scene(&self) -> Option<&SceneFile<'static>> {
+        match &self.content {
+            ParsedContent::Scene(scene) => Some(scene),
+            _ => None,
+        }
+    }
+
+    /// Returns the project file if the file is a `.godot` file
+    pub fn as_project(&self) -> Option<&ProjectFile<'static>> {
+        match &self.content {
+            ParsedContent::Project(project) => Some(project),
+            _ => None,
+        }
+    }
+
+    /// Returns the generic property file if the file is a `.cfg` or `.import` file
+    pub fn as_properties(&self) -> Option<&PropertyFile<'static>> {
+        match &self.content {
+            ParsedContent::Properties(properties) => Some(properties),
+            _ => None,
+        }
+    }
+}
+
+/// Every property file found below a directory by `parse_project_dir`
+#[derive(Debug, Default)]
+pub struct ProjectFiles {
+    /// Parsed files keyed by their `res://` path
+    pub files: BTreeMap<String, ParsedFile>,
+    /// Files that could not be read or parsed, sorted by path
+    pub errors: Vec<ProjectError>,
+}
+
+impl ProjectFiles {
+    /// Returns the parsed file with the given `res://` path
+    pub fn get(&self, res_path: &str) -> Option<&ParsedFile> {
+        self.files.get(res_path)
+    }
+
+    /// Returns every parsed `.tscn` and `.tres` file with its `res://` path
+    pub fn scenes(&self) -> impl Iterator<Item = (&str, &SceneFile<'static>)> {
+        self.files
+            .iter()
+            .filter_map(|(path, file)| Some((path.as_str(), file.as_scene()?)))
+    }
+
+    /// Returns true if every file was read and parsed
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Parses every `.tscn`, `.tres`, `.godot`, `.cfg` and `.import` file below `root`.
+///
+/// Directories containing a `.gdignore` file and hidden directories (such as the `.godot/`
+/// cache) are skipped. `root` does not need to contain a `project.godot` file. With the
+/// `parallel` feature enabled, files are read and parsed on the rayon thread pool.
+///
+/// A file that cannot be read or parsed does not stop the others:
+/// its error is collected in `ProjectFiles::errors`. Only failing to list the directories
+/// returns an error.
+///
+/// # Example
+///
+/// ```no_run
+/// use godot_properties_parser::parse_project_dir;
+///
+/// let parsed = parse_project_dir("path/to/project").unwrap();
+/// for error in &parsed.errors {
+///     eprintln!("{}", error);
+/// }
+/// println!("Scenes: {}", parsed.scenes().count());
+/// ```
+pub fn parse_project_dir(root: impl AsRef<Path>) -> Result<ProjectFiles, ProjectError> {
+    let root = root.as_ref();
+
+    let mut paths = Vec::new();
+    collect_files(root, &mut paths)?;
+    paths.retain(|path| {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| PROPERTY_FILE_EXTENSIONS.contains(&ext))
+    });
+
+    #[cfg(feature = "parallel")]
+    let results: Vec<_> = {
+        use rayon::prelude::*;
+        paths.par_iter().map(|path| parse_file(path)).collect()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let results: Vec<_> = paths.iter().map(|path| parse_file(path)).collect();
+
+    let mut parsed = ProjectFiles::default();
+    for result in results {
+        match result {
+            Ok(file) => {
+                parsed.files.insert(res_path(root, &file.path), file);
+            }
+            Err(error) => parsed.errors.push(error),
+        }
+    }
+    Ok(parsed)
+}
+
+fn parse_file(path: &Path) -> Result<ParsedFile, ProjectError> {
+    let (content, encoding_diagnostics) = read_file(path)?;
+    let property_file = try_parse_property_file(&content)
+        .map_err(|error| parse_error(path, error))?
+        .into_owned();
+    let content = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("tscn" | "tres") => ParsedContent::Scene(SceneFile::from_property_file(property_file)),
+        Some("godot") => ParsedContent::Project(ProjectFile::from_property_file(property_file)),
+        _ => ParsedContent::Properties(property_file),
+    };
+
+    Ok(ParsedFile {
+        path: path.to_path_buf(),
+        content,
+        encoding_diagnostics,
+    })
+}
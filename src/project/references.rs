@@ -0,0 +1,130 @@
+use std::fmt;
+
+use super::{GodotProject, PROJECT_FILE_PATH};
+
+/// What is wrong with a reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BrokenReferenceKind {
+    /// An `[ext_resource]` whose `path` does not exist on disk
+    MissingPath { path: String },
+    /// An `[ext_resource]` whose `uid` does not belong to any known file
+    UnknownUid { uid: String },
+    /// An autoload whose script or scene cannot be found
+    MissingAutoload { name: String, reference: String },
+    /// A `run/main_scene` that cannot be found
+    MissingMainScene { reference: String },
+}
+
+/// A reference that points nowhere, located by file and 1-based line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokenReference {
+    /// The `res://` path of the file containing the reference
+    pub file: String,
+    /// The line of the section header or property holding the reference
+    pub line: usize,
+    pub kind: BrokenReferenceKind,
+}
+
+impl fmt::Display for BrokenReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: ", self.file, self.line)?;
+        match &self.kind {
+            BrokenReferenceKind::MissingPath { path } => {
+                write!(f, "ext_resource path \"{}\" does not exist", path)
+            }
+            BrokenReferenceKind::UnknownUid { uid } => {
+                write!(f, "ext_resource uid \"{}\" does not match any file", uid)
+            }
+            BrokenReferenceKind::MissingAutoload { name, reference } => {
+                write!(
+                    f,
+                    "autoload \"{}\" points to missing \"{}\"",
+                    name, reference
+                )
+            }
+            BrokenReferenceKind::MissingMainScene { reference } => {
+                write!(f, "run/main_scene points to missing \"{}\"", reference)
+            }
+        }
+    }
+}
+
+/// Reports every reference in the project that points nowhere.
+///
+/// This covers `[ext_resource]` sections whose `path` is missing on disk or whose `uid` is
+/// unknown, autoloads whose target cannot be resolved, and an unresolvable `run/main_scene`.
+/// A stale `path` is reported even when the `uid` still resolves, since the file was moved
+/// outside the editor. Findings are sorted by file and line.
+pub fn find_broken_references(project: &GodotProject) -> Vec<BrokenReference> {
+    let mut broken = Vec::new();
+
+    if let Some(reference) = project.main_scene()
+        && project.resolve_reference(reference).is_none()
+    {
+        broken.push(BrokenReference {
+            file: PROJECT_FILE_PATH.to_string(),
+            line: property_line(project, "application", "run/main_scene"),
+            kind: BrokenReferenceKind::MissingMainScene {
+                reference: reference.to_string(),
+            },
+        });
+    }
+
+    for (name, reference) in project.autoloads() {
+        if project.resolve_reference(reference).is_none() {
+            broken.push(BrokenReference {
+                file: PROJECT_FILE_PATH.to_string(),
+                line: property_line(project, "autoload", name),
+                kind: BrokenReferenceKind::MissingAutoload {
+                    name: name.to_string(),
+                    reference: reference.to_string(),
+                },
+            });
+        }
+    }
+
+    for (file, scene) in &project.scenes {
+        for ext_resource in scene.ext_resources() {
+            let line = ext_resource.location.line;
+
+            if let Some(path) = ext_resource.get("path")
+                && !project.contains(path)
+            {
+                broken.push(BrokenReference {
+                    file: file.clone(),
+                    line,
+                    kind: BrokenReferenceKind::MissingPath {
+                        path: path.to_string(),
+                    },
+                });
+            }
+
+            if let Some(uid) = ext_resource.get("uid")
+                && !project.uids.contains_key(uid)
+            {
+                broken.push(BrokenReference {
+                    file: file.clone(),
+                    line,
+                    kind: BrokenReferenceKind::UnknownUid {
+                        uid: uid.to_string(),
+                    },
+                });
+            }
+        }
+    }
+
+    broken.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+    broken
+}
+
+/// 1-based line of `key` in the section `name` of `project.godot`, falling back to the
+/// section header (or 1) when the key cannot be found
+fn property_line(project: &GodotProject, name: &str, key: &str) -> usize {
+    match project.project_file.property(name, key) {
+        Some(property) => property.key_location.line,
+        None => project
+            .project_file
+            .section(name)
+            .map_or(1, |section| section.location.line),
+    }
+}
@@ -0,0 +1,343 @@
+//! Reports of diagnostics across a set of files, for CI.
+//!
+//! A `Report` collects the diagnostics of each file checked, such as parse failures from
+//! `parse_diagnostics` and the findings of a `lint::Linter`, with paths relative to the
+//! project root. It is written as SARIF 2.1.0 for code scanning (with the `json` feature)
+//! or as JUnit XML for test dashboards.
+
+use std::path::{Component, Path, PathBuf};
+
+use nom::IResult;
+
+use crate::lint::{Diagnostic, Severity};
+use crate::parsers::lexer::Span;
+use crate::parsers::parser_property_file::try_parse_property_file;
+use crate::parsers::source_map::{Position, SourceMap};
+
+/// The rule name of diagnostics for text that could not be parsed
+pub const PARSE_ERROR_RULE: &str = "parse-error";
+
+/// Returns the failure of a `parse_scene_file`, `parse_project_file` or
+/// `parse_property_file` call on `source` as an error diagnostic, or nothing if the whole
+/// text was parsed. The diagnostic starts where parsing stopped and covers the rest of that
+/// line. Its message gives the reason, such as an unclosed section header, when the text
+/// stopped at one.
+///
+/// # Example
+///
+/// ```
+/// use godot_properties_parser::parse_scene_file;
+/// use godot_properties_parser::report::parse_diagnostics;
+///
+/// let source = "[gd_scene format=3]\n\n[node name=\"Main\"\n";
+/// let diagnostics = parse_diagnostics(source, &parse_scene_file(source));
+/// assert_eq!(
+///     diagnostics[0].to_string(),
+///     "3:1: error[parse-error]: section header is not closed with `]`, the rest of the file is ignored"
+/// );
+/// ```
+pub fn parse_diagnostics<T>(source: &str, result: &IResult<&str, T>) -> Vec<Diagnostic> {
+    let (offset, message) = match result {
+        Ok((remaining, _)) if remaining.trim().is_empty() => return Vec::new(),
+        Ok((remaining, _)) => {
+            let skipped = remaining.len() - remaining.trim_start().len();
+            // The parse functions only return where they stopped; parse again for the reason
+            let reason = match try_parse_property_file(source) {
+                Err(error) => error.kind.to_string(),
+                Ok(_) => "content could not be parsed".to_string(),
+            };
+            (
+                source.len() - remaining.len() + skipped,
+                format!("{}, the rest of the file is ignored", reason),
+            )
+        }
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => (
+            source.len() - e.input.len(),
+            format!("parse error ({:?})", e.code),
+        ),
+        Err(nom::Err::Incomplete(_)) => (source.len(), "unexpected end of input".to_string()),
+    };
+
+    let line_end = source[offset..]
+        .find('\n')
+        .map_or(source.len(), |i| offset + i);
+    let end = offset + source[offset..line_end].trim_end().len();
+    vec![Diagnostic {
+        rule: PARSE_ERROR_RULE.to_string(),
+        severity: Severity::Error,
+        message,
+        location: SourceMap::new(source).locate(Span::new(offset, end)),
+    }]
+}
+
+/// A diagnostic in a report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportEntry {
+    pub diagnostic: Diagnostic,
+    /// The position of the character after the diagnostic's span
+    pub end: Position,
+}
+
+/// The diagnostics of one file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileReport {
+    /// The path relative to the report's root with `/` separators, or the whole path if the
+    /// file is not below the root
+    pub path: String,
+    pub entries: Vec<ReportEntry>,
+}
+
+/// Diagnostics of a set of files
+///
+/// # Example
+///
+/// ```
+/// use std::path::Path;
+///
+/// use godot_properties_parser::lint::Linter;
+/// use godot_properties_parser::parse_scene_file;
+/// use godot_properties_parser::report::{Report, parse_diagnostics};
+///
+/// let source = "[gd_scene format=3]\n\n[node name=\"Main\" type=\"Spatial\"]\n";
+/// let result = parse_scene_file(source);
+/// let mut diagnostics = parse_diagnostics(source, &result);
+/// if let Ok((_, scene)) = &result {
+///     diagnostics.extend(Linter::default().lint_scene(scene));
+/// }
+///
+/// let mut report = Report::new("/home/me/game");
+/// report.add(Path::new("/home/me/game/levels/main.tscn"), source, diagnostics);
+/// assert_eq!(report.files()[0].path, "levels/main.tscn");
+/// assert!(report.to_junit().contains("<testcase name=\"deprecated-node-type\""));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    root: PathBuf,
+    files: Vec<FileReport>,
+}
+
+impl Report {
+    /// Creates an empty report for files below `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            files: Vec::new(),
+        }
+    }
+
+    /// Adds a checked file with its source text, which the diagnostics' spans point into.
+    /// The diagnostics are sorted by position; files without any are listed as passing.
+    pub fn add(&mut self, path: &Path, source: &str, mut diagnostics: Vec<Diagnostic>) {
+        diagnostics.sort_by_key(|d| d.location.span.start);
+        let map = SourceMap::new(source);
+        let entries = diagnostics
+            .into_iter()
+            .map(|diagnostic| ReportEntry {
+                end: map.position(diagnostic.location.span.end),
+                diagnostic,
+            })
+            .collect();
+        self.files.push(FileReport {
+            path: relative_path(&self.root, path),
+            entries,
+        });
+    }
+
+    /// The files in the order they were added
+    pub fn files(&self) -> &[FileReport] {
+        &self.files
+    }
+
+    /// Returns true if any diagnostic is an error
+    pub fn has_errors(&self) -> bool {
+        self.entries()
+            .any(|(_, entry)| entry.diagnostic.severity == Severity::Error)
+    }
+
+    fn entries(&self) -> impl Iterator<Item = (&FileReport, &ReportEntry)> {
+        self.files
+            .iter()
+            .flat_map(|file| file.entries.iter().map(move |entry| (file, entry)))
+    }
+
+    /// Returns the report as a SARIF 2.1.0 log with a single run. Paths below the root are
+    /// relative to the `%SRCROOT%` base and other files have `file:` URIs. Columns are
+    /// counted in Unicode code points.
+    #[cfg(feature = "json")]
+    pub fn to_sarif(&self) -> serde_json::Value {
+        use serde_json::json;
+
+        let mut rules: Vec<&str> = self
+            .entries()
+            .map(|(_, entry)| entry.diagnostic.rule.as_str())
+            .collect();
+        rules.sort_unstable();
+        rules.dedup();
+
+        let results: Vec<_> = self
+            .entries()
+            .map(|(file, entry)| {
+                let diagnostic = &entry.diagnostic;
+                let level = match diagnostic.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                    Severity::Info => "note",
+                };
+                let artifact = if Path::new(&file.path).is_absolute() {
+                    json!({ "uri": file_uri(&file.path) })
+                } else {
+                    json!({ "uri": encode_uri(&file.path), "uriBaseId": "%SRCROOT%" })
+                };
+                json!({
+                    "ruleId": diagnostic.rule,
+                    "ruleIndex": rules.binary_search(&diagnostic.rule.as_str()).unwrap_or_default(),
+                    "level": level,
+                    "message": { "text": diagnostic.message },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": artifact,
+                            "region": {
+                                "startLine": diagnostic.location.line,
+                                "startColumn": diagnostic.location.column,
+                                "endLine": entry.end.line,
+                                "endColumn": entry.end.column,
+                            },
+                        },
+                    }],
+                })
+            })
+            .collect();
+
+        json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules.iter().map(|id| json!({ "id": id })).collect::<Vec<_>>(),
+                    },
+                },
+                "columnKind": "unicodeCodePoints",
+                "results": results,
+            }],
+        })
+    }
+
+    /// Returns the report as JUnit XML, with a test suite per file. Each diagnostic is a
+    /// failed test case named after its rule; a file without diagnostics has a single
+    /// passing test case named after the file.
+    pub fn to_junit(&self) -> String {
+        let tests: usize = self.files.iter().map(|f| f.entries.len().max(1)).sum();
+        let failures = self.entries().count();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\">\n",
+            env!("CARGO_PKG_NAME"),
+            tests,
+            failures
+        ));
+        for file in &self.files {
+            let path = escape_xml(&file.path);
+            xml.push_str(&format!(
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\">\n",
+                path,
+                file.entries.len().max(1),
+                file.entries.len()
+            ));
+            if file.entries.is_empty() {
+                xml.push_str(&format!(
+                    "    <testcase name=\"{0}\" classname=\"{0}\"/>\n",
+                    path
+                ));
+            }
+            for entry in &file.entries {
+                let diagnostic = &entry.diagnostic;
+                xml.push_str(&format!(
+                    "    <testcase name=\"{}\" classname=\"{}\">\n",
+                    escape_xml(&diagnostic.rule),
+                    path
+                ));
+                xml.push_str(&format!(
+                    "      <failure type=\"{}\" message=\"{}\">{}:{}</failure>\n",
+                    diagnostic.severity,
+                    escape_xml(&diagnostic.message),
+                    path,
+                    escape_xml(&diagnostic.to_string())
+                ));
+                xml.push_str("    </testcase>\n");
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+}
+
+/// Returns `path` relative to `root` with `/` separators, or the whole path if it is not
+/// below `root`
+fn relative_path(root: &Path, path: &Path) -> String {
+    let normal = |path: &Path| -> PathBuf {
+        path.components()
+            .filter(|c| *c != Component::CurDir)
+            .collect()
+    };
+    let (root, path) = (normal(root), normal(path));
+    match path.strip_prefix(&root) {
+        Ok(relative) => relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+        Err(_) => path.to_string_lossy().into_owned(),
+    }
+}
+
+/// Returns the `file:` URI of an absolute path, such as `file:///C:/game/main.tscn` for
+/// `C:\game\main.tscn`
+#[cfg(feature = "json")]
+fn file_uri(path: &str) -> String {
+    let path = path.replace(std::path::MAIN_SEPARATOR, "/");
+    if path.starts_with('/') {
+        format!("file://{}", encode_uri(&path))
+    } else {
+        format!("file:///{}", encode_uri(&path))
+    }
+}
+
+/// Percent-encodes a path for use as a URI reference. A `:` is kept only after the first
+/// `/`, where it cannot be read as the end of a scheme.
+#[cfg(feature = "json")]
+fn encode_uri(path: &str) -> String {
+    let first_slash = path.find('/').unwrap_or(path.len());
+    let mut uri = String::new();
+    for (i, byte) in path.bytes().enumerate() {
+        if byte.is_ascii_alphanumeric()
+            || b"-._~/".contains(&byte)
+            || (byte == b':' && i > first_slash)
+        {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    uri
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
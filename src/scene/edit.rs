@@ -0,0 +1,453 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use super::{ROOT_PATH, join_path, node_exists, node_path, parent_path};
+use crate::parsers::parser_property::UntypedProperty;
+use crate::parsers::parser_property_file::Section;
+use crate::parsers::parser_scene_file::SceneFile;
+use crate::parsers::parser_value::{Value, escape, node_path_literals, unescape};
+
+/// Characters Godot does not allow in node names
+const INVALID_NAME_CHARACTERS: &[char] = &['.', ':', '@', '/', '"', '%'];
+
+/// Why a scene edit was rejected. The scene is left unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SceneEditError {
+    /// No node has the given path
+    NodeNotFound(String),
+    /// The scene root cannot be removed or moved
+    RootNode,
+    /// The name is empty or contains one of `. : @ / " %`
+    InvalidName(String),
+    /// The parent already has a child with this name
+    NameTaken { parent: String, name: String },
+    /// A node cannot be moved below itself or one of its descendants
+    MoveIntoSubtree { path: String, new_parent: String },
+    /// The node does not instance another scene with `instance=ExtResource(...)`
+    NotAnInstance(String),
+    /// An inlined node has a unique name (`%Name`) that the node at `path` already uses
+    UniqueNameTaken { name: String, path: String },
+}
+
+impl fmt::Display for SceneEditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SceneEditError::NodeNotFound(path) => write!(f, "node {} not found", path),
+            SceneEditError::RootNode => write!(f, "the scene root cannot be removed or moved"),
+            SceneEditError::InvalidName(name) => write!(f, "invalid node name \"{}\"", name),
+            SceneEditError::NameTaken { parent, name } => {
+                write!(f, "node {} already has a child named {}", parent, name)
+            }
+            SceneEditError::MoveIntoSubtree { path, new_parent } => {
+                write!(f, "cannot move node {} below {}", path, new_parent)
+            }
+            SceneEditError::NotAnInstance(path) => {
+                write!(f, "node {} does not instance a scene", path)
+            }
+            SceneEditError::UniqueNameTaken { name, path } => {
+                write!(f, "unique name %{} is already used by node {}", name, path)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SceneEditError {}
+
+/// The node paths held by a node property, each with the node it resolved to before an edit
+struct NodePathProperty {
+    section: usize,
+    property: usize,
+    paths: Vec<Option<String>>,
+}
+
+impl<'a> SceneFile<'a> {
+    /// Returns the `[node]` section with the given path relative to the scene root, for editing
+    pub fn find_node_mut(&mut self, path: &str) -> Option<&mut Section<'a>> {
+        self.all_sections
+            .iter_mut()
+            .find(|s| s.header_type == "node" && node_path(s).is_some_and(|p| p == path))
+    }
+
+    /// Adds a `[node name="..." type="..." parent="..."]` section below `parent` and returns
+    /// it, so properties can be added.
+    ///
+    /// The section is inserted after the last node below `parent`, keeping parents before
+    /// their children as Godot does. `parent` may also be a node inside an instanced scene.
+    pub fn add_node(
+        &mut self,
+        parent: &str,
+        name: &str,
+        node_type: &str,
+    ) -> Result<&mut Section<'a>, SceneEditError> {
+        check_name(name)?;
+        let nodes = self.nodes_by_path();
+        if !node_exists(&nodes, parent) {
+            return Err(SceneEditError::NodeNotFound(parent.to_string()));
+        }
+        if nodes.contains_key(&join_path(parent, name)) {
+            return Err(SceneEditError::NameTaken {
+                parent: parent.to_string(),
+                name: name.to_string(),
+            });
+        }
+
+        let mut node = Section::new("node");
+        node.properties = vec![
+            UntypedProperty::new_quoted("name", name.to_string()),
+            UntypedProperty::new_quoted("type", node_type.to_string()),
+            UntypedProperty::new_quoted("parent", parent.to_string()),
+        ];
+        node.attribute_count = node.properties.len();
+
+        let index = self.subtree_end(parent);
+        self.all_sections.insert(index, node);
+        Ok(&mut self.all_sections[index])
+    }
+
+    /// Removes a node and all of its descendants, together with the `[connection]` and
+    /// `[editable]` sections that refer to them. Returns the removed sections in file order.
+    ///
+    /// Node paths in other nodes that pointed into the removed subtree are left as they are;
+    /// `broken_node_paths` reports the exported ones.
+    pub fn remove_node(&mut self, path: &str) -> Result<Vec<Section<'a>>, SceneEditError> {
+        if path == ROOT_PATH {
+            return Err(SceneEditError::RootNode);
+        }
+        if self.find_node(path).is_none() {
+            return Err(SceneEditError::NodeNotFound(path.to_string()));
+        }
+
+        let (removed, kept) = std::mem::take(&mut self.all_sections)
+            .into_iter()
+            .partition(|section| refers_to_subtree(section, path));
+        self.all_sections = kept;
+        Ok(removed)
+    }
+
+    /// Renames a node and returns its new path.
+    ///
+    /// The `parent=` attributes of its descendants, `[connection]` and `[editable]` paths and
+    /// `NodePath` properties that referred to the node or its descendants are updated. A
+    /// `%Name` segment naming the renamed unique node is renamed with it.
+    pub fn rename_node(&mut self, path: &str, new_name: &str) -> Result<String, SceneEditError> {
+        check_name(new_name)?;
+        if self.find_node(path).is_none() {
+            return Err(SceneEditError::NodeNotFound(path.to_string()));
+        }
+        let Some(parent) = parent_path(path) else {
+            // Nothing refers to the root by name
+            if let Some(root) = self.find_node_mut(ROOT_PATH) {
+                set_attribute(root, "name", new_name);
+            }
+            return Ok(ROOT_PATH.to_string());
+        };
+
+        let new_path = join_path(parent, new_name);
+        if new_path == path {
+            return Ok(new_path);
+        }
+        if self.nodes_by_path().contains_key(&new_path) {
+            return Err(SceneEditError::NameTaken {
+                parent: parent.to_string(),
+                name: new_name.to_string(),
+            });
+        }
+
+        self.move_subtree(path, &new_path, "name", new_name);
+        Ok(new_path)
+    }
+
+    /// Moves a node and its descendants below `new_parent` and returns its new path.
+    ///
+    /// References are updated as in `rename_node`. Node paths that were written relative to
+    /// or pointing at a moved node are rewritten as relative paths from their node when they
+    /// no longer resolve to the same node. The moved sections are placed after the last node
+    /// below `new_parent`.
+    pub fn reparent_node(
+        &mut self,
+        path: &str,
+        new_parent: &str,
+    ) -> Result<String, SceneEditError> {
+        if path == ROOT_PATH {
+            return Err(SceneEditError::RootNode);
+        }
+        let Some(name) = self
+            .find_node(path)
+            .and_then(|node| node.get("name"))
+            .map(str::to_string)
+        else {
+            return Err(SceneEditError::NodeNotFound(path.to_string()));
+        };
+        let nodes = self.nodes_by_path();
+        if !node_exists(&nodes, new_parent) {
+            return Err(SceneEditError::NodeNotFound(new_parent.to_string()));
+        }
+        if in_subtree(new_parent, path) {
+            return Err(SceneEditError::MoveIntoSubtree {
+                path: path.to_string(),
+                new_parent: new_parent.to_string(),
+            });
+        }
+
+        let new_path = join_path(new_parent, &name);
+        if new_path == path {
+            return Ok(new_path);
+        }
+        if nodes.contains_key(&new_path) {
+            return Err(SceneEditError::NameTaken {
+                parent: new_parent.to_string(),
+                name,
+            });
+        }
+
+        self.move_subtree(path, &new_path, "parent", new_parent);
+
+        let (moved, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.all_sections)
+            .into_iter()
+            .partition(|s| {
+                s.header_type == "node" && node_path(s).is_some_and(|p| in_subtree(&p, &new_path))
+            });
+        self.all_sections = kept;
+        let index = self.subtree_end(new_parent);
+        self.all_sections.splice(index..index, moved);
+        Ok(new_path)
+    }
+
+    /// Gives the node at `old` the path `new` by setting its `key` attribute, and updates
+    /// every reference to the node and its descendants
+    fn move_subtree(&mut self, old: &str, new: &str, key: &str, value: &str) {
+        let node_path_properties = self.node_path_properties();
+
+        if let Some(node) = self.find_node_mut(old) {
+            set_attribute(node, key, value);
+        }
+        for section in &mut self.all_sections {
+            let keys: &[&str] = match section.header_type.as_ref() {
+                "node" => &["parent"],
+                "connection" => &["from", "to"],
+                "editable" => &["path"],
+                _ => &[],
+            };
+            for key in keys {
+                if let Some(path) = section.get(key).and_then(|p| moved(&unescape(p), old, new)) {
+                    set_attribute(section, key, &path);
+                }
+            }
+        }
+
+        let old_name = old.rsplit('/').next().unwrap_or(old);
+        let new_name = new.rsplit('/').next().unwrap_or(new);
+        let resolver = self.node_path_resolver();
+        let mut changed_values = Vec::new();
+        for property in node_path_properties {
+            let Some(from) = node_path(&self.all_sections[property.section]) else {
+                continue;
+            };
+            let stored = &self.all_sections[property.section].properties[property.property];
+            let Some(value) = Value::parse(&stored.value).filter(|_| !stored.quoted) else {
+                continue;
+            };
+            // Only the changed paths are rewritten, keeping the rest of the value as written
+            let literals = node_path_literals(&stored.value);
+            let paths = value.node_paths();
+            if literals.len() != paths.len() {
+                continue;
+            }
+
+            let mut text = stored.value.to_string();
+            let mut changed = false;
+            let targets = paths.into_iter().zip(property.paths).zip(literals);
+            for ((path, target), span) in targets.rev() {
+                let Some(target) = target else {
+                    continue;
+                };
+                let target = moved(&target, old, new).unwrap_or(target);
+                if resolver.resolve(&from, path).as_ref() == Some(&target) {
+                    continue;
+                }
+
+                let renamed = rename_unique(path, old_name, new_name);
+                let path = if resolver.resolve(&from, &renamed).as_ref() == Some(&target) {
+                    renamed
+                } else {
+                    match path.split_once(':') {
+                        Some((_, subpath)) => {
+                            format!("{}:{}", relative_path(&from, &target), subpath)
+                        }
+                        None => relative_path(&from, &target),
+                    }
+                };
+                text.replace_range(span.start..span.end, &format!("\"{}\"", escape(&path)));
+                changed = true;
+            }
+
+            if changed {
+                changed_values.push((property.section, property.property, text));
+            }
+        }
+
+        for (section, property, value) in changed_values {
+            self.all_sections[section].properties[property].value = Cow::Owned(value);
+        }
+    }
+
+    /// Collects the node properties holding node paths, with the nodes they resolve to
+    fn node_path_properties(&self) -> Vec<NodePathProperty> {
+        let resolver = self.node_path_resolver();
+        let mut properties = Vec::new();
+        for (i, section) in self.all_sections.iter().enumerate() {
+            if section.header_type != "node" {
+                continue;
+            }
+            let Some(from) = node_path(section) else {
+                continue;
+            };
+            for (j, property) in section.body().iter().enumerate() {
+                let Some(value) = Value::parse(&property.value_text()) else {
+                    continue;
+                };
+                let paths: Vec<_> = value
+                    .node_paths()
+                    .into_iter()
+                    .map(|path| resolver.resolve(&from, path))
+                    .collect();
+                if !paths.is_empty() {
+                    properties.push(NodePathProperty {
+                        section: i,
+                        property: section.attribute_count + j,
+                        paths,
+                    });
+                }
+            }
+        }
+        properties
+    }
+
+    /// Returns the index after the last node below `path`, or below its nearest ancestor
+    /// declared in this file
+    fn subtree_end(&self, path: &str) -> usize {
+        let mut current = Some(path);
+        while let Some(path) = current {
+            let last = self.all_sections.iter().rposition(|s| {
+                s.header_type == "node" && node_path(s).is_some_and(|p| in_subtree(&p, path))
+            });
+            if let Some(last) = last {
+                return last + 1;
+            }
+            current = parent_path(path);
+        }
+        self.all_sections.len()
+    }
+}
+
+fn check_name(name: &str) -> Result<(), SceneEditError> {
+    if name.is_empty() || name.contains(INVALID_NAME_CHARACTERS) {
+        return Err(SceneEditError::InvalidName(name.to_string()));
+    }
+    Ok(())
+}
+
+/// Returns true if `path` is `root` or one of its descendants
+pub(super) fn in_subtree(path: &str, root: &str) -> bool {
+    root == ROOT_PATH
+        || path == root
+        || path
+            .strip_prefix(root)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Returns the new path of `path` after the node at `old` moved to `new`, or `None` if
+/// `path` is not in the moved subtree
+fn moved(path: &str, old: &str, new: &str) -> Option<String> {
+    if path == old {
+        return Some(new.to_string());
+    }
+    let rest = path.strip_prefix(old)?.strip_prefix('/')?;
+    Some(format!("{}/{}", new, rest))
+}
+
+/// Returns true if a `[node]`, `[connection]` or `[editable]` section refers to the node at
+/// `root` or one of its descendants
+fn refers_to_subtree(section: &Section, root: &str) -> bool {
+    let inside = |path: Option<&str>| path.is_some_and(|p| in_subtree(&unescape(p), root));
+    match section.header_type.as_ref() {
+        "node" => node_path(section).is_some_and(|p| in_subtree(&p, root)),
+        "connection" => inside(section.get("from")) || inside(section.get("to")),
+        "editable" => inside(section.get("path")),
+        _ => false,
+    }
+}
+
+/// Sets a header attribute to the quoted string `value`, which is escaped, adding it after
+/// the existing ones if it is missing
+pub(crate) fn set_attribute(section: &mut Section, key: &str, value: &str) {
+    let value = Cow::Owned(escape(value));
+    let count = section.attributes().len();
+    match section.properties[..count]
+        .iter_mut()
+        .find(|p| p.key == key)
+    {
+        Some(property) => {
+            property.value = value;
+            property.quoted = true;
+        }
+        None => {
+            let property = UntypedProperty::new_quoted(key.to_string(), value);
+            section.properties.insert(count, property);
+            section.attribute_count = count + 1;
+        }
+    }
+}
+
+/// Removes a header attribute if it is present
+pub(super) fn remove_attribute(section: &mut Section, key: &str) {
+    if let Some(index) = section.attributes().iter().position(|p| p.key == key) {
+        section.properties.remove(index);
+        section.attribute_count -= 1;
+    }
+}
+
+/// Replaces `%old` segments of a node path with `%new`
+fn rename_unique(path: &str, old: &str, new: &str) -> String {
+    let (nodes, subpath) = match path.split_once(':') {
+        Some((nodes, subpath)) => (nodes, Some(subpath)),
+        None => (path, None),
+    };
+    let old = format!("%{}", old);
+    let nodes: Vec<_> = nodes
+        .split('/')
+        .map(|segment| {
+            if segment == old {
+                format!("%{}", new)
+            } else {
+                segment.to_string()
+            }
+        })
+        .collect();
+    match subpath {
+        Some(subpath) => format!("{}:{}", nodes.join("/"), subpath),
+        None => nodes.join("/"),
+    }
+}
+
+/// Returns the node path leading from the node at `from` to the node at `to`, both
+/// relative to the scene root
+fn relative_path(from: &str, to: &str) -> String {
+    let segments = |path: &str| -> Vec<String> {
+        if path == ROOT_PATH {
+            Vec::new()
+        } else {
+            path.split('/').map(str::to_string).collect()
+        }
+    };
+    let (from, to) = (segments(from), segments(to));
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut parts = vec!["..".to_string(); from.len() - common];
+    parts.extend_from_slice(&to[common..]);
+    if parts.is_empty() {
+        ROOT_PATH.to_string()
+    } else {
+        parts.join("/")
+    }
+}
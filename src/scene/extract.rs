@@ -0,0 +1,291 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::edit::{SceneEditError, in_subtree, remove_attribute, set_attribute};
+use super::resources::{ResourceKind, id_number, id_suffix, rename_resource_refs, renumber_id};
+use super::{ROOT_PATH, node_path};
+use crate::parsers::parser_property::UntypedProperty;
+use crate::parsers::parser_property_file::Section;
+use crate::parsers::parser_scene_file::SceneFile;
+use crate::parsers::parser_value::{Value, unescape};
+
+/// A reference between an extracted branch and the rest of the scene. Paths are relative to
+/// the root of the original scene.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoundaryReference {
+    /// A node in the branch holds a node path to a node outside it. The new scene keeps the
+    /// path as written, so it no longer resolves there.
+    NodePath {
+        /// Path of the node holding the property
+        node: String,
+        /// Name of the property
+        property: String,
+        /// The node path as written in the scene
+        path: String,
+        /// The node outside the branch it resolves to
+        resolved: String,
+    },
+    /// A connection with one end in the branch. It stays in the original scene, where it
+    /// now reaches into the instance.
+    Connection {
+        /// Name of the signal
+        signal: String,
+        /// Path of the node emitting the signal
+        from: String,
+        /// Path of the node receiving the signal
+        to: String,
+        /// Name of the method called
+        method: String,
+    },
+}
+
+/// The result of `SceneFile::extract_subtree`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedBranch<'a> {
+    /// The new scene holding the branch
+    pub scene: SceneFile<'a>,
+    /// References crossing the boundary of the branch, in file order, which were neither
+    /// moved nor rewritten
+    pub boundary_references: Vec<BoundaryReference>,
+}
+
+impl<'a> SceneFile<'a> {
+    /// Moves the node at `path` and its descendants into a new scene and returns it, like
+    /// the editor's "Save Branch as Scene".
+    ///
+    /// The new scene gets copies of the ext and sub-resources the branch uses, renumbered
+    /// from 1 in file order, and the `[connection]` and `[editable]` sections that lie within
+    /// the branch. In this scene the branch is replaced with a node instancing `scene_path`
+    /// through a new `[ext_resource]`, and resources nothing else uses are removed.
+    ///
+    /// Connections between the branch and the rest of the scene are kept here, and node paths
+    /// in the branch pointing outside it are left as written. Both are returned as
+    /// `boundary_references`, since the new scene cannot hold them.
+    pub fn extract_subtree(
+        &mut self,
+        path: &str,
+        scene_path: &str,
+    ) -> Result<ExtractedBranch<'a>, SceneEditError> {
+        if path == ROOT_PATH {
+            return Err(SceneEditError::RootNode);
+        }
+        if self.find_node(path).is_none() {
+            return Err(SceneEditError::NodeNotFound(path.to_string()));
+        }
+
+        let in_branch = |section: &Section| {
+            let inside = |key| {
+                section
+                    .get(key)
+                    .is_some_and(|p| in_subtree(&unescape(p), path))
+            };
+            match section.header_type.as_ref() {
+                "node" => node_path(section).is_some_and(|p| in_subtree(&p, path)),
+                "connection" => inside("from") && inside("to"),
+                "editable" => inside("path"),
+                _ => false,
+            }
+        };
+        let (branch, outside): (Vec<&Section>, Vec<&Section>) =
+            self.all_sections.iter().partition(|s| in_branch(s));
+        let used = self.resources_used_by(&branch);
+        let outside: Vec<_> = outside
+            .into_iter()
+            .filter(|s| ResourceKind::of(s).is_none())
+            .collect();
+        let still_used = self.resources_used_by(&outside);
+        let unused = |key: &(ResourceKind, String)| used.contains(key) && !still_used.contains(key);
+
+        let boundary_references = self.boundary_references(path);
+        let scene = self.branch_scene(&branch, path, &used);
+
+        // Replace the branch with an instance and drop the resources only it used
+        let number = self
+            .ext_resources()
+            .into_iter()
+            .filter_map(|ext| ext.get("id"))
+            .filter(|id| !unused(&(ResourceKind::Ext, id.to_string())))
+            .filter_map(id_number)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let id = format!("{}_{}", number, id_suffix(scene_path));
+        let sections = std::mem::take(&mut self.all_sections);
+        self.all_sections = sections
+            .into_iter()
+            .filter_map(|section| {
+                if section.header_type == "node" && node_path(&section).as_deref() == Some(path) {
+                    return Some(instance_node(&section, &id));
+                }
+                if in_branch(&section) {
+                    return None;
+                }
+                if let Some(kind) = ResourceKind::of(&section) {
+                    let key = (kind, section.get("id").unwrap_or_default().to_string());
+                    if unused(&key) {
+                        return None;
+                    }
+                }
+                Some(section)
+            })
+            .collect();
+
+        let mut ext = Section::new("ext_resource");
+        ext.properties = vec![
+            UntypedProperty::new_quoted("type", "PackedScene"),
+            UntypedProperty::new_quoted("path", scene_path.to_string()),
+            UntypedProperty::new_quoted("id", id),
+        ];
+        ext.attribute_count = ext.properties.len();
+        let index = self
+            .all_sections
+            .iter()
+            .rposition(|s| matches!(s.header_type.as_ref(), "ext_resource" | "gd_scene"))
+            .map_or(0, |i| i + 1);
+        self.all_sections.insert(index, ext);
+        self.update_load_steps();
+
+        Ok(ExtractedBranch {
+            scene,
+            boundary_references,
+        })
+    }
+
+    /// Collects the node paths and connections between the branch at `path` and the rest
+    /// of the scene
+    fn boundary_references(&self, path: &str) -> Vec<BoundaryReference> {
+        let resolver = self.node_path_resolver();
+        let mut references = Vec::new();
+        for section in &self.all_sections {
+            match section.header_type.as_ref() {
+                "node" => {
+                    let Some(from) = node_path(section).filter(|p| in_subtree(p, path)) else {
+                        continue;
+                    };
+                    for property in section.body() {
+                        let Some(value) = Value::parse(&property.value_text()) else {
+                            continue;
+                        };
+                        for node_path in value.node_paths() {
+                            let Some(resolved) = resolver.resolve(&from, node_path) else {
+                                continue;
+                            };
+                            if !in_subtree(&resolved, path) {
+                                references.push(BoundaryReference::NodePath {
+                                    node: from.clone(),
+                                    property: property.key.to_string(),
+                                    path: node_path.to_string(),
+                                    resolved,
+                                });
+                            }
+                        }
+                    }
+                }
+                "connection" => {
+                    let get = |key| section.get(key).map(unescape).unwrap_or_default();
+                    let (from, to) = (get("from"), get("to"));
+                    if in_subtree(&from, path) != in_subtree(&to, path) {
+                        references.push(BoundaryReference::Connection {
+                            signal: get("signal"),
+                            from,
+                            to,
+                            method: get("method"),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        references
+    }
+
+    /// Builds the scene for a branch: a header, the `used` resources renumbered, and the
+    /// branch sections with their paths made relative to the branch root
+    fn branch_scene(
+        &self,
+        branch: &[&Section<'a>],
+        path: &str,
+        used: &BTreeSet<(ResourceKind, String)>,
+    ) -> SceneFile<'a> {
+        let mut scene = SceneFile::new();
+        let mut header = Section::new("gd_scene");
+        let format = self.header().and_then(|h| h.get("format")).unwrap_or("3");
+        header
+            .properties
+            .push(UntypedProperty::new("format", format.to_string()));
+        header.attribute_count = header.properties.len();
+        scene.all_sections.push(header);
+
+        // Resources keep their file order, ext before sub, and each kind is numbered from 1
+        let mut renamed = BTreeMap::new();
+        for kind in [ResourceKind::Ext, ResourceKind::Sub] {
+            let resources = self.all_sections.iter().filter(|s| {
+                ResourceKind::of(s) == Some(kind)
+                    && s.get("id")
+                        .is_some_and(|id| used.contains(&(kind, id.to_string())))
+            });
+            for (i, resource) in resources.enumerate() {
+                let mut resource = resource.clone();
+                let id = resource.get("id").unwrap_or_default().to_string();
+                let new_id = renumber_id(&id, i + 1);
+                set_attribute(&mut resource, "id", &new_id);
+                renamed.insert((kind, id), new_id);
+                scene.all_sections.push(resource);
+            }
+        }
+
+        // Godot writes nodes first, then connections, then editable paths
+        let local = |p: &str| match p.strip_prefix(path) {
+            Some("") => ROOT_PATH.to_string(),
+            Some(rest) => rest.trim_start_matches('/').to_string(),
+            None => p.to_string(),
+        };
+        for (header_type, keys) in [
+            ("node", &["parent"][..]),
+            ("connection", &["from", "to"][..]),
+            ("editable", &["path"][..]),
+        ] {
+            for section in branch.iter().filter(|s| s.header_type == header_type) {
+                let mut section = (*section).clone();
+                for key in keys {
+                    match section.get(key).map(unescape) {
+                        Some(p) if in_subtree(&p, path) => {
+                            let p = local(&p);
+                            set_attribute(&mut section, key, &p);
+                        }
+                        // The parent of the branch root
+                        Some(_) => remove_attribute(&mut section, key),
+                        None => {}
+                    }
+                }
+                scene.all_sections.push(section);
+            }
+        }
+
+        let rename = |kind, id: &str| renamed.get(&(kind, id.to_string())).cloned();
+        for section in &mut scene.all_sections {
+            rename_resource_refs(section, &rename);
+        }
+        scene.update_load_steps();
+        scene
+    }
+}
+
+/// Returns a `[node]` section instancing `ExtResource(id)` in place of `node`. The node keeps
+/// its name, parent and unique name.
+fn instance_node<'a>(node: &Section<'a>, id: &str) -> Section<'a> {
+    let mut instance = Section::new("node");
+    for key in ["name", "parent"] {
+        if let Some(property) = node.attributes().iter().find(|p| p.key == key) {
+            instance.properties.push(property.clone());
+        }
+    }
+    instance.properties.push(UntypedProperty::new(
+        "instance",
+        format!("ExtResource(\"{}\")", id),
+    ));
+    instance.attribute_count = instance.properties.len();
+    if let Some(unique) = node.body().iter().find(|p| p.key == "unique_name_in_owner") {
+        instance.properties.push(unique.clone());
+    }
+    instance
+}
@@ -0,0 +1,35 @@
+use std::collections::BTreeMap;
+
+use super::node_path;
+use crate::parsers::parser_property_file::Section;
+use crate::parsers::parser_scene_file::SceneFile;
+use crate::parsers::parser_value::Value;
+
+/// Returns the groups assigned to a node by its `groups=[...]` attribute
+pub fn node_groups(node: &Section) -> Vec<String> {
+    node.get("groups")
+        .and_then(Value::parse)
+        .and_then(|value| {
+            value
+                .as_string_array()
+                .map(|groups| groups.into_iter().map(str::to_string).collect())
+        })
+        .unwrap_or_default()
+}
+
+impl SceneFile<'_> {
+    /// Returns every group used in the scene, mapped to the paths of its member nodes in
+    /// file order
+    pub fn groups(&self) -> BTreeMap<String, Vec<String>> {
+        let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for node in self.nodes() {
+            let Some(path) = node_path(node) else {
+                continue;
+            };
+            for group in node_groups(node) {
+                groups.entry(group).or_default().push(path.clone());
+            }
+        }
+        groups
+    }
+}
@@ -0,0 +1,334 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::edit::{SceneEditError, in_subtree, set_attribute};
+use super::resources::{ResourceKind, id_number, id_suffix, rename_resource_refs};
+use super::unique_names::{is_unique, owner_of};
+use super::{ROOT_PATH, join_path, node_path};
+use crate::parsers::parser_property::UntypedProperty;
+use crate::parsers::parser_property_file::Section;
+use crate::parsers::parser_scene_file::SceneFile;
+use crate::parsers::parser_value::{Value, unescape};
+
+/// Attributes of the instancing node that do not carry over to the inlined root
+const INSTANCE_ATTRIBUTES: &[&str] = &["name", "parent", "instance", "instance_placeholder"];
+
+/// The resources of an instanced scene, ready to be added to the instancing scene
+struct ImportedResources {
+    /// New ids by kind and old id
+    renamed: BTreeMap<(ResourceKind, String), String>,
+    ext_resources: Vec<Section<'static>>,
+    sub_resources: Vec<Section<'static>>,
+}
+
+impl<'a> SceneFile<'a> {
+    /// Replaces the node at `path`, which instances another scene, with the contents of
+    /// `instanced`, the scene it instances.
+    ///
+    /// The instanced nodes are copied below the node's parent, keeping the node's name. The
+    /// properties the node overrides, and those of `[node]` sections overriding nodes inside
+    /// the instance, are applied to the copies. External resources are reused when this scene
+    /// already loads the same path, sub-resources get new unique ids, and the instanced
+    /// scene's connections and editable paths are added with their paths adjusted. The
+    /// `[ext_resource]` of the instanced scene is removed once nothing uses it.
+    ///
+    /// The inlined nodes become owned by this scene's root, so the inlining is rejected
+    /// with `SceneEditError::UniqueNameTaken` if one of their unique names is already used
+    /// here.
+    pub fn inline_instance(
+        &mut self,
+        path: &str,
+        instanced: &SceneFile,
+    ) -> Result<(), SceneEditError> {
+        let Some(instance) = self.find_node(path).cloned().map(Section::into_owned) else {
+            return Err(SceneEditError::NodeNotFound(path.to_string()));
+        };
+        let Some(scene_id) = instance
+            .get("instance")
+            .and_then(Value::parse)
+            .and_then(|value| value.as_ext_resource().map(str::to_string))
+        else {
+            return Err(SceneEditError::NotAnInstance(path.to_string()));
+        };
+        if instanced.find_node(ROOT_PATH).is_none() {
+            return Err(SceneEditError::NodeNotFound(ROOT_PATH.to_string()));
+        }
+
+        let ImportedResources {
+            renamed,
+            ext_resources,
+            sub_resources,
+        } = self.import_resources(path, instanced);
+        let rename = |kind, id: &str| renamed.get(&(kind, id.to_string())).cloned();
+        let under = |local: &str| match local {
+            ROOT_PATH => path.to_string(),
+            _ => join_path(path, local),
+        };
+
+        // Sections overriding properties of nodes inside the instance
+        let overrides: BTreeMap<String, Section> = self
+            .nodes()
+            .into_iter()
+            .filter(|node| node.get("type").is_none() && node.get("instance").is_none())
+            .filter_map(|node| Some((node_path(node)?, node.clone())))
+            .filter(|(node, _)| node != path && in_subtree(node, path))
+            .collect();
+        if let Some((name, taken_by)) = self.unique_name_clash(path, instanced, &overrides) {
+            return Err(SceneEditError::UniqueNameTaken {
+                name,
+                path: taken_by,
+            });
+        }
+        let mut applied = BTreeSet::new();
+
+        let mut nodes = Vec::new();
+        for node in instanced.nodes() {
+            let Some(local) = node_path(node) else {
+                continue;
+            };
+            let mut node: Section<'a> = node.clone().into_owned();
+            rename_resource_refs(&mut node, &rename);
+            let full_path = under(&local);
+            if local == ROOT_PATH {
+                node = merge_root(&instance, node);
+            } else {
+                if let Some(parent) = node.get("parent").map(|p| under(&unescape(p))) {
+                    set_attribute(&mut node, "parent", &parent);
+                }
+                if let Some(overriding) = overrides.get(&full_path) {
+                    apply_overrides(&mut node, overriding.body());
+                    applied.insert(full_path);
+                }
+            }
+            nodes.push(node);
+        }
+
+        let mut connections = Vec::new();
+        for section in instanced
+            .connections()
+            .into_iter()
+            .chain(instanced.editables())
+        {
+            let mut section = section.clone().into_owned();
+            for key in ["from", "to", "path"] {
+                if let Some(value) = section.get(key).map(|p| under(&unescape(p))) {
+                    set_attribute(&mut section, key, &value);
+                }
+            }
+            connections.push(section);
+        }
+
+        let sections = std::mem::take(&mut self.all_sections);
+        for section in sections {
+            let current = node_path(&section).filter(|_| section.header_type == "node");
+            if current.as_deref() == Some(path) {
+                self.all_sections.append(&mut nodes);
+            } else if current.is_some_and(|p| applied.contains(&p))
+                || (section.header_type == "editable" && section.get("path") == Some(path))
+            {
+                continue;
+            } else {
+                self.all_sections.push(section);
+            }
+        }
+
+        // Connections go after the nodes and existing connections, editable paths last
+        for section in connections {
+            let index = match section.header_type.as_ref() {
+                "connection" => self
+                    .all_sections
+                    .iter()
+                    .position(|s| s.header_type == "editable")
+                    .unwrap_or(self.all_sections.len()),
+                _ => self.all_sections.len(),
+            };
+            self.all_sections.insert(index, section);
+        }
+        for (kind, resources) in [
+            (ResourceKind::Sub, sub_resources),
+            (ResourceKind::Ext, ext_resources),
+        ] {
+            let index = self.resource_insert_index(kind);
+            self.all_sections.splice(index..index, resources);
+        }
+
+        let others: Vec<_> = self
+            .all_sections
+            .iter()
+            .filter(|s| ResourceKind::of(s).is_none())
+            .collect();
+        if !self
+            .resources_used_by(&others)
+            .contains(&(ResourceKind::Ext, scene_id.clone()))
+        {
+            self.all_sections.retain(|s| {
+                ResourceKind::of(s) != Some(ResourceKind::Ext) || s.get("id") != Some(&scene_id)
+            });
+        }
+        self.update_load_steps();
+
+        Ok(())
+    }
+
+    /// Returns a unique name that a node of `instanced` would bring into this scene while a
+    /// node here already uses it, with the path of that node. `overrides` may set or clear
+    /// `unique_name_in_owner` on the inlined nodes; the instanced root takes the instancing
+    /// node's unique name, so it is not checked.
+    fn unique_name_clash(
+        &self,
+        path: &str,
+        instanced: &SceneFile,
+        overrides: &BTreeMap<String, Section>,
+    ) -> Option<(String, String)> {
+        let taken = self.unique_names();
+        let nodes = instanced.nodes_by_path();
+        nodes.iter().find_map(|(local, node)| {
+            if owner_of(&nodes, local).as_deref() != Some(ROOT_PATH) {
+                return None;
+            }
+            let unique = match overrides.get(&join_path(path, local)) {
+                Some(overriding) if overriding.get("unique_name_in_owner").is_some() => {
+                    is_unique(overriding)
+                }
+                _ => is_unique(node),
+            };
+            let name = node.get("name").filter(|_| unique)?;
+            let taken_by = taken.get(name)?;
+            Some((name.to_string(), taken_by.clone()))
+        })
+    }
+
+    /// Prepares the resources of `instanced` for this scene.
+    ///
+    /// External resources already loaded by this scene are reused. The others are numbered
+    /// after the existing ones. Sub-resources get a new `Type_xxxxx` id derived from `path`.
+    fn import_resources(&self, path: &str, instanced: &SceneFile) -> ImportedResources {
+        let mut renamed = BTreeMap::new();
+        let mut ext_resources = Vec::new();
+        let mut next = self
+            .ext_resources()
+            .iter()
+            .filter_map(|ext| ext.get("id").and_then(id_number))
+            .max()
+            .unwrap_or(0)
+            + 1;
+        for ext in instanced.ext_resources() {
+            let id = ext.get("id").unwrap_or_default().to_string();
+            let existing = self
+                .ext_resources()
+                .into_iter()
+                .find(|own| own.get("path") == ext.get("path"))
+                .and_then(|own| own.get("id"));
+            let new_id = match existing {
+                Some(existing) => existing.to_string(),
+                None => {
+                    let new_id = format!("{}_{}", next, id_suffix(ext.get("path").unwrap_or(&id)));
+                    next += 1;
+                    let mut section = ext.clone().into_owned();
+                    set_attribute(&mut section, "id", &new_id);
+                    ext_resources.push(section);
+                    new_id
+                }
+            };
+            renamed.insert((ResourceKind::Ext, id), new_id);
+        }
+
+        let mut taken: BTreeSet<String> = self
+            .sub_resources()
+            .iter()
+            .filter_map(|sub| sub.get("id"))
+            .map(str::to_string)
+            .collect();
+        let mut sub_resources = Vec::new();
+        for sub in instanced.sub_resources() {
+            let id = sub.get("id").unwrap_or_default().to_string();
+            let resource_type = sub.get("type").unwrap_or("Resource");
+            let mut seed = format!("{}/{}", path, id);
+            let mut new_id = format!("{}_{}", resource_type, id_suffix(&seed));
+            while taken.contains(&new_id) {
+                seed.push('/');
+                new_id = format!("{}_{}", resource_type, id_suffix(&seed));
+            }
+            taken.insert(new_id.clone());
+
+            let mut section = sub.clone().into_owned();
+            set_attribute(&mut section, "id", &new_id);
+            sub_resources.push(section);
+            renamed.insert((ResourceKind::Sub, id), new_id);
+        }
+
+        let rename = |kind, id: &str| renamed.get(&(kind, id.to_string())).cloned();
+        for section in &mut sub_resources {
+            rename_resource_refs(section, &rename);
+        }
+        ImportedResources {
+            renamed,
+            ext_resources,
+            sub_resources,
+        }
+    }
+
+    /// Returns the index after the last section of `kind`, or where the first one would go
+    fn resource_insert_index(&self, kind: ResourceKind) -> usize {
+        let last = |kinds: &[&str]| {
+            self.all_sections
+                .iter()
+                .rposition(|s| kinds.contains(&s.header_type.as_ref()))
+                .map(|i| i + 1)
+        };
+        let after = match kind {
+            ResourceKind::Ext => last(&["ext_resource", "gd_scene", "gd_resource"]),
+            ResourceKind::Sub => last(&["sub_resource", "ext_resource", "gd_scene", "gd_resource"]),
+        };
+        after.unwrap_or(0)
+    }
+}
+
+/// Combines the instancing node with the root of the instanced scene: the name and parent
+/// of the instance, the type and attributes of the root, and the root's properties with the
+/// instance's overrides applied
+fn merge_root<'a>(instance: &Section<'a>, root: Section<'a>) -> Section<'a> {
+    let mut merged = Section::new("node");
+    let attribute =
+        |section: &Section<'a>, key| section.attributes().iter().find(|p| p.key == key).cloned();
+    merged.properties.extend(attribute(instance, "name"));
+    merged.properties.extend(attribute(&root, "type"));
+    merged.properties.extend(attribute(instance, "parent"));
+    let root_attributes = root
+        .attributes()
+        .iter()
+        .filter(|p| !matches!(p.key.as_ref(), "name" | "type" | "parent"));
+    let instance_attributes = instance
+        .attributes()
+        .iter()
+        .filter(|p| !INSTANCE_ATTRIBUTES.contains(&p.key.as_ref()));
+    for property in root_attributes.chain(instance_attributes) {
+        match merged.properties.iter_mut().find(|p| p.key == property.key) {
+            Some(existing) => *existing = property.clone(),
+            None => merged.properties.push(property.clone()),
+        }
+    }
+    merged.attribute_count = merged.properties.len();
+
+    merged.properties.extend(
+        root.body()
+            .iter()
+            .filter(|p| p.key != "unique_name_in_owner")
+            .cloned(),
+    );
+    apply_overrides(&mut merged, instance.body());
+    merged
+}
+
+/// Sets each of the `overrides` on the body of `node`, replacing existing values
+fn apply_overrides<'a>(node: &mut Section<'a>, overrides: &[UntypedProperty<'a>]) {
+    for property in overrides {
+        let attribute_count = node.attribute_count;
+        match node.properties[attribute_count..]
+            .iter_mut()
+            .find(|p| p.key == property.key)
+        {
+            Some(existing) => *existing = property.clone(),
+            None => node.properties.push(property.clone()),
+        }
+    }
+}
@@ -0,0 +1,176 @@
+pub mod edit;
+mod extract;
+pub mod groups;
+mod inline;
+pub mod node_paths;
+pub(crate) mod resources;
+pub mod unique_names;
+
+use std::collections::BTreeMap;
+
+use crate::parsers::parser_property_file::Section;
+use crate::parsers::parser_scene_file::SceneFile;
+use crate::parsers::parser_value::unescape;
+
+pub use edit::SceneEditError;
+pub use extract::{BoundaryReference, ExtractedBranch};
+pub use groups::node_groups;
+pub use node_paths::{ExportedNodePath, node_path_properties};
+pub use unique_names::DuplicateUniqueName;
+
+/// The path of the scene root, as used for `parent="."`
+pub const ROOT_PATH: &str = ".";
+
+/// Returns the path of a `[node]` section relative to the scene root.
+///
+/// The root node (the one without `parent`) has the path `"."`, its children have their
+/// plain name, and deeper nodes have `parent/name`, matching how Godot writes `parent=`.
+/// Escape sequences in the attributes are resolved.
+pub fn node_path(node: &Section) -> Option<String> {
+    let name = node.get("name")?;
+    Some(match node.get("parent") {
+        None => ROOT_PATH.to_string(),
+        Some(parent) => unescape(&join_path(parent, name)),
+    })
+}
+
+/// Appends a child name to a node path
+pub fn join_path(parent: &str, name: &str) -> String {
+    if parent == ROOT_PATH || parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", parent, name)
+    }
+}
+
+/// Returns the parent of a node path, or `None` for the scene root
+pub fn parent_path(path: &str) -> Option<&str> {
+    if path == ROOT_PATH {
+        return None;
+    }
+    Some(
+        path.rsplit_once('/')
+            .map_or(ROOT_PATH, |(parent, _)| parent),
+    )
+}
+
+impl SceneFile<'_> {
+    /// Returns the `[node]` section with the given path relative to the scene root
+    pub fn find_node(&self, path: &str) -> Option<&Section<'_>> {
+        self.nodes()
+            .into_iter()
+            .find(|node| node_path(node).is_some_and(|p| p == path))
+    }
+
+    /// Returns every node keyed by its path relative to the scene root
+    pub fn nodes_by_path(&self) -> BTreeMap<String, &Section<'_>> {
+        self.nodes()
+            .into_iter()
+            .filter_map(|node| Some((node_path(node)?, node)))
+            .collect()
+    }
+
+    /// Resolves a node path written relative to the node at `from`.
+    ///
+    /// Supports `.`, `..`, plain child names and `%Name` segments. A `%Name` is looked up
+    /// among the unique names owned by the node reached so far, which only the scene root
+    /// and instanced nodes have, then among those of its owner. A `:property` subpath is
+    /// ignored. Returns the resolved path relative to the scene root if it names a node in this
+    /// scene, or a node inside an instanced scene whose contents are not part of this file.
+    /// Absolute paths (starting with `/`) depend on the running scene tree and never resolve.
+    ///
+    /// Each call indexes the nodes of the scene; `NodePathResolver` keeps the index to
+    /// resolve many paths.
+    pub fn resolve_node_path(&self, from: &str, path: &str) -> Option<String> {
+        self.node_path_resolver().resolve(from, path)
+    }
+
+    /// Indexes the nodes and unique names of the scene to resolve node paths against
+    pub fn node_path_resolver(&self) -> NodePathResolver<'_> {
+        let nodes = self.nodes_by_path();
+        let mut unique_names: BTreeMap<String, BTreeMap<&str, String>> = BTreeMap::new();
+        for (path, node) in &nodes {
+            if !unique_names::is_unique(node) {
+                continue;
+            }
+            if let (Some(owner), Some(name)) =
+                (unique_names::owner_of(&nodes, path), node.get("name"))
+            {
+                unique_names
+                    .entry(owner)
+                    .or_default()
+                    .entry(name)
+                    .or_insert_with(|| path.clone());
+            }
+        }
+        NodePathResolver {
+            nodes,
+            unique_names,
+        }
+    }
+}
+
+/// Resolves node paths in a scene, see `SceneFile::resolve_node_path`
+#[derive(Debug, Clone)]
+pub struct NodePathResolver<'a> {
+    nodes: BTreeMap<String, &'a Section<'a>>,
+    /// Unique names mapped to their node paths, by the path of their owner. If a name is
+    /// declared more than once, the first node in path order wins.
+    unique_names: BTreeMap<String, BTreeMap<&'a str, String>>,
+}
+
+impl NodePathResolver<'_> {
+    /// Resolves a node path written relative to the node at `from`
+    pub fn resolve(&self, from: &str, path: &str) -> Option<String> {
+        if path.starts_with('/') {
+            return None;
+        }
+        let path = path.split_once(':').map_or(path, |(nodes, _)| nodes);
+
+        let mut current = from.to_string();
+        for segment in path.split('/') {
+            match segment {
+                "" | "." => {}
+                ".." => current = parent_path(&current)?.to_string(),
+                _ => match segment.strip_prefix('%') {
+                    Some(name) => current = self.unique_node(&current, name)?.to_string(),
+                    None => current = join_path(&current, segment),
+                },
+            }
+        }
+
+        node_exists(&self.nodes, &current).then_some(current)
+    }
+
+    /// Looks up a unique name from the node at `path`, first among the names the node owns
+    /// and then among those of its owner
+    fn unique_node(&self, path: &str, name: &str) -> Option<&str> {
+        let owner = unique_names::owner_of(&self.nodes, path);
+        [Some(path), owner.as_deref()]
+            .into_iter()
+            .flatten()
+            .find_map(|scope| self.unique_names.get(scope)?.get(name))
+            .map(String::as_str)
+    }
+}
+
+/// Returns true if the node instances another scene
+fn is_instance(node: &Section) -> bool {
+    node.get("instance").is_some() || node.get("instance_placeholder").is_some()
+}
+
+/// Returns true if `path` is declared in the scene, or lies below an instanced node whose
+/// children are defined in another file
+fn node_exists(nodes: &BTreeMap<String, &Section>, path: &str) -> bool {
+    if nodes.contains_key(path) {
+        return true;
+    }
+    let mut ancestor = parent_path(path);
+    while let Some(current) = ancestor {
+        if nodes.get(current).is_some_and(|node| is_instance(node)) {
+            return true;
+        }
+        ancestor = parent_path(current);
+    }
+    false
+}
@@ -0,0 +1,77 @@
+use super::node_path;
+use crate::parsers::parser_property_file::Section;
+use crate::parsers::parser_scene_file::SceneFile;
+use crate::parsers::parser_value::Value;
+
+/// A `NodePath` held by an exported property listed in a node's `node_paths` attribute
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportedNodePath {
+    /// Path of the node declaring the property, relative to the scene root
+    pub node: String,
+    /// Name of the property holding the node path
+    pub property: String,
+    /// The node path as written in the scene
+    pub path: String,
+    /// The target relative to the scene root, or `None` if it does not resolve
+    pub resolved: Option<String>,
+}
+
+/// Returns the property names listed in a node's `node_paths` attribute, e.g.
+/// `node_paths=PackedStringArray("room", "ui")`
+pub fn node_path_properties(node: &Section) -> Vec<String> {
+    node.get("node_paths")
+        .and_then(Value::parse)
+        .and_then(|value| {
+            value
+                .as_string_array()
+                .map(|names| names.into_iter().map(str::to_string).collect())
+        })
+        .unwrap_or_default()
+}
+
+impl SceneFile<'_> {
+    /// Resolves every node path held by the properties listed in `node_paths` attributes.
+    ///
+    /// Each `NodePath("...")` is resolved relative to the node that declares it, see
+    /// `resolve_node_path`. Properties holding arrays of node paths yield one entry per path.
+    /// Empty node paths (an unassigned export) are skipped.
+    pub fn exported_node_paths(&self) -> Vec<ExportedNodePath> {
+        let resolver = self.node_path_resolver();
+        let mut exported = Vec::new();
+
+        for node in self.nodes() {
+            let Some(from) = node_path(node) else {
+                continue;
+            };
+
+            for property in node_path_properties(node) {
+                let Some(value) = node.get(&property).and_then(Value::parse) else {
+                    continue;
+                };
+
+                for path in value.node_paths() {
+                    if path.is_empty() {
+                        continue;
+                    }
+                    exported.push(ExportedNodePath {
+                        node: from.clone(),
+                        property: property.clone(),
+                        path: path.to_string(),
+                        resolved: resolver.resolve(&from, path),
+                    });
+                }
+            }
+        }
+
+        exported
+    }
+
+    /// Returns the exported node paths that do not resolve to a node in the scene tree,
+    /// typically left behind after a node was renamed or moved
+    pub fn broken_node_paths(&self) -> Vec<ExportedNodePath> {
+        self.exported_node_paths()
+            .into_iter()
+            .filter(|exported| exported.resolved.is_none())
+            .collect()
+    }
+}
@@ -0,0 +1,277 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::edit::set_attribute;
+use crate::parsers::parser_property::UntypedProperty;
+use crate::parsers::parser_property_file::Section;
+use crate::parsers::parser_scene_file::SceneFile;
+use crate::parsers::parser_value::Value;
+
+/// Whether an id names an `[ext_resource]` or a `[sub_resource]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum ResourceKind {
+    Ext,
+    Sub,
+}
+
+impl ResourceKind {
+    pub(crate) fn of(section: &Section) -> Option<ResourceKind> {
+        match section.header_type.as_ref() {
+            "ext_resource" => Some(ResourceKind::Ext),
+            "sub_resource" => Some(ResourceKind::Sub),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the `ExtResource(...)` and `SubResource(...)` ids a section refers to
+pub(crate) fn resource_refs(section: &Section) -> Vec<(ResourceKind, String)> {
+    let mut refs = Vec::new();
+    for property in &section.properties {
+        if property.quoted {
+            continue;
+        }
+        if let Some(value) = Value::parse(&property.value) {
+            collect_resource_refs(&value, &mut refs);
+        }
+    }
+    refs
+}
+
+fn collect_resource_refs(value: &Value, refs: &mut Vec<(ResourceKind, String)>) {
+    value.visit(&mut |value| {
+        if let Some(id) = value.as_ext_resource() {
+            refs.push((ResourceKind::Ext, id.to_string()));
+        } else if let Some(id) = value.as_sub_resource() {
+            refs.push((ResourceKind::Sub, id.to_string()));
+        } else if let Value::Constructor { name, .. } = value
+            && let Some((_, parameters)) = type_parameters(name)
+        {
+            collect_resource_refs(&parameters, refs);
+        }
+    });
+}
+
+/// Parses the type parameters of a constructor name such as
+/// `Array[ExtResource("1_item")]`, returning where they start and the parameters as an array
+fn type_parameters(name: &str) -> Option<(usize, Value)> {
+    let start = name.find('[')?;
+    Some((start, Value::parse(&name[start..])?))
+}
+
+/// Rewrites the resource references of a section. `rename` returns the new id of a
+/// reference, or `None` to keep it. Properties are only rewritten if an id changed.
+pub(crate) fn rename_resource_refs(
+    section: &mut Section,
+    rename: &impl Fn(ResourceKind, &str) -> Option<String>,
+) {
+    for property in &mut section.properties {
+        if property.quoted {
+            continue;
+        }
+        let Some(mut value) = Value::parse(&property.value) else {
+            continue;
+        };
+        if rename_refs_in_value(&mut value, rename) {
+            property.value = Cow::Owned(value.to_string());
+        }
+    }
+}
+
+/// Renames the references in a value, returning whether any changed
+fn rename_refs_in_value(
+    value: &mut Value,
+    rename: &impl Fn(ResourceKind, &str) -> Option<String>,
+) -> bool {
+    let mut changed = false;
+    value.visit_mut(&mut |value| {
+        let Value::Constructor { name, args } = value else {
+            return;
+        };
+        let kind = match name.as_str() {
+            "ExtResource" => ResourceKind::Ext,
+            "SubResource" => ResourceKind::Sub,
+            _ => {
+                if let Some((start, mut parameters)) = type_parameters(name)
+                    && rename_refs_in_value(&mut parameters, rename)
+                {
+                    *name = format!("{}{}", &name[..start], parameters);
+                    changed = true;
+                }
+                return;
+            }
+        };
+        if let [Value::String(id)] = args.as_mut_slice()
+            && let Some(new_id) = rename(kind, id)
+        {
+            *id = new_id;
+            changed = true;
+        }
+    });
+    changed
+}
+
+/// Returns `id` with its leading number replaced by `index`, e.g. `7_k2x4p` becomes `2_k2x4p`.
+/// Ids without a leading number, such as `RectangleShape2D_x7k2p`, are returned unchanged.
+pub(crate) fn renumber_id(id: &str, index: usize) -> String {
+    let digits = id.len() - id.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let rest = &id[digits..];
+    if digits == 0 || !(rest.is_empty() || rest.starts_with('_')) {
+        return id.to_string();
+    }
+    format!("{}{}", index, rest)
+}
+
+/// Returns the leading number of an id such as `3_k2x4p`
+pub(crate) fn id_number(id: &str) -> Option<usize> {
+    id.split('_').next()?.parse().ok()
+}
+
+/// Returns a five character suffix derived from `seed`, in the style of the random suffixes
+/// Godot 4 appends to resource ids
+pub(crate) fn id_suffix(seed: &str) -> String {
+    const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+    // FNV-1a, so the same input always produces the same ids
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in seed.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    (0..5)
+        .map(|i| ALPHABET[((hash >> (i * 8)) % ALPHABET.len() as u64) as usize] as char)
+        .collect()
+}
+
+impl SceneFile<'_> {
+    /// Returns the resources that `sections` refer to, directly or through sub-resources
+    pub(crate) fn resources_used_by(
+        &self,
+        sections: &[&Section],
+    ) -> BTreeSet<(ResourceKind, String)> {
+        let mut used = BTreeSet::new();
+        let mut pending: Vec<_> = sections.iter().flat_map(|s| resource_refs(s)).collect();
+        while let Some(reference) = pending.pop() {
+            if !used.insert(reference.clone()) || reference.0 != ResourceKind::Sub {
+                continue;
+            }
+            if let Some(sub) = self.resource_section(ResourceKind::Sub, &reference.1) {
+                pending.extend(resource_refs(sub));
+            }
+        }
+        used
+    }
+
+    /// Sets `load_steps` in the `gd_scene` or `gd_resource` header to the number of
+    /// `[ext_resource]` and `[sub_resource]` sections plus one, as Godot writes it.
+    ///
+    /// A missing `load_steps` is added before `format`, and it is removed when the file has no
+    /// resources, since Godot omits it then.
+    pub fn update_load_steps(&mut self) {
+        let count = self
+            .all_sections
+            .iter()
+            .filter(|s| ResourceKind::of(s).is_some())
+            .count();
+        let Some(header) = self
+            .all_sections
+            .iter_mut()
+            .rfind(|s| matches!(s.header_type.as_ref(), "gd_scene" | "gd_resource"))
+        else {
+            return;
+        };
+
+        let attributes = header.attributes();
+        match attributes.iter().position(|p| p.key == "load_steps") {
+            Some(index) if count == 0 => {
+                header.properties.remove(index);
+                header.attribute_count -= 1;
+            }
+            Some(index) => {
+                header.properties[index].value = Cow::Owned((count + 1).to_string());
+            }
+            None if count > 0 => {
+                let index = attributes
+                    .iter()
+                    .position(|p| p.key == "format")
+                    .unwrap_or(attributes.len());
+                let load_steps = UntypedProperty::new("load_steps", (count + 1).to_string());
+                header.properties.insert(index, load_steps);
+                header.attribute_count += 1;
+            }
+            None => {}
+        }
+    }
+
+    /// Renumbers resource ids the way Godot 4 writes them and rewrites every
+    /// `ExtResource(...)` and `SubResource(...)` reference to match.
+    ///
+    /// External resources are numbered from 1 in file order as `N_xxxxx`, keeping the
+    /// existing suffix where there is one. Sub-resources keep their `Type_xxxxx` ids; repeated
+    /// or purely numeric ids are replaced with new unique ones. Where an id was declared
+    /// more than once, each reference is resolved to the latest declaration before it, as
+    /// Godot does when loading the file.
+    pub fn renumber_resources(&mut self) {
+        let mut ext_count = 0;
+        let mut sub_ids = BTreeSet::new();
+        let mut new_ids = Vec::with_capacity(self.all_sections.len());
+        let mut last_declared = BTreeMap::new();
+        for section in &self.all_sections {
+            let Some(kind) = ResourceKind::of(section) else {
+                new_ids.push(None);
+                continue;
+            };
+            let id = section.get("id").unwrap_or_default();
+            let new_id = match kind {
+                ResourceKind::Ext => {
+                    ext_count += 1;
+                    let suffix = match id.split_once('_') {
+                        Some((number, suffix)) if id_number(number).is_some() => suffix.to_string(),
+                        _ => id_suffix(section.get("path").unwrap_or(id)),
+                    };
+                    format!("{}_{}", ext_count, suffix)
+                }
+                ResourceKind::Sub if !sub_ids.contains(id) && id_number(id).is_none() => {
+                    id.to_string()
+                }
+                ResourceKind::Sub => {
+                    let resource_type = section.get("type").unwrap_or("Resource");
+                    let mut seed = format!("{}/{}", resource_type, id);
+                    loop {
+                        let new_id = format!("{}_{}", resource_type, id_suffix(&seed));
+                        if !sub_ids.contains(&new_id) {
+                            break new_id;
+                        }
+                        seed.push('/');
+                    }
+                }
+            };
+            if kind == ResourceKind::Sub {
+                sub_ids.insert(new_id.clone());
+            }
+            last_declared.insert((kind, id.to_string()), new_id.clone());
+            new_ids.push(Some(new_id));
+        }
+
+        // References declared later in the file, such as a sub-resource used before its
+        // section, fall back to the last declaration
+        let mut declared = BTreeMap::new();
+        for (section, new_id) in self.all_sections.iter_mut().zip(new_ids) {
+            rename_resource_refs(section, &|kind, id| {
+                let key = (kind, id.to_string());
+                declared.get(&key).or(last_declared.get(&key)).cloned()
+            });
+            if let (Some(kind), Some(new_id)) = (ResourceKind::of(section), new_id) {
+                let id = section.get("id").unwrap_or_default().to_string();
+                set_attribute(section, "id", &new_id);
+                declared.insert((kind, id), new_id);
+            }
+        }
+    }
+
+    /// Returns the `[ext_resource]` or `[sub_resource]` section with the given id
+    pub(crate) fn resource_section(&self, kind: ResourceKind, id: &str) -> Option<&Section<'_>> {
+        self.all_sections
+            .iter()
+            .find(|s| ResourceKind::of(s) == Some(kind) && s.get("id") == Some(id))
+    }
+}
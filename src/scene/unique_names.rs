@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+
+use super::{ROOT_PATH, is_instance, node_path, parent_path};
+use crate::parsers::parser_property_file::Section;
+use crate::parsers::parser_scene_file::SceneFile;
+
+/// A unique name (`%Name`) declared by more than one node with the same owner, which Godot
+/// rejects at runtime
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateUniqueName {
+    /// Path of the owner node whose scope contains the name more than once
+    pub owner: String,
+    pub name: String,
+    /// Paths of the nodes sharing the name, in file order
+    pub paths: Vec<String>,
+}
+
+impl SceneFile<'_> {
+    /// Returns the unique names owned by the scene root, mapped to their node paths.
+    ///
+    /// A node is unique when it sets `unique_name_in_owner = true`. If a name is declared
+    /// more than once, the first node in path order wins; see `duplicate_unique_names`.
+    pub fn unique_names(&self) -> BTreeMap<String, String> {
+        let nodes = self.nodes_by_path();
+        unique_names_in(&nodes, ROOT_PATH)
+            .into_iter()
+            .map(|(name, path)| (name.to_string(), path.to_string()))
+            .collect()
+    }
+
+    /// Returns every unique name that more than one node declares within the same owner
+    pub fn duplicate_unique_names(&self) -> Vec<DuplicateUniqueName> {
+        let nodes = self.nodes_by_path();
+        let mut duplicates: Vec<DuplicateUniqueName> = Vec::new();
+
+        for node in self.nodes().into_iter().filter(|node| is_unique(node)) {
+            let (Some(path), Some(name)) = (node_path(node), node.get("name")) else {
+                continue;
+            };
+            let Some(owner) = owner_of(&nodes, &path) else {
+                continue;
+            };
+
+            match duplicates
+                .iter_mut()
+                .find(|d| d.owner == owner && d.name == name)
+            {
+                Some(duplicate) => duplicate.paths.push(path),
+                None => duplicates.push(DuplicateUniqueName {
+                    owner,
+                    name: name.to_string(),
+                    paths: vec![path],
+                }),
+            }
+        }
+
+        duplicates.retain(|d| d.paths.len() > 1);
+        duplicates
+    }
+}
+
+pub(crate) fn is_unique(node: &Section) -> bool {
+    node.get("unique_name_in_owner") == Some("true")
+}
+
+/// Returns the owner of the node at `path`.
+///
+/// Nodes declared with a `type` or `instance` are owned by the scene root. Nodes without
+/// either only override properties of a node inside an instanced scene, so they are owned
+/// by the nearest instanced ancestor. The scene root has no owner.
+pub(crate) fn owner_of(nodes: &BTreeMap<String, &Section>, path: &str) -> Option<String> {
+    if path == ROOT_PATH {
+        return None;
+    }
+    if nodes
+        .get(path)
+        .is_some_and(|node| node.get("type").is_some() || is_instance(node))
+    {
+        return Some(ROOT_PATH.to_string());
+    }
+
+    let mut ancestor = parent_path(path);
+    while let Some(current) = ancestor {
+        if nodes.get(current).is_some_and(|node| is_instance(node)) {
+            return Some(current.to_string());
+        }
+        ancestor = parent_path(current);
+    }
+    Some(ROOT_PATH.to_string())
+}
+
+/// Returns the unique names owned by `owner`, mapped to their node paths
+pub(crate) fn unique_names_in<'a>(
+    nodes: &'a BTreeMap<String, &'a Section>,
+    owner: &str,
+) -> BTreeMap<&'a str, &'a str> {
+    let mut names = BTreeMap::new();
+    for (path, node) in nodes {
+        if !is_unique(node) || owner_of(nodes, path).as_deref() != Some(owner) {
+            continue;
+        }
+        if let Some(name) = node.get("name") {
+            names.entry(name).or_insert(path.as_str());
+        }
+    }
+    names
+}
@@ -32,7 +32,7 @@ fn test_has_application_section() {
     let (_, project) = parse_project_file(&cleaned).unwrap();
 
     assert!(
-        project.application.is_some(),
+        project.application().is_some(),
         "Project should have application section"
     );
 }
@@ -43,7 +43,7 @@ fn test_has_autoload_section() {
     let (_, project) = parse_project_file(&cleaned).unwrap();
 
     assert!(
-        project.autoload.is_some(),
+        project.autoload().is_some(),
         "Project should have autoload section"
     );
 }
@@ -53,7 +53,10 @@ fn test_has_input_section() {
     let cleaned = strip_comments_and_preamble(PROJECT_CONTENT);
     let (_, project) = parse_project_file(&cleaned).unwrap();
 
-    assert!(project.input.is_some(), "Project should have input section");
+    assert!(
+        project.input().is_some(),
+        "Project should have input section"
+    );
 }
 
 #[test]
@@ -62,7 +65,7 @@ fn test_has_rendering_section() {
     let (_, project) = parse_project_file(&cleaned).unwrap();
 
     assert!(
-        project.rendering.is_some(),
+        project.rendering().is_some(),
         "Project should have rendering section"
     );
 }
@@ -73,7 +76,7 @@ fn test_has_display_section() {
     let (_, project) = parse_project_file(&cleaned).unwrap();
 
     assert!(
-        project.display.is_some(),
+        project.display().is_some(),
         "Project should have display section"
     );
 }
@@ -93,18 +96,18 @@ fn test_all_sections_categorized() {
 
     // Count categorized sections
     let categorized = [
-        project.application.is_some() as usize,
-        project.audio.is_some() as usize,
-        project.autoload.is_some() as usize,
-        project.debug.is_some() as usize,
-        project.display.is_some() as usize,
-        project.editor_plugins.is_some() as usize,
-        project.input.is_some() as usize,
-        project.input_devices.is_some() as usize,
-        project.internationalization.is_some() as usize,
-        project.layer_names.is_some() as usize,
-        project.physics.is_some() as usize,
-        project.rendering.is_some() as usize,
+        project.application().is_some() as usize,
+        project.audio().is_some() as usize,
+        project.autoload().is_some() as usize,
+        project.debug().is_some() as usize,
+        project.display().is_some() as usize,
+        project.editor_plugins().is_some() as usize,
+        project.input().is_some() as usize,
+        project.input_devices().is_some() as usize,
+        project.internationalization().is_some() as usize,
+        project.layer_names().is_some() as usize,
+        project.physics().is_some() as usize,
+        project.rendering().is_some() as usize,
     ]
     .iter()
     .sum::<usize>();
@@ -185,3 +188,88 @@ fn test_preamble_not_in_sections() {
         );
     }
 }
+
+#[test]
+fn test_has_extended_standard_sections() {
+    let (_, project) = parse_project_file(PROJECT_CONTENT).unwrap();
+
+    assert!(
+        project.addons().is_some(),
+        "Project should have addons section"
+    );
+    assert!(
+        project.dotnet().is_some(),
+        "Project should have dotnet section"
+    );
+    assert!(
+        project.global_group().is_some(),
+        "Project should have global_group section"
+    );
+    assert!(project.gui().is_some(), "Project should have gui section");
+    assert!(project.editor().is_none());
+    assert!(project.xr().is_none());
+}
+
+#[test]
+fn test_section_lookup_by_name() {
+    let (_, project) = parse_project_file(PROJECT_CONTENT).unwrap();
+
+    let gui = project.section("gui").expect("Should find gui section");
+    assert_eq!(gui.get("theme/custom"), Some("uid://bmceb367p3pue"));
+
+    let gdunit = project
+        .section("gdunit4")
+        .expect("Should find gdunit4 section");
+    assert_eq!(gdunit.get("report/godot/push_error"), Some("true"));
+
+    assert!(project.section("missing").is_none());
+}
+
+#[test]
+fn test_custom_sections() {
+    let (_, project) = parse_project_file(PROJECT_CONTENT).unwrap();
+
+    let custom: Vec<&str> = project
+        .custom_sections()
+        .iter()
+        .map(|s| s.header_type.as_ref())
+        .collect();
+    assert_eq!(custom, vec!["gdunit4", "steam"]);
+}
+
+#[test]
+fn test_every_section_is_categorized_or_custom() {
+    let (_, project) = parse_project_file(PROJECT_CONTENT).unwrap();
+
+    let categorized = [
+        &project.addons(),
+        &project.application(),
+        &project.audio(),
+        &project.autoload(),
+        &project.debug(),
+        &project.display(),
+        &project.dotnet(),
+        &project.editor(),
+        &project.editor_plugins(),
+        &project.filesystem(),
+        &project.global_group(),
+        &project.gui(),
+        &project.input(),
+        &project.input_devices(),
+        &project.internationalization(),
+        &project.layer_names(),
+        &project.navigation(),
+        &project.physics(),
+        &project.rendering(),
+        &project.shader_globals(),
+        &project.xr(),
+    ]
+    .iter()
+    .filter(|s| s.is_some())
+    .count();
+
+    assert_eq!(
+        categorized + project.custom_sections().len(),
+        project.all_sections.len()
+    );
+}
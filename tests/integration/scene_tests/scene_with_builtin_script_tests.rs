@@ -130,14 +130,14 @@ fn test_sub_resource_has_gdscript_type() {
 
     let (_, scene) = parse_scene_file(SCENE_CONTENT).unwrap();
 
-    assert_eq!(scene.sub_resources.len(), 1, "Should have 1 sub_resource");
+    assert_eq!(scene.sub_resources().len(), 1, "Should have 1 sub_resource");
 
-    let sub_resource = &scene.sub_resources[0];
+    let sub_resource = &scene.sub_resources()[0];
     let resource_name = sub_resource
         .properties
         .iter()
         .find(|p| p.key == "resource_name")
-        .map(|p| p.value.as_str());
+        .map(|p| p.value.as_ref());
 
     assert_eq!(
         resource_name,
@@ -152,7 +152,7 @@ fn test_builtin_script_has_script_source_property() {
 
     let (_, scene) = parse_scene_file(SCENE_CONTENT).unwrap();
 
-    let sub_resource = &scene.sub_resources[0];
+    let sub_resource = &scene.sub_resources()[0];
 
     // Verify basic sub_resource properties
     assert_eq!(sub_resource.header_type, "sub_resource");
@@ -213,7 +213,7 @@ fn test_builtin_script_contains_expected_code() {
     use godot_properties_parser::parse_scene_file;
 
     let (_, scene) = parse_scene_file(SCENE_CONTENT).unwrap();
-    let sub_resource = &scene.sub_resources[0];
+    let sub_resource = &scene.sub_resources()[0];
 
     let script_source = sub_resource
         .properties
@@ -157,7 +157,7 @@ impl<'a> SceneTestSuite<'a> {
     pub fn test_scene_has_header(&self) {
         let (_, scene) = parse_scene_file(self.content).unwrap();
         assert!(
-            scene.header.is_some(),
+            scene.header().is_some(),
             "[{}] Scene should have a header",
             self.expectations.name
         );
@@ -166,7 +166,7 @@ impl<'a> SceneTestSuite<'a> {
     pub fn test_ext_resources_count(&self) {
         let (_, scene) = parse_scene_file(self.content).unwrap();
         assert_eq!(
-            scene.ext_resources.len(),
+            scene.ext_resources().len(),
             self.expectations.ext_resources,
             "[{}] Expected {} external resources",
             self.expectations.name,
@@ -177,7 +177,7 @@ impl<'a> SceneTestSuite<'a> {
     pub fn test_sub_resources_count(&self) {
         let (_, scene) = parse_scene_file(self.content).unwrap();
         assert_eq!(
-            scene.sub_resources.len(),
+            scene.sub_resources().len(),
             self.expectations.sub_resources,
             "[{}] Expected {} sub-resources",
             self.expectations.name,
@@ -188,7 +188,7 @@ impl<'a> SceneTestSuite<'a> {
     pub fn test_nodes_count(&self) {
         let (_, scene) = parse_scene_file(self.content).unwrap();
         assert_eq!(
-            scene.nodes.len(),
+            scene.nodes().len(),
             self.expectations.nodes,
             "[{}] Expected {} nodes",
             self.expectations.name,
@@ -199,7 +199,7 @@ impl<'a> SceneTestSuite<'a> {
     pub fn test_connections_count(&self) {
         let (_, scene) = parse_scene_file(self.content).unwrap();
         assert_eq!(
-            scene.connections.len(),
+            scene.connections().len(),
             self.expectations.connections,
             "[{}] Expected {} connections",
             self.expectations.name,
@@ -210,7 +210,7 @@ impl<'a> SceneTestSuite<'a> {
     pub fn test_editables_count(&self) {
         let (_, scene) = parse_scene_file(self.content).unwrap();
         assert_eq!(
-            scene.editables.len(),
+            scene.editables().len(),
             self.expectations.editables,
             "[{}] Expected {} editables",
             self.expectations.name,
@@ -222,7 +222,7 @@ impl<'a> SceneTestSuite<'a> {
     pub fn test_all_ext_resources_have_required_properties(&self) {
         let (_, scene) = parse_scene_file(self.content).unwrap();
 
-        for (i, ext_res) in scene.ext_resources.iter().enumerate() {
+        for (i, ext_res) in scene.ext_resources().iter().enumerate() {
             let has_type = ext_res.properties.iter().any(|p| p.key == "type");
             let has_path = ext_res.properties.iter().any(|p| p.key == "path");
 
@@ -242,7 +242,7 @@ impl<'a> SceneTestSuite<'a> {
     pub fn test_ext_resource_paths_are_quoted(&self) {
         let (_, scene) = parse_scene_file(self.content).unwrap();
 
-        for ext_res in &scene.ext_resources {
+        for ext_res in &scene.ext_resources() {
             if let Some(path) = ext_res.properties.iter().find(|p| p.key == "path") {
                 // Values are now parsed without quotes
                 assert!(
@@ -258,7 +258,7 @@ impl<'a> SceneTestSuite<'a> {
     pub fn test_all_sub_resources_have_required_properties(&self) {
         let (_, scene) = parse_scene_file(self.content).unwrap();
 
-        for (i, sub_res) in scene.sub_resources.iter().enumerate() {
+        for (i, sub_res) in scene.sub_resources().iter().enumerate() {
             let has_type = sub_res.properties.iter().any(|p| p.key == "type");
             let has_id = sub_res.properties.iter().any(|p| p.key == "id");
 
@@ -280,7 +280,7 @@ impl<'a> SceneTestSuite<'a> {
 
         let mut ids = std::collections::HashSet::new();
 
-        for sub_res in &scene.sub_resources {
+        for sub_res in &scene.sub_resources() {
             if let Some(id_prop) = sub_res.properties.iter().find(|p| p.key == "id") {
                 assert!(
                     ids.insert(id_prop.value.clone()),
@@ -295,7 +295,7 @@ impl<'a> SceneTestSuite<'a> {
     pub fn test_all_nodes_have_name(&self) {
         let (_, scene) = parse_scene_file(self.content).unwrap();
 
-        for (i, node) in scene.nodes.iter().enumerate() {
+        for (i, node) in scene.nodes().iter().enumerate() {
             let has_name = node.properties.iter().any(|p| p.key == "name");
             assert!(
                 has_name,
@@ -308,7 +308,7 @@ impl<'a> SceneTestSuite<'a> {
     pub fn test_node_names_are_quoted(&self) {
         let (_, scene) = parse_scene_file(self.content).unwrap();
 
-        for node in &scene.nodes {
+        for node in &scene.nodes() {
             if let Some(name) = node.properties.iter().find(|p| p.key == "name") {
                 // Values are now parsed without quotes
                 assert!(
@@ -324,7 +324,7 @@ impl<'a> SceneTestSuite<'a> {
     pub fn test_nodes_have_type_or_instance(&self) {
         let (_, scene) = parse_scene_file(self.content).unwrap();
 
-        for (i, node) in scene.nodes.iter().enumerate() {
+        for (i, node) in scene.nodes().iter().enumerate() {
             let has_type = node.properties.iter().any(|p| p.key == "type");
             let has_instance = node.properties.iter().any(|p| p.key == "instance");
 
@@ -340,7 +340,7 @@ impl<'a> SceneTestSuite<'a> {
     pub fn test_all_connections_have_required_properties(&self) {
         let (_, scene) = parse_scene_file(self.content).unwrap();
 
-        for (i, conn) in scene.connections.iter().enumerate() {
+        for (i, conn) in scene.connections().iter().enumerate() {
             let has_signal = conn.properties.iter().any(|p| p.key == "signal");
             let has_from = conn.properties.iter().any(|p| p.key == "from");
             let has_to = conn.properties.iter().any(|p| p.key == "to");
@@ -393,12 +393,12 @@ impl<'a> SceneTestSuite<'a> {
     pub fn test_categorization_is_complete(&self) {
         let (_, scene) = parse_scene_file(self.content).unwrap();
 
-        let categorized_count = (if scene.header.is_some() { 1 } else { 0 })
-            + scene.ext_resources.len()
-            + scene.sub_resources.len()
-            + scene.nodes.len()
-            + scene.connections.len()
-            + scene.editables.len();
+        let categorized_count = (if scene.header().is_some() { 1 } else { 0 })
+            + scene.ext_resources().len()
+            + scene.sub_resources().len()
+            + scene.nodes().len()
+            + scene.connections().len()
+            + scene.editables().len();
 
         assert_eq!(
             categorized_count,
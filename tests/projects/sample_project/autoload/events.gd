@@ -0,0 +1,3 @@
+extends Node
+
+signal player_died
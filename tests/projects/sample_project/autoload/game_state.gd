@@ -0,0 +1,3 @@
+extends Node
+
+var score := 0
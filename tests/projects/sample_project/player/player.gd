@@ -0,0 +1,3 @@
+extends CharacterBody2D
+
+@export var weapon: Resource
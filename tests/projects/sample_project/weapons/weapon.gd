@@ -0,0 +1,4 @@
+class_name Weapon
+extends Resource
+
+@export var damage := 1
@@ -0,0 +1,134 @@
+use godot_properties_parser::project::{
+    BrokenReference, BrokenReferenceKind, GodotProject, find_broken_references,
+};
+use godot_properties_parser::{parse_project_file, parse_scene_file};
+
+const BROKEN_PROJECT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/projects/broken_project");
+const SAMPLE_PROJECT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/projects/sample_project");
+
+fn broken_references() -> Vec<BrokenReference> {
+    let project = GodotProject::open(BROKEN_PROJECT).unwrap();
+    find_broken_references(&project)
+}
+
+#[test]
+fn test_sample_project_has_no_broken_references() {
+    let project = GodotProject::open(SAMPLE_PROJECT).unwrap();
+    assert_eq!(find_broken_references(&project), vec![]);
+}
+
+#[test]
+fn test_project_built_in_memory() {
+    let (_, project_file) = parse_project_file(
+        "config_version=5\n\n[application]\n\nrun/main_scene=\"res://main.tscn\"\n",
+    )
+    .unwrap();
+    let mut project = GodotProject::new("/work/game", project_file.into_owned());
+    let (_, hud) = parse_scene_file(
+        "[gd_scene format=3 uid=\"uid://b7hud\"]\n\n[node name=\"Hud\" type=\"Control\"]\n",
+    )
+    .unwrap();
+    let (_, main) = parse_scene_file(
+        "[gd_scene format=3]\n\n\
+         [ext_resource type=\"PackedScene\" uid=\"uid://b7hud\" path=\"res://ui/old_hud.tscn\" id=\"1_hud\"]\n\n\
+         [node name=\"Main\" type=\"Node\"]\n\n\
+         [node name=\"Hud\" parent=\".\" instance=ExtResource(\"1_hud\")]\n",
+    )
+    .unwrap();
+    project.add_scene("res://ui/hud.tscn", hud.into_owned());
+    project.add_scene("res://main.tscn", main.into_owned());
+
+    assert_eq!(project.files, ["res://main.tscn", "res://ui/hud.tscn"]);
+    assert_eq!(
+        project.resolve_reference("uid://b7hud"),
+        Some("res://ui/hud.tscn")
+    );
+    // The uid still finds the moved scene, only the stale path is reported
+    let broken = find_broken_references(&project);
+    assert_eq!(broken.len(), 1);
+    assert_eq!(
+        broken[0].kind,
+        BrokenReferenceKind::MissingPath {
+            path: "res://ui/old_hud.tscn".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_missing_main_scene() {
+    let broken = broken_references();
+    let finding = broken
+        .iter()
+        .find(|b| matches!(b.kind, BrokenReferenceKind::MissingMainScene { .. }))
+        .unwrap();
+
+    assert_eq!(finding.file, "res://project.godot");
+    assert_eq!(finding.line, 6);
+}
+
+#[test]
+fn test_missing_autoload() {
+    let broken = broken_references();
+    let autoloads: Vec<_> = broken
+        .iter()
+        .filter(|b| matches!(b.kind, BrokenReferenceKind::MissingAutoload { .. }))
+        .collect();
+
+    assert_eq!(autoloads.len(), 1);
+    assert_eq!(autoloads[0].line, 10);
+    assert_eq!(
+        autoloads[0].kind,
+        BrokenReferenceKind::MissingAutoload {
+            name: "Audio".to_string(),
+            reference: "res://autoload/audio.gd".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_moved_file_with_valid_uid_reports_path_only() {
+    let broken = broken_references();
+    let hud: Vec<_> = broken.iter().filter(|b| b.line == 3).collect();
+
+    assert_eq!(hud.len(), 1);
+    assert_eq!(hud[0].file, "res://scenes/level.tscn");
+    assert_eq!(
+        hud[0].kind,
+        BrokenReferenceKind::MissingPath {
+            path: "res://ui/hud.gd".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_deleted_file_reports_path_and_uid() {
+    let broken = broken_references();
+    let enemy: Vec<_> = broken
+        .iter()
+        .filter(|b| b.file == "res://scenes/level.tscn" && b.line == 4)
+        .map(|b| &b.kind)
+        .collect();
+
+    assert_eq!(
+        enemy,
+        vec![
+            &BrokenReferenceKind::MissingPath {
+                path: "res://scenes/enemy.tscn".to_string()
+            },
+            &BrokenReferenceKind::UnknownUid {
+                uid: "uid://b9gone0scene".to_string()
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_display_includes_location() {
+    let broken = broken_references();
+    let rendered: Vec<String> = broken.iter().map(|b| b.to_string()).collect();
+
+    assert!(rendered.contains(
+        &"res://scenes/level.tscn:4: ext_resource uid \"uid://b9gone0scene\" does not match any file"
+            .to_string()
+    ));
+}
@@ -0,0 +1,324 @@
+#![cfg(feature = "cli")]
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+const SAMPLE_PROJECT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/projects/sample_project");
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_godot-props"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn sample(path: &str) -> String {
+    format!("{}/{}", SAMPLE_PROJECT, path)
+}
+
+#[test]
+fn test_get_project_setting() {
+    let output = run(&["get", SAMPLE_PROJECT, "application/config/version"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "0.2.1\n");
+
+    let output = run(&["get", &sample("project.godot"), "config_version"]);
+    assert_eq!(stdout(&output), "5\n");
+
+    let output = run(&["get", SAMPLE_PROJECT, "application/config/missing"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "");
+}
+
+#[test]
+fn test_tree() {
+    let output = run(&["tree", &sample("levels/main.tscn")]);
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "Main (Node2D)\n\
+         ├─ Player [res://player/player.tscn]\n\
+         ├─ Logo (Sprite2D)\n\
+         └─ Spawner (Node2D)\n   \
+            └─ Drone (Node2D)\n"
+    );
+}
+
+#[test]
+fn test_deps() {
+    let output = run(&["deps", &sample("levels/main.tscn")]);
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output).lines().next(),
+        Some("ext\t1_main\tScript\tres://levels/main.gd")
+    );
+    assert_eq!(stdout(&output).lines().count(), 3);
+}
+
+#[test]
+fn test_check() {
+    let output = run(&[
+        "check",
+        &sample("project.godot"),
+        &sample("levels/main.tscn"),
+        &sample("weapons/sword.tres"),
+    ]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+
+    let path: PathBuf = std::env::temp_dir().join("godot_props_check.tscn");
+    fs::write(
+        &path,
+        "[gd_scene format=3]\n\n\
+         [node name=\"Main\" type=\"Node\" node_paths=PackedStringArray(\"target\")]\n\
+         target = NodePath(\"Missing\")\n\n\
+         [node name=\"Broken\"\n",
+    )
+    .unwrap();
+    let output = run(&["check", path.to_str().unwrap()]);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    let report = stdout(&output);
+    let lines: Vec<_> = report.lines().collect();
+    assert_eq!(lines.len(), 2, "{}", report);
+    assert!(
+        lines[0].ends_with(
+            ":6: section header is not closed with `]`, the rest of the file is ignored"
+        )
+    );
+    assert!(lines[1].ends_with(":3: target of node . points to missing node \"Missing\""));
+}
+
+#[test]
+fn test_json() {
+    let output = run(&["json", "--compact", &sample("project.godot")]);
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(
+        json["preamble"][0],
+        serde_json::json!({"key": "config_version", "value": {"type": "int", "value": 5}})
+    );
+}
+
+#[test]
+fn test_missing_file() {
+    let output = run(&["tree", &sample("missing.tscn")]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("godot-props: "));
+}
+
+#[test]
+fn test_truncated_file() {
+    let path: PathBuf = std::env::temp_dir().join("godot_props_truncated.tscn");
+    fs::write(
+        &path,
+        "[gd_scene format=3]\n\n[node name=\"Main\" type=\"Node\"]\n\n[node name=\"Child\" parent=\".\"\n",
+    )
+    .unwrap();
+    let outputs = ["tree", "deps", "json"].map(|command| run(&[command, path.to_str().unwrap()]));
+    fs::remove_file(&path).unwrap();
+
+    for output in outputs {
+        assert_eq!(output.status.code(), Some(2));
+        assert_eq!(stdout(&output), "");
+        let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+        assert!(
+            stderr.ends_with(
+                "godot_props_truncated.tscn:5:30: section header is not closed with `]`\n"
+            ),
+            "{}",
+            stderr
+        );
+    }
+}
+
+#[test]
+fn test_merge_driver() {
+    let dir = std::env::temp_dir();
+    let files =
+        ["base", "ours", "theirs"].map(|name| dir.join(format!("godot_merge_{}.godot", name)));
+    let base = "config_version=5\n\n[application]\n\nconfig/name=\"Game\"\n";
+    fs::write(&files[0], base).unwrap();
+    fs::write(&files[1], base.replace("Game", "Our Game")).unwrap();
+    fs::write(&files[2], format!("{}config/version=\"1.0\"\n", base)).unwrap();
+
+    let merge = |files: &[PathBuf]| {
+        Command::new(env!("CARGO_BIN_EXE_godot-merge"))
+            .args(files)
+            .arg("project.godot")
+            .output()
+            .unwrap()
+    };
+    let output = merge(&files);
+    assert!(output.status.success());
+    assert_eq!(
+        fs::read_to_string(&files[1]).unwrap(),
+        "config_version=5\n\n[application]\n\nconfig/name=\"Our Game\"\nconfig/version=\"1.0\"\n"
+    );
+
+    fs::write(&files[2], base.replace("Game", "Their Game")).unwrap();
+    let output = merge(&files);
+    let merged = fs::read_to_string(&files[1]).unwrap();
+    for file in &files {
+        fs::remove_file(file).unwrap();
+    }
+    assert_eq!(output.status.code(), Some(1));
+    assert!(merged.contains("<<<<<<< ours\nconfig/name=\"Our Game\"\n=======\nconfig/name=\"Their Game\"\n>>>>>>> theirs\n"));
+
+    // Other config files without a scene header are written like a ConfigFile too
+    let files = ["base", "ours", "theirs"]
+        .map(|name| dir.join(format!("godot_merge_{}_presets.cfg", name)));
+    let base =
+        "[preset.0]\n\nname=\"Linux\"\n\n[preset.0.options]\n\nbinary_format/embed_pck=false\n";
+    fs::write(&files[0], base).unwrap();
+    fs::write(&files[1], base.replace("Linux", "Desktop")).unwrap();
+    fs::write(&files[2], base.replace("false", "true")).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_godot-merge"))
+        .args(&files)
+        .output()
+        .unwrap();
+    let merged = fs::read_to_string(&files[1]).unwrap();
+    for file in &files {
+        fs::remove_file(file).unwrap();
+    }
+    assert!(output.status.success());
+    assert_eq!(
+        merged,
+        "[preset.0]\n\nname=\"Desktop\"\n\n[preset.0.options]\n\nbinary_format/embed_pck=true\n"
+    );
+}
+
+#[test]
+fn test_diff() {
+    let output = run(&[
+        "diff",
+        &sample("levels/main.tscn"),
+        &sample("levels/main.tscn"),
+    ]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+
+    let path: PathBuf = std::env::temp_dir().join("godot_props_diff.tscn");
+    let main = fs::read_to_string(sample("levels/main.tscn")).unwrap();
+    fs::write(&path, main.replace("Vector2(64, 32)", "Vector2(0, 0)")).unwrap();
+    let output = run(&[
+        "diff",
+        "--json",
+        &sample("levels/main.tscn"),
+        path.to_str().unwrap(),
+    ]);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!([{
+            "change": "property_changed",
+            "node": "Player",
+            "key": "position",
+            "old": "Vector2(64, 32)",
+            "new": "Vector2(0, 0)",
+        }])
+    );
+}
+
+#[test]
+fn test_fmt() {
+    let output = run(&[
+        "fmt",
+        "--check",
+        &sample("project.godot"),
+        &sample("levels/main.tscn"),
+        &sample("export_presets.cfg"),
+    ]);
+    assert!(output.status.success(), "{}", stdout(&output));
+
+    let path: PathBuf = std::env::temp_dir().join("godot_props_fmt.tscn");
+    fs::write(
+        &path,
+        "[gd_scene format=3]\n[node name=\"Main\" type=\"Node\"]\nspeed=2\n",
+    )
+    .unwrap();
+    let output = run(&["fmt", "--check", path.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), format!("{}\n", path.display()));
+
+    let output = run(&["fmt", path.to_str().unwrap()]);
+    let formatted = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        formatted,
+        "[gd_scene format=3]\n\n[node name=\"Main\" type=\"Node\"]\nspeed = 2\n"
+    );
+}
+
+#[test]
+fn test_lint() {
+    let output = run(&[
+        "lint",
+        &sample("levels/main.tscn"),
+        &sample("project.godot"),
+    ]);
+    assert!(output.status.success(), "{}", stdout(&output));
+    assert_eq!(stdout(&output), "");
+
+    let path: PathBuf = std::env::temp_dir().join("godot_props_lint.tscn");
+    fs::write(
+        &path,
+        "[gd_scene format=3]\n\n[node name=\"Main\" type=\"Spatial\"]\nvisible = true\n\n\
+         [connection signal=\"ready\" from=\".\" to=\".\" method=\"\"]\n",
+    )
+    .unwrap();
+    let file = path.to_str().unwrap();
+    let output = run(&["lint", file]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        format!(
+            "{0}:3:1: warning[deprecated-node-type]: node . uses deprecated type Spatial, use Node3D instead\n\
+             {0}:4:1: info[default-value]: visible of node . is set to its default value true\n\
+             {0}:6:1: error[empty-connection]: connection has no method\n",
+            path.display()
+        )
+    );
+
+    let output = run(&[
+        "lint",
+        "--allow",
+        "default-value",
+        "--warning",
+        "empty-connection",
+        file,
+    ]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output).lines().count(), 2);
+
+    let root = std::env::temp_dir();
+    let root = root.to_str().unwrap();
+    let output = run(&["lint", "--format", "sarif", "--root", root, file]);
+    assert_eq!(output.status.code(), Some(1));
+    let sarif: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 3);
+    assert_eq!(
+        results[2]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+        "godot_props_lint.tscn"
+    );
+
+    let output = run(&["lint", "--format", "junit", "--root", root, file]);
+    assert!(stdout(&output).contains(
+        "<testsuite name=\"godot_props_lint.tscn\" tests=\"3\" failures=\"3\" errors=\"0\" skipped=\"0\">"
+    ));
+
+    let output = run(&["lint", "--allow", "no-such-rule", file]);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,177 @@
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use godot_properties_parser::project::{DependencyGraph, DependencyKind, GodotProject};
+use godot_properties_parser::{parse_project_file, parse_scene_file};
+
+const SAMPLE_PROJECT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/projects/sample_project");
+
+fn sample_graph() -> DependencyGraph {
+    DependencyGraph::from_project_dir(SAMPLE_PROJECT).unwrap()
+}
+
+#[test]
+fn test_project_skips_ignored_and_cache_directories() {
+    let project = GodotProject::open(SAMPLE_PROJECT).unwrap();
+
+    assert!(project.contains("res://levels/main.tscn"));
+    assert!(!project.contains("res://addons/ignored_plugin/ignored.tscn"));
+    assert!(!project.contains("res://.godot/imported/cached.tscn"));
+    assert!(!project.contains("res://levels/main.gd.uid"));
+}
+
+#[test]
+fn test_project_collects_uids() {
+    let project = GodotProject::open(SAMPLE_PROJECT).unwrap();
+
+    assert_eq!(
+        project.resolve_reference("uid://b1main0scene"),
+        Some("res://levels/main.tscn")
+    );
+    assert_eq!(
+        project.resolve_reference("uid://a8player0gd"),
+        Some("res://player/player.gd")
+    );
+    assert_eq!(
+        project.resolve_reference("uid://d3icon0svg"),
+        Some("res://icon.svg")
+    );
+}
+
+#[test]
+fn test_project_edges() {
+    let graph = sample_graph();
+
+    assert_eq!(
+        graph.dependencies("res://project.godot"),
+        vec![
+            "res://autoload/events.gd",
+            "res://autoload/game_state.tscn",
+            "res://levels/main.tscn"
+        ]
+    );
+
+    let main_scene_edge = graph
+        .edges()
+        .iter()
+        .find(|e| e.kind == DependencyKind::MainScene)
+        .unwrap();
+    assert_eq!(main_scene_edge.to, "res://levels/main.tscn");
+}
+
+#[test]
+fn test_instance_edges() {
+    let graph = sample_graph();
+
+    let instances: Vec<_> = graph
+        .edges()
+        .iter()
+        .filter(|e| e.kind == DependencyKind::Instance && e.from == "res://levels/main.tscn")
+        .map(|e| e.to.as_str())
+        .collect();
+    assert_eq!(instances, vec!["res://player/player.tscn"]);
+}
+
+#[test]
+fn test_one_edge_per_pair_of_files() {
+    let graph = sample_graph();
+
+    let pairs: BTreeSet<_> = graph.edges().iter().map(|e| (&e.from, &e.to)).collect();
+    assert_eq!(pairs.len(), graph.edges().len());
+    let player = graph
+        .edges()
+        .iter()
+        .find(|e| e.from == "res://levels/main.tscn" && e.to == "res://player/player.tscn")
+        .unwrap();
+    assert_eq!(player.kind, DependencyKind::Instance);
+}
+
+#[test]
+fn test_dependents() {
+    let graph = sample_graph();
+
+    assert_eq!(
+        graph.dependents("res://weapons/sword.tres"),
+        vec!["res://player/player.tscn"]
+    );
+    assert_eq!(
+        graph.transitive_dependents("res://weapons/weapon.gd"),
+        vec![
+            "res://levels/main.tscn",
+            "res://player/player.tscn",
+            "res://project.godot",
+            "res://weapons/sword.tres"
+        ]
+    );
+}
+
+#[test]
+fn test_transitive_dependencies() {
+    let graph = sample_graph();
+
+    assert_eq!(
+        graph.transitive_dependencies("res://levels/main.tscn"),
+        vec![
+            "res://icon.svg",
+            "res://levels/main.gd",
+            "res://player/player.gd",
+            "res://player/player.tscn",
+            "res://weapons/sword.tres",
+            "res://weapons/weapon.gd"
+        ]
+    );
+}
+
+#[test]
+fn test_cycles() {
+    let graph = sample_graph();
+
+    assert_eq!(
+        graph.cycles(),
+        vec![vec![
+            "res://unused/cycle_a.tscn",
+            "res://unused/cycle_b.tscn"
+        ]]
+    );
+}
+
+#[test]
+fn test_unreachable() {
+    let graph = sample_graph();
+
+    assert_eq!(
+        graph.unreachable(),
+        vec![
+            "res://unused/cycle_a.tscn",
+            "res://unused/cycle_b.tscn",
+            "res://unused/orphan.gd"
+        ]
+    );
+}
+
+#[test]
+fn test_long_cycles() {
+    // A chain of scenes, each instancing the next and the last instancing the first
+    let count = 20_000;
+    let path = |i: usize| format!("res://chain/{}.tscn", i % count);
+    let mut project = GodotProject::new(
+        PathBuf::new(),
+        parse_project_file("config_version=5\n")
+            .unwrap()
+            .1
+            .into_owned(),
+    );
+    for i in 0..count {
+        let text = format!(
+            "[gd_scene format=3]\n\n[ext_resource type=\"PackedScene\" path=\"{}\" id=\"1\"]\n\n\
+             [node name=\"Link\" instance=ExtResource(\"1\")]\n",
+            path(i + 1)
+        );
+        project.add_scene(&path(i), parse_scene_file(&text).unwrap().1.into_owned());
+    }
+
+    let graph = DependencyGraph::from_project(&project);
+    let cycles = graph.cycles();
+    assert_eq!(cycles.len(), 1);
+    assert_eq!(cycles[0].len(), count);
+}
@@ -0,0 +1,198 @@
+use godot_properties_parser::diff::{Change, Owner};
+use godot_properties_parser::{diff, diff_projects, parse_project_file, parse_scene_file};
+
+const OLD: &str = r#"[gd_scene load_steps=4 format=3]
+
+[ext_resource type="Script" uid="uid://a6main0gd" path="res://levels/main.gd" id="1_main"]
+[ext_resource type="Texture2D" path="res://icon.svg" id="2_icon"]
+
+[sub_resource type="CircleShape2D" id="CircleShape2D_hit01"]
+radius = 8.0
+
+[node name="Main" type="Node2D"]
+script = ExtResource("1_main")
+
+[node name="Logo" type="Sprite2D" parent="."]
+texture = ExtResource("2_icon")
+
+[node name="Spawner" type="Node2D" parent="."]
+
+[node name="Drone" type="Node2D" parent="Spawner"]
+speed = 4.0
+
+[connection signal="ready" from="." to="Spawner" method="_on_ready"]
+"#;
+
+/// The same scene saved again by Godot, with new resource ids and the spawner moved
+const RESAVED: &str = r#"[gd_scene load_steps=4 format=3]
+
+[ext_resource type="Texture2D" path="res://icon.svg" id="1_k2x4p"]
+[ext_resource type="Script" uid="uid://a6main0gd" path="res://main.gd" id="2_m8a1c"]
+
+[sub_resource type="CircleShape2D" id="CircleShape2D_hit01"]
+radius = 8.0
+
+[node name="Main" type="Node2D"]
+script = ExtResource("2_m8a1c")
+
+[node name="Logo" type="Sprite2D" parent="."]
+texture = ExtResource("1_k2x4p")
+
+[node name="World" type="Node2D" parent="."]
+
+[node name="Spawner" type="Node2D" parent="World"]
+
+[node name="Drone" type="Node2D" parent="World/Spawner"]
+speed = 4.0
+
+[connection signal="ready" from="." to="World/Spawner" method="_on_ready"]
+"#;
+
+#[test]
+fn test_resource_ids_are_ignored() {
+    let (_, old) = parse_scene_file(OLD).unwrap();
+    let (_, new) = parse_scene_file(RESAVED).unwrap();
+    let diff = diff(&old, &new);
+
+    assert_eq!(
+        diff.changes,
+        [
+            // The script is matched by uid although it moved
+            Change::PropertyChanged {
+                owner: Owner::Resource("res://main.gd".to_string()),
+                key: "path".to_string(),
+                old: Some("\"res://levels/main.gd\"".to_string()),
+                new: Some("\"res://main.gd\"".to_string()),
+            },
+            Change::NodeMoved {
+                from: "Spawner".to_string(),
+                to: "World/Spawner".to_string(),
+            },
+            Change::NodeAdded {
+                path: "World".to_string(),
+                node_type: Some("Node2D".to_string()),
+            },
+            Change::ConnectionRemoved {
+                connection: "signal=\"ready\" from=\".\" to=\"Spawner\" method=\"_on_ready\""
+                    .to_string(),
+            },
+            Change::ConnectionAdded {
+                connection: "signal=\"ready\" from=\".\" to=\"World/Spawner\" method=\"_on_ready\""
+                    .to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_node_with_a_different_body_is_not_a_move() {
+    let (_, old) = parse_scene_file(OLD).unwrap();
+    let changed = OLD.replace(
+        "[node name=\"Logo\" type=\"Sprite2D\" parent=\".\"]\ntexture = ExtResource(\"2_icon\")",
+        "[node name=\"Logo\" type=\"Sprite2D\" parent=\"Spawner\"]",
+    );
+    let (_, new) = parse_scene_file(&changed).unwrap();
+
+    assert_eq!(
+        diff(&old, &new).to_string(),
+        "- node Logo (Sprite2D)\n+ node Spawner/Logo (Sprite2D)\n"
+    );
+}
+
+#[test]
+fn test_sub_resources_are_paired_by_content() {
+    let (_, old) = parse_scene_file(
+        r#"[gd_scene load_steps=4 format=3]
+
+[sub_resource type="Gradient" id="Gradient_a1"]
+colors = PackedColorArray(1, 0, 0, 1)
+
+[sub_resource type="GradientTexture1D" id="GradientTexture1D_a2"]
+gradient = SubResource("Gradient_a1")
+
+[sub_resource type="CircleShape2D" id="CircleShape2D_a3"]
+radius = 8.0
+
+[node name="Main" type="Node2D"]
+
+[node name="Glow" type="Sprite2D" parent="."]
+texture = SubResource("GradientTexture1D_a2")
+
+[node name="Hitbox" type="CollisionShape2D" parent="."]
+shape = SubResource("CircleShape2D_a3")
+"#,
+    )
+    .unwrap();
+    // Saved again with new ids for the gradient, and a larger hitbox
+    let (_, new) = parse_scene_file(
+        r#"[gd_scene load_steps=4 format=3]
+
+[sub_resource type="Gradient" id="Gradient_x9"]
+colors = PackedColorArray(1, 0, 0, 1)
+
+[sub_resource type="GradientTexture1D" id="GradientTexture1D_x8"]
+gradient = SubResource("Gradient_x9")
+
+[sub_resource type="CircleShape2D" id="CircleShape2D_a3"]
+radius = 10.0
+
+[node name="Main" type="Node2D"]
+
+[node name="Glow" type="Sprite2D" parent="."]
+texture = SubResource("GradientTexture1D_x8")
+
+[node name="Hitbox" type="CollisionShape2D" parent="."]
+shape = SubResource("CircleShape2D_a3")
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        diff(&old, &new).to_string(),
+        "~ resource SubResource(\"CircleShape2D_a3\") radius: 8.0 -> 10.0\n"
+    );
+}
+
+#[test]
+fn test_text_output() {
+    let (_, old) = parse_scene_file(OLD).unwrap();
+    let changed = OLD
+        .replace("radius = 8.0", "radius = 12.0")
+        .replace("speed = 4.0\n", "")
+        .replace(
+            "[ext_resource type=\"Texture2D\" path=\"res://icon.svg\" id=\"2_icon\"]",
+            "[ext_resource type=\"Texture2D\" path=\"res://logo.png\" id=\"2_icon\"]",
+        )
+        .replace(
+            "[node name=\"Drone\" type=\"Node2D\" parent=\"Spawner\"]",
+            "[node name=\"Drone\" type=\"Node2D\" parent=\"Spawner\"]\n\n\
+             [node name=\"Turret\" type=\"StaticBody2D\" parent=\"Spawner\"]",
+        );
+    let (_, new) = parse_scene_file(&changed).unwrap();
+
+    assert_eq!(
+        diff(&old, &new).to_string(),
+        "- resource res://icon.svg (Texture2D)\n\
+         + resource res://logo.png (Texture2D)\n\
+         ~ resource SubResource(\"CircleShape2D_hit01\") radius: 8.0 -> 12.0\n\
+         ~ node Logo texture: ExtResource(\"res://icon.svg\") -> ExtResource(\"res://logo.png\")\n\
+         ~ node Spawner/Drone speed: 4.0 -> (unset)\n\
+         + node Spawner/Turret (StaticBody2D)\n"
+    );
+    assert!(diff(&old, &old).is_empty());
+}
+
+#[test]
+fn test_project_diff() {
+    let (_, old) =
+        parse_project_file("config_version=5\n\n[application]\n\nconfig/name=\"Game\"\n").unwrap();
+    let (_, new) = parse_project_file(
+        "config_version=6\n\n[application]\n\nconfig/name=\"Game\"\n\n[autoload]\n\nEvents=\"*res://events.gd\"\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        diff_projects(&old, &new).to_string(),
+        "~ config_version: 5 -> 6\n~ [autoload] Events: (unset) -> \"*res://events.gd\"\n"
+    );
+}
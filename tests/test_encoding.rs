@@ -0,0 +1,127 @@
+use godot_properties_parser::parsers::encoding::decode;
+use godot_properties_parser::{
+    SectionReader, parse_project_file_bytes, parse_property_file, parse_property_file_bytes,
+    parse_scene_file_bytes,
+};
+use std::borrow::Cow;
+use std::io::Cursor;
+
+const PLAY_ROOM_CONTENT: &str = include_str!("scenes/play_room.tscn");
+const PROJECT_CONTENT: &str = include_str!("projects/project_gamblers_table.godot");
+
+/// Saves `content` the way a Windows editor might: with a byte order mark and CRLF endings
+fn windows_authored(content: &str) -> Vec<u8> {
+    let mut bytes = b"\xEF\xBB\xBF".to_vec();
+    bytes.extend(content.replace('\n', "\r\n").into_bytes());
+    bytes
+}
+
+#[test]
+fn test_valid_input_is_borrowed() {
+    let decoded = decode(PLAY_ROOM_CONTENT.as_bytes());
+    assert!(matches!(decoded.text, Cow::Borrowed(_)));
+    assert!(decoded.diagnostics.is_empty());
+
+    let parsed = parse_scene_file_bytes(PLAY_ROOM_CONTENT.as_bytes());
+    let header = parsed.value.header().unwrap();
+    assert!(matches!(header.header_type, Cow::Borrowed(_)));
+}
+
+#[test]
+fn test_windows_authored_scene_matches_original() {
+    let (_, expected) = parse_property_file(PLAY_ROOM_CONTENT).unwrap();
+
+    let bytes = windows_authored(PLAY_ROOM_CONTENT);
+    let parsed = parse_property_file_bytes(&bytes);
+
+    assert_eq!(parsed.value, expected);
+    assert_eq!(parsed.remaining, "");
+    assert!(parsed.diagnostics.is_empty());
+}
+
+#[test]
+fn test_windows_authored_project() {
+    let bytes = windows_authored(PROJECT_CONTENT);
+    let project = parse_project_file_bytes(&bytes).value;
+
+    assert_eq!(project.preamble_properties[0].key, "config_version");
+    assert_eq!(
+        project.get("application", "run/main_scene"),
+        Some("uid://hmkin06akodv")
+    );
+}
+
+#[test]
+fn test_lone_cr_line_endings() {
+    let bytes = b"[gd_scene format=3]\r\r[node name=\"Root\" type=\"Node\"]\rvisible = false\r";
+    let decoded = decode(bytes);
+    assert_eq!(
+        decoded.text,
+        "[gd_scene format=3]\n\n[node name=\"Root\" type=\"Node\"]\nvisible = false\n"
+    );
+
+    let scene = parse_scene_file_bytes(bytes).value;
+    let nodes = scene.nodes();
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(nodes[0].get("visible"), Some("false"));
+}
+
+#[test]
+fn test_multiline_value_keeps_normalized_newlines() {
+    let bytes = b"[resource]\r\ntext = \"first\r\nsecond\"\r\n";
+    let parsed = parse_property_file_bytes(bytes);
+    assert_eq!(parsed.value.sections[0].get("text"), Some("first\nsecond"));
+}
+
+#[test]
+fn test_invalid_utf8_is_located() {
+    let bytes =
+        b"[node name=\"Root\" type=\"Label\"]\ntext = \"caf\xE9 au lait\"\nvisible = true\n";
+    let parsed = parse_scene_file_bytes(bytes);
+
+    assert_eq!(parsed.diagnostics.len(), 1);
+    let diagnostic = &parsed.diagnostics[0];
+    assert_eq!(diagnostic.offset, 43);
+    assert_eq!(diagnostic.line, 2);
+    assert_eq!(diagnostic.column, 12);
+    assert_eq!(diagnostic.bytes, vec![0xE9]);
+    assert_eq!(diagnostic.to_string(), "2:12: invalid UTF-8 sequence 0xe9");
+
+    // The rest of the file still parses, with the bad byte replaced
+    let node = parsed.value.nodes()[0];
+    assert_eq!(node.get("text"), Some("caf\u{FFFD} au lait"));
+    assert_eq!(node.get("visible"), Some("true"));
+}
+
+#[test]
+fn test_diagnostics_after_bom_and_crlf() {
+    let mut bytes = windows_authored("[resource]\nname = \"ok\"\n");
+    bytes.extend(b"bad = \"\xFF\xFE\"\r\n");
+    let parsed = parse_property_file_bytes(&bytes);
+
+    let diagnostics = &parsed.diagnostics;
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].offset, bytes.len() - 5);
+    assert_eq!(diagnostics[0].line, 3);
+    assert_eq!(diagnostics[0].column, 8);
+    assert_eq!(diagnostics[0].bytes, vec![0xFF, 0xFE]);
+    assert_eq!(parsed.value.sections[0].get("name"), Some("ok"));
+}
+
+#[test]
+fn test_section_reader_decodes_bytes() {
+    let mut bytes = windows_authored(PLAY_ROOM_CONTENT);
+    bytes.extend(b"[node name=\"Extra\" type=\"Label\" parent=\".\"]\r\ntext = \"\xC3\"\r\n");
+
+    let mut reader = SectionReader::new(Cursor::new(bytes));
+    let sections: Vec<_> = reader.by_ref().collect::<Result<_, _>>().unwrap();
+
+    let (_, expected) = parse_property_file(PLAY_ROOM_CONTENT).unwrap();
+    assert_eq!(sections[..expected.sections.len()], expected.sections[..]);
+    assert_eq!(sections.last().unwrap().get("text"), Some("\u{FFFD}"));
+
+    let line = PLAY_ROOM_CONTENT.lines().count() + 2;
+    assert_eq!(reader.diagnostics().len(), 1);
+    assert_eq!(reader.diagnostics()[0].line, line);
+    assert_eq!(reader.diagnostics()[0].column, 9);
+}
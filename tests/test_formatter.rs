@@ -0,0 +1,119 @@
+use godot_properties_parser::format_text;
+use godot_properties_parser::formatter::{ExtResourceOrder, FormatError, FormatOptions};
+
+const MESSY: &str = "[gd_scene load_steps=3 format=3]
+[ext_resource type=\"Texture2D\" path=\"res://icon.svg\" id=\"2_icon\"]
+
+[ext_resource type=\"Script\"   path=\"res://main.gd\" id=\"1_main\"]
+[node name=\"Main\" type=\"Node2D\" groups=[\"a\",\"b\"]]
+script=ExtResource( \"1_main\" )
+scale   =   Vector2(2.0, 0.5)
+rotation = 1.5e-6
+
+
+[node name=\"Icon\" type=\"Sprite2D\" parent=\".\"]
+texture = ExtResource(\"2_icon\")
+modulate = Color(1.0, 1.0, 1.0, 0.5)
+speed = 2.0
+[connection signal=\"ready\" from=\".\" to=\".\" method=\"_a\"]
+
+[connection signal=\"ready\" from=\".\" to=\".\" method=\"_b\"]";
+
+const FORMATTED: &str = r#"[gd_scene load_steps=3 format=3]
+
+[ext_resource type="Script" path="res://main.gd" id="1_main"]
+[ext_resource type="Texture2D" path="res://icon.svg" id="2_icon"]
+
+[node name="Main" type="Node2D" groups=["a", "b"]]
+script = ExtResource("1_main")
+scale = Vector2(2, 0.5)
+rotation = 1.5e-06
+
+[node name="Icon" type="Sprite2D" parent="."]
+texture = ExtResource("2_icon")
+modulate = Color(1, 1, 1, 0.5)
+speed = 2.0
+
+[connection signal="ready" from="." to="." method="_a"]
+[connection signal="ready" from="." to="." method="_b"]
+"#;
+
+#[test]
+fn test_scene_is_formatted_like_godot() {
+    let options = FormatOptions::default();
+    assert_eq!(format_text(MESSY, &options).unwrap(), FORMATTED);
+    assert_eq!(format_text(FORMATTED, &options).unwrap(), FORMATTED);
+}
+
+#[test]
+fn test_ext_resource_order() {
+    let keep = FormatOptions {
+        ext_resource_order: ExtResourceOrder::Keep,
+    };
+    let formatted = format_text(MESSY, &keep).unwrap();
+    assert!(formatted.contains(
+        "[ext_resource type=\"Texture2D\" path=\"res://icon.svg\" id=\"2_icon\"]\n\
+         [ext_resource type=\"Script\" path=\"res://main.gd\" id=\"1_main\"]\n"
+    ));
+
+    let by_path = FormatOptions {
+        ext_resource_order: ExtResourceOrder::Path,
+    };
+    let formatted = format_text(FORMATTED, &by_path).unwrap();
+    assert!(formatted.contains(
+        "[ext_resource type=\"Texture2D\" path=\"res://icon.svg\" id=\"2_icon\"]\n\
+         [ext_resource type=\"Script\" path=\"res://main.gd\" id=\"1_main\"]\n"
+    ));
+}
+
+#[test]
+fn test_config_files_keep_their_comments() {
+    let project = "; Engine configuration file.\n;\n;   param=value\n\nconfig_version = 5\n[application]\nconfig/name = \"Game\"\n; options\n\n[preset.0.options]\ncustom_template/debug=\"\"\n";
+    let formatted = format_text(project, &FormatOptions::default()).unwrap();
+    assert_eq!(
+        formatted,
+        "; Engine configuration file.\n;\n;   param=value\n\nconfig_version=5\n\n\
+         [application]\n\nconfig/name=\"Game\"\n\n\
+         ; options\n[preset.0.options]\n\ncustom_template/debug=\"\"\n"
+    );
+    assert_eq!(
+        format_text(&formatted, &FormatOptions::default()).unwrap(),
+        formatted
+    );
+}
+
+#[test]
+fn test_comments_stay_with_their_lines() {
+    let scene = "[gd_scene format=3]\n[node name=\"Main\" type=\"Node2D\"] ; the root\n; how fast\nspeed=2.0\nrows=[\n; first\n1]\nscale=Vector2(2.0, 2.0) ; doubled\n; end\n";
+    let formatted = format_text(scene, &FormatOptions::default()).unwrap();
+    assert_eq!(
+        formatted,
+        "[gd_scene format=3]\n\n[node name=\"Main\" type=\"Node2D\"] ; the root\n\
+         ; how fast\nspeed = 2.0\nrows = [\n; first\n1]\nscale = Vector2(2, 2) ; doubled\n; end\n"
+    );
+    assert_eq!(
+        format_text(&formatted, &FormatOptions::default()).unwrap(),
+        formatted
+    );
+}
+
+#[test]
+fn test_negative_zero_keeps_its_sign() {
+    let scene =
+        "[gd_scene format=3]\n\n[node name=\"Main\" type=\"Node2D\"]\nscale = Vector2(-0.0, 0.0)\n";
+    assert_eq!(
+        format_text(scene, &FormatOptions::default()).unwrap(),
+        scene.replace("0.0)", "0)")
+    );
+}
+
+#[test]
+fn test_unparsed_content_is_an_error() {
+    let error = format_text(
+        "[gd_scene format=3]\n\n[node name=\"Broken\"\n",
+        &FormatOptions::default(),
+    )
+    .unwrap_err();
+    assert_eq!(error, FormatError { line: 3 });
+    assert_eq!(error.to_string(), "line 3 could not be parsed");
+}
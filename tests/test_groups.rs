@@ -0,0 +1,80 @@
+use godot_properties_parser::parse_scene_file;
+use godot_properties_parser::project::{GodotProject, GroupIndex, GroupMember};
+use godot_properties_parser::scene::node_groups;
+
+const SAMPLE_PROJECT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/projects/sample_project");
+const TABLE_SELECTION_ENTRY_CONTENT: &str = include_str!("scenes/table_selection_entry.tscn");
+
+const ARENA_SCENE: &str = r#"[gd_scene format=3]
+
+[node name="Arena" type="Node2D"]
+
+[node name="Goblin" type="CharacterBody2D" parent="." groups=["enemies", "damageable"]]
+
+[node name="Crate" type="RigidBody2D" parent="." groups=["damageable"]]
+
+[node name="Floor" type="StaticBody2D" parent="."]
+"#;
+
+#[test]
+fn test_node_groups() {
+    let (_, scene) = parse_scene_file(ARENA_SCENE).unwrap();
+
+    assert_eq!(
+        node_groups(scene.find_node("Goblin").unwrap()),
+        vec!["enemies", "damageable"]
+    );
+    assert!(node_groups(scene.find_node("Floor").unwrap()).is_empty());
+}
+
+#[test]
+fn test_scene_group_index() {
+    let (_, scene) = parse_scene_file(ARENA_SCENE).unwrap();
+    let groups = scene.groups();
+
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups["damageable"], vec!["Goblin", "Crate"]);
+    assert_eq!(groups["enemies"], vec!["Goblin"]);
+}
+
+#[test]
+fn test_fixture_scene_groups() {
+    let (_, scene) = parse_scene_file(TABLE_SELECTION_ENTRY_CONTENT).unwrap();
+
+    assert_eq!(scene.groups()["tooltip_trigger"], vec!["SelectButton"]);
+}
+
+#[test]
+fn test_project_group_index() {
+    let project = GodotProject::open(SAMPLE_PROJECT).unwrap();
+    let index = GroupIndex::from_project(&project);
+
+    assert_eq!(
+        index.global_groups["enemies"],
+        "Everything that attacks the player"
+    );
+    assert_eq!(
+        index.members("enemies"),
+        &[
+            GroupMember {
+                scene: "res://levels/main.tscn".to_string(),
+                node: "Spawner".to_string(),
+            },
+            GroupMember {
+                scene: "res://levels/main.tscn".to_string(),
+                node: "Spawner/Drone".to_string(),
+            },
+        ]
+    );
+    assert_eq!(index.members("damageable").len(), 2);
+    assert!(index.members("unknown").is_empty());
+}
+
+#[test]
+fn test_project_group_cross_check() {
+    let project = GodotProject::open(SAMPLE_PROJECT).unwrap();
+    let index = GroupIndex::from_project(&project);
+
+    assert_eq!(index.undeclared_groups(), vec!["damageable"]);
+    assert_eq!(index.unused_global_groups(), vec!["pickups"]);
+}
@@ -0,0 +1,186 @@
+#![cfg(feature = "json")]
+
+use godot_properties_parser::lint::Linter;
+use godot_properties_parser::parsers::json::{
+    project_from_json, project_to_json, scene_from_json, scene_to_json, value_from_json,
+    value_to_json,
+};
+use godot_properties_parser::parsers::parser_value::Value;
+use godot_properties_parser::report::{Report, parse_diagnostics};
+use godot_properties_parser::{parse_project_file, parse_scene_file};
+use serde_json::json;
+
+const PLAY_ROOM_CONTENT: &str = include_str!("scenes/play_room.tscn");
+const PROJECT_CONTENT: &str = include_str!("projects/project_gamblers_table.godot");
+
+#[test]
+fn test_values_are_tagged_with_their_type() {
+    let cases = [
+        ("null", json!({"type": "Nil", "value": null})),
+        ("true", json!({"type": "bool", "value": true})),
+        ("42", json!({"type": "int", "value": 42})),
+        ("0.5", json!({"type": "float", "value": 0.5})),
+        ("-inf", json!({"type": "float", "value": "-inf"})),
+        ("\"room\"", json!({"type": "String", "value": "room"})),
+        ("&\"room\"", json!({"type": "StringName", "value": "room"})),
+        (
+            "NodePath(\"../Label\")",
+            json!({"type": "NodePath", "value": "../Label"}),
+        ),
+        (
+            "Vector2(263, 14)",
+            json!({"type": "Vector2", "value": [263, 14]}),
+        ),
+        (
+            "[1, Vector2(1.5, 2)]",
+            json!({"type": "Array", "value": [1, {"type": "Vector2", "value": [1.5, 2]}]}),
+        ),
+        (
+            "{\n\"a\": ExtResource(\"1_abc\")\n}",
+            json!({"type": "Dictionary", "value": [
+                {"key": "a", "value": {"type": "ExtResource", "value": ["1_abc"]}}
+            ]}),
+        ),
+        (
+            "Object(InputEventKey,\"keycode\":65)",
+            json!({"type": "Object", "class": "InputEventKey", "value": [
+                {"key": "keycode", "value": 65}
+            ]}),
+        ),
+    ];
+
+    for (text, expected) in cases {
+        let value = Value::parse(text).unwrap();
+        let json = value_to_json(&value);
+        assert_eq!(json, expected, "{}", text);
+        assert_eq!(value_from_json(&json).unwrap().to_string(), text);
+    }
+}
+
+#[test]
+fn test_scene_round_trip() {
+    let (_, scene) = parse_scene_file(PLAY_ROOM_CONTENT).unwrap();
+    let json = scene_to_json(&scene);
+
+    let text = serde_json::to_string(&json).unwrap();
+    let restored = scene_from_json(&serde_json::from_str(&text).unwrap()).unwrap();
+    assert_eq!(scene_to_json(&restored), json);
+
+    for (section, original) in restored.all_sections.iter().zip(&scene.all_sections) {
+        assert_eq!(section.header_type, original.header_type);
+        assert_eq!(section.attribute_count, original.attribute_count);
+        for (property, original) in section.properties.iter().zip(&original.properties) {
+            assert_eq!(property.key, original.key);
+            assert_eq!(property.quoted, original.quoted);
+            assert_eq!(
+                Value::parse(&property.value_text()),
+                Value::parse(&original.value_text())
+            );
+        }
+    }
+
+    // The restored scene is written back as a file that parses to the same JSON
+    let written = restored.to_string();
+    let (_, reparsed) = parse_scene_file(&written).unwrap();
+    assert_eq!(scene_to_json(&reparsed), json);
+}
+
+#[test]
+fn test_project_round_trip() {
+    let (_, project) = parse_project_file(PROJECT_CONTENT).unwrap();
+    let json = project_to_json(&project);
+    assert_eq!(
+        json["preamble"][0],
+        json!({"key": "config_version", "value": {"type": "int", "value": 5}})
+    );
+
+    let restored = project_from_json(&json).unwrap();
+    assert_eq!(restored, project);
+    assert_eq!(
+        restored.get("application", "config/name"),
+        Some("Gamblers Table")
+    );
+
+    let written = restored.to_string();
+    let (_, reparsed) = parse_project_file(&written).unwrap();
+    assert_eq!(reparsed, project);
+}
+
+#[test]
+fn test_invalid_values_are_kept_raw() {
+    let (_, scene) =
+        parse_scene_file("[node name=\"A\" type=\"Node\"]\nmode = canvas_items\n").unwrap();
+    let json = scene_to_json(&scene);
+    assert_eq!(
+        json["sections"][0]["properties"][0]["value"],
+        json!({"type": "raw", "value": "canvas_items"})
+    );
+
+    let restored = scene_from_json(&json).unwrap();
+    assert_eq!(restored.all_sections[0].get("mode"), Some("canvas_items"));
+}
+
+#[test]
+fn test_errors_point_to_the_problem() {
+    let json = json!({"sections": [{"header": "node", "properties": [
+        {"key": "position", "value": {"type": "Vector2", "value": 3}}
+    ]}]});
+    let error = scene_from_json(&json).unwrap_err();
+    assert_eq!(error.path, "sections[0].properties[0].value.value");
+    assert_eq!(
+        error.to_string(),
+        "sections[0].properties[0].value.value: expected an array"
+    );
+
+    let error = value_from_json(&json!({"type": "int", "value": "5"})).unwrap_err();
+    assert_eq!(error.message, "expected an integer");
+}
+
+#[test]
+fn test_sarif_report() {
+    let source = "[gd_scene format=3]\n\n[node name=\"Main\" type=\"Spatial\"]\n\n[node name=\"\n";
+    let result = parse_scene_file(source);
+    let mut diagnostics = parse_diagnostics(source, &result);
+    diagnostics.extend(Linter::default().lint_scene(&result.unwrap().1));
+    let mut report = Report::new("/work/game");
+    report.add(
+        std::path::Path::new("/work/game/levels/main menu.tscn"),
+        source,
+        diagnostics.clone(),
+    );
+    report.add(
+        std::path::Path::new("/work/shared/hud bar.tscn"),
+        source,
+        diagnostics,
+    );
+    assert!(report.has_errors());
+
+    let sarif = report.to_sarif();
+    assert_eq!(sarif["version"], "2.1.0");
+    let run = &sarif["runs"][0];
+    assert_eq!(
+        run["tool"]["driver"]["rules"],
+        json!([{ "id": "deprecated-node-type" }, { "id": "parse-error" }])
+    );
+    assert_eq!(
+        run["results"][1],
+        json!({
+            "ruleId": "parse-error",
+            "ruleIndex": 1,
+            "level": "error",
+            "message": { "text": "string is not closed before the end of the file, the rest of the file is ignored" },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": { "uri": "levels/main%20menu.tscn", "uriBaseId": "%SRCROOT%" },
+                    "region": { "startLine": 5, "startColumn": 1, "endLine": 5, "endColumn": 13 },
+                },
+            }],
+        })
+    );
+    assert_eq!(run["results"][0]["level"], "warning");
+    // A file outside the root has an absolute URI and no base
+    assert_eq!(
+        run["results"][3]["locations"][0]["physicalLocation"]["artifactLocation"],
+        json!({ "uri": "file:///work/shared/hud%20bar.tscn" })
+    );
+}
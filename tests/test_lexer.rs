@@ -0,0 +1,94 @@
+use godot_properties_parser::parsers::lexer::{Lexer, Span, TokenKind, tokenize};
+
+fn kinds(input: &str) -> Vec<TokenKind> {
+    tokenize(input).into_iter().map(|t| t.kind).collect()
+}
+
+#[test]
+fn test_header_tokens() {
+    use TokenKind::*;
+
+    assert_eq!(
+        kinds("[node name=\"Root\" type=\"Node2D\"]\n"),
+        vec![
+            OpenBracket,
+            Identifier,
+            Identifier,
+            Equals,
+            String,
+            Identifier,
+            Equals,
+            String,
+            CloseBracket,
+            Newline
+        ]
+    );
+}
+
+#[test]
+fn test_token_spans() {
+    let input = "position = Vector2(578, 325)";
+    let tokens = tokenize(input);
+
+    assert_eq!(tokens[0].span, Span::new(0, 8));
+    assert_eq!(tokens[2].text, "Vector2");
+    assert_eq!(tokens[2].span.slice(input), "Vector2");
+    assert!(tokens[3].follows(&tokens[2]));
+    assert!(!tokens[2].follows(&tokens[1]));
+    for token in &tokens {
+        assert_eq!(token.span.slice(input), token.text);
+    }
+}
+
+#[test]
+fn test_multiline_string_is_one_token() {
+    let tokens = tokenize("text = \"a [\n{ \\\" b\"\nnext = 1");
+
+    assert_eq!(tokens[2].kind, TokenKind::String);
+    assert_eq!(tokens[2].string_content(), "a [\n{ \\\" b");
+    assert_eq!(tokens[3].kind, TokenKind::Newline);
+    assert_eq!(tokens[4].text, "next");
+}
+
+#[test]
+fn test_numbers_and_names() {
+    let tokens = tokenize("1 -2.5 1e-05 .5 0/position config/name -inf");
+    let texts: Vec<(TokenKind, &str)> = tokens.iter().map(|t| (t.kind, t.text)).collect();
+
+    assert_eq!(
+        texts,
+        vec![
+            (TokenKind::Number, "1"),
+            (TokenKind::Number, "-2.5"),
+            (TokenKind::Number, "1e-05"),
+            (TokenKind::Number, ".5"),
+            (TokenKind::Identifier, "0/position"),
+            (TokenKind::Identifier, "config/name"),
+            (TokenKind::Symbol, "-"),
+            (TokenKind::Identifier, "inf"),
+        ]
+    );
+}
+
+#[test]
+fn test_comments_and_unterminated_strings() {
+    use TokenKind::*;
+
+    assert_eq!(
+        kinds("; comment \"x\n[a]"),
+        vec![Comment, Newline, OpenBracket, Identifier, CloseBracket]
+    );
+    assert_eq!(
+        kinds("a = \"open"),
+        vec![Identifier, Equals, UnterminatedString]
+    );
+}
+
+#[test]
+fn test_lexer_resumes_at_offset() {
+    let mut lexer = Lexer::at("skipped value", 8);
+
+    assert_eq!(lexer.next().map(|t| t.text), Some("value"));
+    assert_eq!(lexer.offset(), 13);
+    assert_eq!(lexer.next(), None);
+}
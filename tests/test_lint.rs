@@ -0,0 +1,126 @@
+use godot_properties_parser::lint::{Finding, Lint, Linter, Severity};
+use godot_properties_parser::parsers::parser_project_file::ProjectFile;
+use godot_properties_parser::{parse_project_file, parse_scene_file};
+
+const SCENE: &str = r#"[gd_scene load_steps=6 format=3]
+
+[ext_resource type="Script" path="res://player.gd" id="1_player"]
+[ext_resource type="Texture2D" path="res://unused.png" id="2_unused"]
+[ext_resource type="Script" path="res://item.gd" id="3_item"]
+
+[sub_resource type="Gradient" id="Gradient_used"]
+
+[sub_resource type="GradientTexture1D" id="GradientTexture1D_used"]
+gradient = SubResource("Gradient_used")
+
+[sub_resource type="CircleShape2D" id="CircleShape2D_unused"]
+
+[node name="Player" type="KinematicBody2D"]
+script = ExtResource("1_player")
+texture = SubResource("GradientTexture1D_used")
+items = Array[ExtResource("3_item")]([])
+
+[node name="Sprite" type="Sprite2D" parent="."]
+position = Vector2(0.0, 0)
+scale = Vector2(2, 2)
+visible = true
+
+[node name="Health" type="Label" parent="."]
+unique_name_in_owner = true
+script = ExtResource("4_missing")
+
+[node name="Health" type="Label" parent="Sprite"]
+unique_name_in_owner = true
+
+[node name="Weapon" parent="." instance=ExtResource("1_player")]
+visible = true
+
+[connection signal="" from="." to="." method="_on_hit"]
+"#;
+
+#[test]
+fn test_builtin_rules() {
+    let (_, scene) = parse_scene_file(SCENE).unwrap();
+    let report: Vec<String> = Linter::default()
+        .lint_scene(&scene)
+        .iter()
+        .map(|d| d.to_string())
+        .collect();
+
+    assert_eq!(
+        report,
+        [
+            "4:1: warning[unused-ext-resource]: ext_resource 2_unused (res://unused.png) is not used",
+            "12:1: warning[unused-sub-resource]: sub_resource CircleShape2D_unused is not used",
+            "14:1: warning[deprecated-node-type]: node . uses deprecated type KinematicBody2D, use CharacterBody2D instead",
+            "20:1: info[default-value]: position of node Sprite is set to its default value Vector2(0.0, 0)",
+            "22:1: info[default-value]: visible of node Sprite is set to its default value true",
+            "26:1: error[missing-script]: script of node Health refers to undeclared ExtResource(\"4_missing\")",
+            "28:1: error[duplicate-unique-name]: unique name %Health of node Sprite/Health is already used by Health",
+            "34:1: error[empty-connection]: connection has no signal",
+        ]
+    );
+
+    assert_eq!(Linter::new().rules(), Linter::default().rules());
+    assert!(Linter::empty().rules().is_empty());
+}
+
+#[test]
+fn test_severities_can_be_configured() {
+    let (_, scene) = parse_scene_file(SCENE).unwrap();
+    let mut linter = Linter::default();
+    assert!(linter.set_severity("default-value", None));
+    assert!(linter.set_severity("unused-ext-resource", Some(Severity::Error)));
+    assert!(!linter.set_severity("no-such-rule", None));
+
+    let diagnostics = linter.lint_scene(&scene);
+    assert!(diagnostics.iter().all(|d| d.rule != "default-value"));
+    let unused = &diagnostics[0];
+    assert_eq!(unused.rule, "unused-ext-resource");
+    assert_eq!(unused.severity, Severity::Error);
+    assert_eq!((unused.location.line, unused.location.column), (4, 1));
+    assert_eq!(&SCENE[unused.location.span.start..][..14], "[ext_resource ");
+}
+
+/// Autoloads should be scripts or scenes, enabled with `*`
+struct AutoloadPaths;
+
+impl Lint for AutoloadPaths {
+    fn name(&self) -> &'static str {
+        "autoload-path"
+    }
+
+    fn check_project(&self, project: &ProjectFile) -> Vec<Finding> {
+        let Some(autoload) = project
+            .all_sections
+            .iter()
+            .find(|s| s.header_type == "autoload")
+        else {
+            return Vec::new();
+        };
+        autoload
+            .properties
+            .iter()
+            .filter(|p| !p.value.starts_with("*res://"))
+            .map(|p| Finding::new(format!("autoload {} is disabled", p.key), p.location()))
+            .collect()
+    }
+}
+
+#[test]
+fn test_custom_project_lint() {
+    let (_, project) = parse_project_file(
+        "config_version=5\n\n[autoload]\n\nEvents=\"*res://events.gd\"\nDebug=\"res://debug.gd\"\n",
+    )
+    .unwrap();
+    let mut linter = Linter::empty();
+    linter.add(AutoloadPaths, Severity::Warning);
+    assert_eq!(linter.rules(), ["autoload-path"]);
+
+    let diagnostics = linter.lint_project(&project);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "6:1: warning[autoload-path]: autoload Debug is disabled"
+    );
+}
@@ -0,0 +1,250 @@
+use godot_properties_parser::parsers::parser_project_file::ProjectFile;
+use godot_properties_parser::parsers::parser_scene_file::SceneFile;
+use godot_properties_parser::{merge_projects, merge_scenes, parse_project_file, parse_scene_file};
+
+const BASE: &str = r#"[gd_scene load_steps=3 format=3]
+
+[ext_resource type="Script" path="res://player.gd" id="1_plr01"]
+[ext_resource type="Texture2D" path="res://player.png" id="2_tex01"]
+
+[node name="Player" type="CharacterBody2D"]
+script = ExtResource("1_plr01")
+speed = 100.0
+
+[node name="Sprite" type="Sprite2D" parent="."]
+texture = ExtResource("2_tex01")
+
+[node name="Camera" type="Camera2D" parent="."]
+zoom = Vector2(2, 2)
+"#;
+
+fn scene(text: &str) -> SceneFile<'_> {
+    let (remaining, scene) = parse_scene_file(text).unwrap();
+    assert!(remaining.trim().is_empty());
+    scene
+}
+
+fn project(text: &str) -> ProjectFile<'_> {
+    parse_project_file(text).unwrap().1
+}
+
+#[test]
+fn test_independent_changes_are_merged() {
+    let ours = BASE
+        .replace("speed = 100.0", "speed = 120.0")
+        .replace("zoom = Vector2(2, 2)\n", "");
+    let theirs = BASE.replace(
+        "[node name=\"Camera\"",
+        "[node name=\"Shadow\" type=\"Sprite2D\" parent=\"Sprite\"]\n\n[node name=\"Camera\"",
+    ) + "offset = Vector2(0, -16)\n";
+
+    let result = merge_scenes(&scene(BASE), &scene(&ours), &scene(&theirs));
+    assert!(result.is_clean(), "{:?}", result.conflicts);
+    let merged = &result.merged;
+    assert_eq!(merged.find_node(".").unwrap().get("speed"), Some("120.0"));
+
+    // Their new node keeps its place after its parent
+    let names: Vec<_> = merged
+        .nodes()
+        .iter()
+        .map(|node| node.get("name").unwrap())
+        .collect();
+    assert_eq!(names, ["Player", "Sprite", "Shadow", "Camera"]);
+
+    // Properties are merged one by one, so removing one and adding another both apply
+    let camera = merged.find_node("Camera").unwrap();
+    assert_eq!(camera.get("zoom"), None);
+    assert_eq!(camera.get("offset"), Some("Vector2(0, -16)"));
+    assert_eq!(result.to_string(), merged.to_string());
+}
+
+#[test]
+fn test_colliding_resource_ids_are_renamed() {
+    let add = |path: &str, id: &str, node: &str| {
+        BASE.replace(
+            "\n[node name=\"Player\"",
+            &format!(
+                "[ext_resource type=\"Texture2D\" path=\"{}\" id=\"{}\"]\n\n[node name=\"Player\"",
+                path, id
+            ),
+        ) + &format!(
+            "\n[node name=\"{}\" type=\"Sprite2D\" parent=\".\"]\ntexture = ExtResource(\"{}\")\n",
+            node, id
+        )
+    };
+    let ours = add("res://hat.png", "3_hat01", "Hat");
+    let theirs = add("res://cape.png", "3_hat01", "Cape")
+        .replace("ExtResource(\"2_tex01\")", "ExtResource(\"2_tex02\")")
+        .replace("id=\"2_tex01\"", "id=\"2_tex02\"");
+
+    let result = merge_scenes(&scene(BASE), &scene(&ours), &scene(&theirs));
+    assert!(result.is_clean(), "{:?}", result.conflicts);
+    let merged = &result.merged;
+
+    let ext: Vec<_> = merged
+        .ext_resources()
+        .iter()
+        .map(|e| (e.get("id").unwrap(), e.get("path").unwrap()))
+        .collect();
+    assert_eq!(ext.len(), 4);
+    assert_eq!(ext[1], ("2_tex01", "res://player.png"));
+    assert_eq!(ext[2], ("3_hat01", "res://hat.png"));
+    let (cape_id, cape_path) = ext[3];
+    assert_eq!(cape_path, "res://cape.png");
+    assert!(cape_id.starts_with("4_"), "{}", cape_id);
+
+    // References follow the renamed ids, and the same texture keeps our id
+    let texture = |path| merged.find_node(path).unwrap().get("texture");
+    assert_eq!(texture("Sprite"), Some("ExtResource(\"2_tex01\")"));
+    assert_eq!(texture("Hat"), Some("ExtResource(\"3_hat01\")"));
+    assert_eq!(
+        texture("Cape"),
+        Some(format!("ExtResource(\"{}\")", cape_id).as_str())
+    );
+    assert_eq!(merged.header().unwrap().get("load_steps"), Some("5"));
+}
+
+#[test]
+fn test_conflicts() {
+    let ours = BASE
+        .replace("speed = 100.0", "speed = 120.0")
+        .replace("zoom = Vector2(2, 2)", "zoom = Vector2(3, 3)");
+    let theirs = BASE.replace("speed = 100.0", "speed = 80.0").replace(
+        "\n[node name=\"Camera\" type=\"Camera2D\" parent=\".\"]\nzoom = Vector2(2, 2)\n",
+        "",
+    );
+
+    let result = merge_scenes(&scene(BASE), &scene(&ours), &scene(&theirs));
+    assert_eq!(result.conflicts.len(), 2);
+
+    let speed = &result.conflicts[0];
+    assert_eq!(
+        speed.section,
+        "[node name=\"Player\" type=\"CharacterBody2D\"]"
+    );
+    assert_eq!(speed.key.as_deref(), Some("speed"));
+    assert_eq!(
+        (
+            speed.base.as_deref(),
+            speed.ours.as_deref(),
+            speed.theirs.as_deref()
+        ),
+        (Some("100.0"), Some("120.0"), Some("80.0"))
+    );
+    assert_eq!(
+        result.merged.find_node(".").unwrap().get("speed"),
+        Some("120.0")
+    );
+
+    let camera = &result.conflicts[1];
+    assert_eq!(camera.key, None);
+    assert_eq!(camera.theirs, None);
+    assert_eq!(
+        camera.to_string(),
+        "[node name=\"Camera\" type=\"Camera2D\" parent=\".\"] was changed on our side and removed on theirs"
+    );
+
+    let written = result.to_string();
+    assert!(written.contains(
+        "script = ExtResource(\"1_plr01\")\n\
+         <<<<<<< ours\nspeed = 120.0\n=======\nspeed = 80.0\n>>>>>>> theirs\n"
+    ));
+    assert!(written.ends_with(
+        "<<<<<<< ours\n\
+         [node name=\"Camera\" type=\"Camera2D\" parent=\".\"]\nzoom = Vector2(3, 3)\n\
+         =======\n>>>>>>> theirs\n"
+    ));
+}
+
+#[test]
+fn test_project_merge() {
+    let base = "config_version=5\n\n[application]\n\nconfig/name=\"Game\"\n\n[display]\n\nwindow/size/viewport_width=1280\n";
+    let ours = "config_version=5\n\n[application]\n\nconfig/name=\"Game\"\nconfig/version=\"1.1\"\n\n[display]\n\nwindow/size/viewport_width=1920\n";
+    let theirs = "config_version=5\n\n[application]\n\nconfig/name=\"Game\"\nconfig/version=\"1.2\"\n\n[display]\n\nwindow/size/viewport_width=1280\n\n[input]\n\njump={}\n";
+
+    let result = merge_projects(&project(base), &project(ours), &project(theirs));
+    assert_eq!(result.conflicts.len(), 1);
+    assert_eq!(result.conflicts[0].section, "[application]");
+    assert_eq!(result.conflicts[0].key.as_deref(), Some("config/version"));
+    assert_eq!(
+        result.to_string(),
+        "config_version=5\n\n[application]\n\nconfig/name=\"Game\"\n\
+         <<<<<<< ours\nconfig/version=\"1.1\"\n=======\nconfig/version=\"1.2\"\n>>>>>>> theirs\n\n\
+         [display]\n\nwindow/size/viewport_width=1920\n\n[input]\n\njump={}\n"
+    );
+}
+
+#[test]
+fn test_node_added_under_removed_parent_conflicts() {
+    let ours = BASE.replace(
+        "\n[node name=\"Sprite\" type=\"Sprite2D\" parent=\".\"]\ntexture = ExtResource(\"2_tex01\")\n",
+        "",
+    );
+    let theirs = BASE.replace(
+        "[node name=\"Camera\"",
+        "[node name=\"Shadow\" type=\"Sprite2D\" parent=\"Sprite\"]\n\n[node name=\"Camera\"",
+    );
+
+    let result = merge_scenes(&scene(BASE), &scene(&ours), &scene(&theirs));
+    assert_eq!(result.conflicts.len(), 1);
+    assert_eq!(result.conflicts[0].key, None);
+    assert_eq!(result.conflicts[0].ours, None);
+    assert_eq!(
+        result.conflicts[0].to_string(),
+        "[node name=\"Sprite\" type=\"Sprite2D\" parent=\".\"] was removed on our side and changed on theirs"
+    );
+
+    // The parent stays so the new node is not left without one
+    assert!(result.merged.find_node("Sprite").is_some());
+    assert!(result.merged.find_node("Sprite/Shadow").is_some());
+}
+
+#[test]
+fn test_conflicts_on_repeated_keys() {
+    let base = "[gd_scene format=3]\n\n[node name=\"Main\" type=\"Node\"]\ntag = 1\ntag = 2\n";
+    let ours = base.replace("tag = 2", "tag = 3");
+    let theirs = base.replace("tag = 2", "tag = 4");
+
+    let result = merge_scenes(&scene(base), &scene(&ours), &scene(&theirs));
+    assert_eq!(result.conflicts.len(), 1);
+    assert_eq!(result.conflicts[0].key.as_deref(), Some("tag"));
+    assert!(
+        result
+            .to_string()
+            .ends_with("tag = 1\n<<<<<<< ours\ntag = 3\n=======\ntag = 4\n>>>>>>> theirs\n")
+    );
+}
+
+#[test]
+fn test_large_scenes() {
+    // Each side adds nodes between the ones in the common ancestor
+    let nodes = |count: usize, name: &str| {
+        let mut text = "[gd_scene format=3]\n\n[node name=\"Root\" type=\"Node\"]\n".to_string();
+        for i in 0..count {
+            text.push_str(&format!(
+                "\n[node name=\"Base{}\" type=\"Node\" parent=\".\"]\n",
+                i
+            ));
+            if !name.is_empty() {
+                text.push_str(&format!(
+                    "\n[node name=\"{}{}\" type=\"Node\" parent=\"Base{}\"]\n",
+                    name, i, i
+                ));
+            }
+        }
+        text
+    };
+    let count = 5_000;
+    let (base, ours, theirs) = (
+        nodes(count, ""),
+        nodes(count, "Ours"),
+        nodes(count, "Theirs"),
+    );
+
+    let result = merge_scenes(&scene(&base), &scene(&ours), &scene(&theirs));
+    assert!(result.is_clean(), "{:?}", result.conflicts);
+    let nodes = result.merged.nodes();
+    assert_eq!(nodes.len(), 1 + 3 * count);
+    let names: Vec<_> = nodes[1..4].iter().map(|n| n.get("name").unwrap()).collect();
+    assert_eq!(names, ["Base0", "Ours0", "Theirs0"]);
+}
@@ -0,0 +1,118 @@
+use godot_properties_parser::parse_project_file;
+use godot_properties_parser::parsers::parser_project_file::Duplicate;
+
+const DUPLICATED_PROJECT: &str = r#"config_version=5
+
+[application]
+
+config/name="First"
+run/main_scene="res://main.tscn"
+
+[input]
+
+jump={}
+
+[application]
+
+config/name="Second"
+config/version="1.2.0"
+
+[display]
+
+window/size/viewport_width=1280
+window/size/viewport_width=1920
+"#;
+
+#[test]
+fn test_duplicate_sections_are_merged() {
+    let (_, project) = parse_project_file(DUPLICATED_PROJECT).unwrap();
+    let application = project.application().unwrap();
+
+    assert_eq!(application.properties.len(), 3);
+    assert_eq!(application.properties[0].key, "config/name");
+    assert_eq!(application.properties[0].value, "Second");
+    assert_eq!(application.get("run/main_scene"), Some("res://main.tscn"));
+    assert_eq!(application.get("config/version"), Some("1.2.0"));
+}
+
+#[test]
+fn test_duplicate_keys_use_last_value() {
+    let (_, project) = parse_project_file(DUPLICATED_PROJECT).unwrap();
+    let display = project.display().unwrap();
+
+    assert_eq!(display.properties.len(), 1);
+    assert_eq!(display.get("window/size/viewport_width"), Some("1920"));
+}
+
+#[test]
+fn test_all_sections_keep_duplicates() {
+    let (_, project) = parse_project_file(DUPLICATED_PROJECT).unwrap();
+
+    assert_eq!(project.all_sections.len(), 4);
+    assert_eq!(
+        project.section("application").unwrap().get("config/name"),
+        Some("First")
+    );
+    assert_eq!(
+        project
+            .merged_section("application")
+            .unwrap()
+            .get("config/name"),
+        Some("Second")
+    );
+}
+
+#[test]
+fn test_duplicates_diagnostic() {
+    let (_, project) = parse_project_file(DUPLICATED_PROJECT).unwrap();
+
+    assert_eq!(
+        project.duplicates(),
+        vec![
+            Duplicate::Section {
+                section: "application".to_string(),
+                count: 2,
+            },
+            Duplicate::Key {
+                section: "display".to_string(),
+                key: "window/size/viewport_width".to_string(),
+                count: 2,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_no_duplicates_in_fixture() {
+    let content = include_str!("projects/project_gamblers_table.godot");
+    let (_, project) = parse_project_file(content).unwrap();
+
+    assert!(project.duplicates().is_empty());
+}
+
+#[test]
+fn test_single_sections_are_borrowed() {
+    use std::borrow::Cow;
+
+    let (_, project) = parse_project_file(DUPLICATED_PROJECT).unwrap();
+
+    assert!(matches!(project.application(), Some(Cow::Owned(_))));
+    assert!(matches!(project.display(), Some(Cow::Owned(_))));
+    assert!(project.autoload().is_none());
+
+    let (_, project) = parse_project_file("[autoload]\n\nEvents=\"*res://events.gd\"\n").unwrap();
+    assert!(matches!(project.autoload(), Some(Cow::Borrowed(_))));
+}
+
+#[test]
+fn test_get_uses_last_value() {
+    let (_, project) = parse_project_file(DUPLICATED_PROJECT).unwrap();
+
+    assert_eq!(project.get("application", "config/name"), Some("Second"));
+    assert_eq!(
+        project.get("display", "window/size/viewport_width"),
+        Some("1920")
+    );
+    assert_eq!(project.get("display", "missing"), None);
+    assert_eq!(project.get("missing", "config/name"), None);
+}
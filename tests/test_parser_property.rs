@@ -43,3 +43,34 @@ fn test_multiline_array() {
     assert_eq!(props[0].value, "2300");
     assert_eq!(props[1].key, "something");
 }
+
+#[test]
+fn test_constructor_with_spaces() {
+    let input = r#"node_paths=PackedStringArray("room", "ui") position=Vector2(578, 325)"#;
+    let (remaining, props) = properties0(input).unwrap();
+    assert_eq!(remaining, "");
+    assert_eq!(props.len(), 2);
+    assert_eq!(props[0].key, "node_paths");
+    assert_eq!(props[0].value, r#"PackedStringArray("room", "ui")"#);
+    assert_eq!(props[1].key, "position");
+    assert_eq!(props[1].value, "Vector2(578, 325)");
+}
+
+#[test]
+fn test_keys_with_separators() {
+    let input = "0:0/0 = 0\npath.s3tc=\"res://.godot/imported/icon.s3tc.ctex\"";
+    let (remaining, props) = properties0(input).unwrap();
+    assert_eq!(remaining, "");
+    assert_eq!(props.len(), 2);
+    assert_eq!(props[0].key, "0:0/0");
+    assert_eq!(props[0].value, "0");
+    assert_eq!(props[1].key, "path.s3tc");
+    assert_eq!(props[1].value, "res://.godot/imported/icon.s3tc.ctex");
+}
+
+#[test]
+fn test_stops_at_non_property() {
+    let (remaining, props) = properties0("a=1 ]").unwrap();
+    assert_eq!(remaining, "]");
+    assert_eq!(props.len(), 1);
+}
@@ -1,4 +1,6 @@
-use godot_properties_parser::parse_property_file;
+use godot_properties_parser::{
+    ParseErrorKind, parse_property_file, parse_property_file_bytes, try_parse_property_file,
+};
 
 #[test]
 fn test_parse_simple_section() {
@@ -50,6 +52,21 @@ fn test_parse_section_with_nested_brackets() {
     assert_eq!(file.sections[0].properties.len(), 3);
 }
 
+#[test]
+fn test_parse_dotted_section_names() {
+    let input =
+        "[preset.0]\n\nname=\"Linux\"\n\n[preset.0.options]\n\nbinary_format/embed_pck=false\n";
+    let (remaining, file) = parse_property_file(input).unwrap();
+    assert_eq!(remaining, "");
+    assert_eq!(file.sections[0].header_type, "preset.0");
+    assert_eq!(file.sections[0].attribute_count, 0);
+    assert_eq!(file.sections[1].header_type, "preset.0.options");
+    assert_eq!(
+        file.sections[1].get("binary_format/embed_pck"),
+        Some("false")
+    );
+}
+
 #[test]
 fn test_parse_with_spaces_around_equals() {
     let input = "[ext_resource type = \"Script\" uid = \"uid://test\"]\n";
@@ -60,3 +77,76 @@ fn test_parse_with_spaces_around_equals() {
     assert_eq!(file.sections[0].properties[0].key, "type");
     assert_eq!(file.sections[0].properties[0].value, "Script");
 }
+
+#[test]
+fn test_results_borrow_from_input() {
+    use std::borrow::Cow;
+
+    let input = "[node name=\"Root\" type=\"Node2D\"]\nposition = Vector2(1, 2)\ntext = \"a\nb\"\n";
+    let (_, file) = parse_property_file(input).unwrap();
+    let section = &file.sections[0];
+
+    assert!(matches!(section.header_type, Cow::Borrowed("node")));
+    assert!(matches!(
+        section.properties[2].key,
+        Cow::Borrowed("position")
+    ));
+    assert!(matches!(
+        section.properties[2].value,
+        Cow::Borrowed("Vector2(1, 2)")
+    ));
+    assert!(matches!(section.properties[3].value, Cow::Borrowed("a\nb")));
+}
+
+#[test]
+fn test_into_owned_outlives_input() {
+    let file = {
+        let input = String::from("config_version=5\n\n[application]\nconfig/name=\"Game\"\n");
+        parse_property_file(&input).unwrap().1.into_owned()
+    };
+
+    assert_eq!(file.preamble_properties[0].key, "config_version");
+    assert_eq!(file.sections[0].get("config/name"), Some("Game"));
+}
+
+#[test]
+fn test_parse_errors() {
+    let error = |input: &str| {
+        let error = try_parse_property_file(input).unwrap_err();
+        (error.kind, error.location.line, error.location.column)
+    };
+
+    assert_eq!(
+        error("[gd_scene format=3]\n\n[]\n"),
+        (ParseErrorKind::MissingSectionName, 3, 2)
+    );
+    assert_eq!(
+        error("[node name=\"A\" type=\"Node\"\nvisible = false\n"),
+        (ParseErrorKind::UnclosedHeader, 1, 27)
+    );
+    assert_eq!(
+        error("[node name=\"A\" parent=Vector2(1, 2\n"),
+        (ParseErrorKind::UnclosedHeader, 2, 1)
+    );
+    assert_eq!(
+        error("[node name=\"Broken]\n\n[node name=\"B\"]\n"),
+        (ParseErrorKind::UnterminatedString, 3, 14)
+    );
+    assert_eq!(
+        error("[node name=\"A\"]\ntext = \"never closed\nvisible = false\n"),
+        (ParseErrorKind::UnterminatedString, 2, 8)
+    );
+
+    let input = "[application]\nconfig/name=\"Game\"\n[broken\n";
+    let (remaining, file) = parse_property_file(input).unwrap();
+    assert_eq!(remaining, "[broken\n");
+    assert_eq!(file.sections.len(), 1);
+
+    let parsed = parse_property_file_bytes(input.as_bytes());
+    assert_eq!(parsed.value.sections.len(), 1);
+    assert_eq!(parsed.remaining, "[broken\n");
+    assert_eq!(
+        parsed.error.unwrap().to_string(),
+        "3:8: section header is not closed with `]`"
+    );
+}
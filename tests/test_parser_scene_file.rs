@@ -13,13 +13,13 @@ fn test_parse_scene_file_basic() {
     assert_eq!(remaining, "");
 
     // Check header
-    assert!(scene.header.is_some());
-    assert_eq!(scene.header.as_ref().unwrap().header_type, "gd_scene");
+    assert!(scene.header().is_some());
+    assert_eq!(scene.header().unwrap().header_type, "gd_scene");
 
     // Check categorized sections
-    assert_eq!(scene.ext_resources.len(), 1);
-    assert_eq!(scene.nodes.len(), 1);
-    assert_eq!(scene.connections.len(), 1);
+    assert_eq!(scene.ext_resources().len(), 1);
+    assert_eq!(scene.nodes().len(), 1);
+    assert_eq!(scene.connections().len(), 1);
 
     // Check all_sections contains everything
     assert_eq!(scene.all_sections.len(), 4);
@@ -38,9 +38,9 @@ fn test_scene_file_multiple_resources() {
 "#;
     let (_, scene) = parse_scene_file(input).unwrap();
 
-    assert_eq!(scene.ext_resources.len(), 2);
-    assert_eq!(scene.sub_resources.len(), 2);
-    assert_eq!(scene.nodes.len(), 2);
+    assert_eq!(scene.ext_resources().len(), 2);
+    assert_eq!(scene.sub_resources().len(), 2);
+    assert_eq!(scene.nodes().len(), 2);
     assert_eq!(scene.all_sections.len(), 7);
 }
 
@@ -51,30 +51,18 @@ fn test_scene_file_from_property_file() {
     let property_file = PropertyFile {
         preamble_properties: vec![],
         sections: vec![
-            Section {
-                header_type: "gd_scene".to_string(),
-                properties: vec![],
-            },
-            Section {
-                header_type: "ext_resource".to_string(),
-                properties: vec![],
-            },
-            Section {
-                header_type: "node".to_string(),
-                properties: vec![],
-            },
-            Section {
-                header_type: "custom_section".to_string(),
-                properties: vec![],
-            },
+            Section::new("gd_scene"),
+            Section::new("ext_resource"),
+            Section::new("node"),
+            Section::new("custom_section"),
         ],
     };
 
     let scene = SceneFile::from_property_file(property_file);
 
-    assert!(scene.header.is_some());
-    assert_eq!(scene.ext_resources.len(), 1);
-    assert_eq!(scene.nodes.len(), 1);
+    assert!(scene.header().is_some());
+    assert_eq!(scene.ext_resources().len(), 1);
+    assert_eq!(scene.nodes().len(), 1);
     assert_eq!(scene.all_sections.len(), 4);
 
     // Custom section should only be in all_sections
@@ -92,9 +80,9 @@ fn test_scene_file_no_header() {
 "#;
     let (_, scene) = parse_scene_file(input).unwrap();
 
-    assert!(scene.header.is_none());
-    assert_eq!(scene.ext_resources.len(), 1);
-    assert_eq!(scene.nodes.len(), 1);
+    assert!(scene.header().is_none());
+    assert_eq!(scene.ext_resources().len(), 1);
+    assert_eq!(scene.nodes().len(), 1);
     assert_eq!(scene.all_sections.len(), 2);
 }
 
@@ -103,6 +91,18 @@ fn test_scene_file_gd_resource() {
     let input = "[gd_resource type=\"Resource\" format=3]\n";
     let (_, scene) = parse_scene_file(input).unwrap();
 
-    assert!(scene.header.is_some());
-    assert_eq!(scene.header.as_ref().unwrap().header_type, "gd_resource");
+    assert!(scene.header().is_some());
+    assert_eq!(scene.header().unwrap().header_type, "gd_resource");
+}
+
+#[test]
+fn test_scene_file_is_written_back() {
+    let input = "[gd_scene load_steps=2 format=3]\n\n[ext_resource type=\"Script\" path=\"res://main.gd\" id=\"1\"]\n\n[node name=\"Main\" type=\"Node2D\"]\nscript = ExtResource(\"1\")\ntext = \"a \\\"quoted\\\" word\"\n";
+    let (_, scene) = parse_scene_file(input).unwrap();
+
+    let node = &scene.all_sections[2];
+    assert_eq!(node.attributes().len(), 2);
+    assert_eq!(node.body()[1].value, "a \\\"quoted\\\" word");
+    assert!(node.body()[1].quoted);
+    assert_eq!(scene.to_string(), input);
 }
@@ -0,0 +1,123 @@
+use godot_properties_parser::parsers::parser_value::Value;
+
+#[test]
+fn test_scalars() {
+    assert_eq!(Value::parse("null"), Some(Value::Nil));
+    assert_eq!(Value::parse("true"), Some(Value::Bool(true)));
+    assert_eq!(Value::parse("42"), Some(Value::Int(42)));
+    assert_eq!(Value::parse("-7"), Some(Value::Int(-7)));
+    assert_eq!(Value::parse("0.5"), Some(Value::Float(0.5)));
+    assert_eq!(Value::parse("1e+06"), Some(Value::Float(1e6)));
+    assert_eq!(Value::parse("-inf"), Some(Value::Float(f64::NEG_INFINITY)));
+}
+
+#[test]
+fn test_strings() {
+    assert_eq!(
+        Value::parse(r#""say \"hi\"\n""#),
+        Some(Value::String("say \"hi\"\n".to_string()))
+    );
+    assert_eq!(
+        Value::parse(r#"&"idle""#),
+        Some(Value::StringName("idle".to_string()))
+    );
+    assert_eq!(
+        Value::parse(r#"^"../UI""#),
+        Some(Value::NodePath("../UI".to_string()))
+    );
+    assert_eq!(
+        Value::parse(r#"NodePath("../UI")"#),
+        Some(Value::NodePath("../UI".to_string()))
+    );
+}
+
+#[test]
+fn test_constructors() {
+    assert_eq!(
+        Value::parse("Vector2(578, 325)"),
+        Some(Value::Constructor {
+            name: "Vector2".to_string(),
+            args: vec![Value::Int(578), Value::Int(325)],
+        })
+    );
+    assert_eq!(
+        Value::parse(r#"ExtResource("5_758co")"#)
+            .unwrap()
+            .as_ext_resource(),
+        Some("5_758co")
+    );
+    assert_eq!(
+        Value::parse(r#"SubResource("Gradient_2r2rb")"#)
+            .unwrap()
+            .as_sub_resource(),
+        Some("Gradient_2r2rb")
+    );
+}
+
+#[test]
+fn test_arrays() {
+    let value = Value::parse(r#"PackedStringArray("room", "ui")"#).unwrap();
+    assert_eq!(value.as_string_array(), Some(vec!["room", "ui"]));
+
+    let value = Value::parse(r#"["enemies", "damageable",]"#).unwrap();
+    assert_eq!(value.as_string_array(), Some(vec!["enemies", "damageable"]));
+
+    let value = Value::parse(r#"Array[ExtResource("2_dfemt")]([ExtResource("7_3polh")])"#).unwrap();
+    assert_eq!(value.as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_dictionary_with_object() {
+    let input = r#"{
+"deadzone": 0.5,
+"events": [Object(InputEventKey,"resource_local_to_scene":false,"keycode":4194320)
+]
+}"#;
+    let value = Value::parse(input).unwrap();
+    let Value::Dictionary(entries) = value else {
+        panic!("expected a dictionary");
+    };
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].1, Value::Float(0.5));
+
+    let events = entries[1].1.as_array().unwrap();
+    let Value::Object { class, properties } = &events[0] else {
+        panic!("expected an object");
+    };
+    assert_eq!(class, "InputEventKey");
+    assert_eq!(properties[1], ("keycode".to_string(), Value::Int(4194320)));
+}
+
+#[test]
+fn test_nested_node_paths() {
+    let value = Value::parse(r#"[NodePath("A"), NodePath("../B")]"#).unwrap();
+    assert_eq!(value.node_paths(), vec!["A", "../B"]);
+}
+
+#[test]
+fn test_invalid_values() {
+    assert_eq!(Value::parse("Vector2(1, 2"), None);
+    assert_eq!(Value::parse("\"unterminated"), None);
+    assert_eq!(Value::parse("1 2"), None);
+}
+
+#[test]
+fn test_values_are_written_in_godot_format() {
+    for text in [
+        "null",
+        "-7",
+        "0.5",
+        "1e-05",
+        "1.5e+16",
+        "-inf",
+        "\"say \\\"hi\\\"\"",
+        "&\"room\"",
+        "NodePath(\"../Label\")",
+        "[1, \"two\", Vector2(3, 4.5)]",
+        "{\n\"a\": 1,\n\"b\": [Object(InputEventKey,\"keycode\":65)\n]\n}",
+        "Array[int]([1, 2])",
+    ] {
+        let value = Value::parse(text).unwrap();
+        assert_eq!(value.to_string(), text);
+    }
+}
@@ -0,0 +1,99 @@
+use godot_properties_parser::project::{ParsedContent, ProjectError};
+use godot_properties_parser::{ParseErrorKind, parse_project_dir};
+use std::fs;
+
+const SAMPLE_PROJECT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/projects/sample_project");
+
+#[test]
+fn test_discovers_property_files() {
+    let parsed = parse_project_dir(SAMPLE_PROJECT).unwrap();
+    assert!(parsed.is_ok());
+
+    let paths: Vec<_> = parsed.files.keys().map(String::as_str).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "res://autoload/game_state.tscn",
+            "res://export_presets.cfg",
+            "res://icon.svg.import",
+            "res://levels/main.tscn",
+            "res://player/player.tscn",
+            "res://project.godot",
+            "res://unused/cycle_a.tscn",
+            "res://unused/cycle_b.tscn",
+            "res://weapons/sword.tres",
+        ]
+    );
+}
+
+#[test]
+fn test_files_are_parsed_by_type() {
+    let parsed = parse_project_dir(SAMPLE_PROJECT).unwrap();
+
+    let project = parsed
+        .get("res://project.godot")
+        .unwrap()
+        .as_project()
+        .unwrap();
+    assert!(project.get("application", "run/main_scene").is_some());
+
+    let scene = parsed.get("res://player/player.tscn").unwrap();
+    assert!(matches!(scene.content, ParsedContent::Scene(_)));
+    assert_eq!(parsed.scenes().count(), 6);
+
+    let presets = parsed.get("res://export_presets.cfg").unwrap();
+    let presets = presets.as_properties().unwrap();
+    assert_eq!(presets.sections[0].get("platform"), Some("Linux"));
+
+    let import = parsed.get("res://icon.svg.import").unwrap();
+    let remap = &import.as_properties().unwrap().sections[0];
+    assert_eq!(remap.get("uid"), Some("uid://d3icon0svg"));
+}
+
+#[test]
+fn test_errors_are_collected_per_file() {
+    let root = std::env::temp_dir().join(format!("gpp_project_dir_{}", std::process::id()));
+    fs::create_dir_all(root.join("levels")).unwrap();
+    fs::write(root.join("project.godot"), "config_version=5\n").unwrap();
+    fs::write(
+        root.join("levels/good.tscn"),
+        "[gd_scene format=3]\n\n[node name=\"Root\" type=\"Node\"]\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("levels/bad.tscn"),
+        "[gd_scene format=3]\n\n[node name=\"Root\"\nvisible = false\n",
+    )
+    .unwrap();
+
+    let parsed = parse_project_dir(&root);
+    fs::remove_dir_all(&root).unwrap();
+    let parsed = parsed.unwrap();
+
+    assert!(!parsed.is_ok());
+    assert_eq!(parsed.errors.len(), 1);
+    match &parsed.errors[0] {
+        ProjectError::Parse { path, error } => {
+            assert!(path.ends_with("levels/bad.tscn"));
+            assert_eq!(error.kind, ParseErrorKind::UnclosedHeader);
+            assert_eq!(
+                error.to_string(),
+                "3:18: section header is not closed with `]`"
+            );
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+
+    // The other files are still parsed
+    assert!(parsed.get("res://levels/good.tscn").is_some());
+    assert!(parsed.get("res://project.godot").is_some());
+}
+
+#[test]
+fn test_missing_directory_fails() {
+    let result = parse_project_dir(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/projects/missing"
+    ));
+    assert!(matches!(result, Err(ProjectError::Io { .. })));
+}
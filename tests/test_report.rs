@@ -0,0 +1,99 @@
+use std::path::Path;
+
+use godot_properties_parser::lint::{Diagnostic, Severity};
+use godot_properties_parser::parsers::lexer::Span;
+use godot_properties_parser::parsers::source_map::{Position, SourceMap};
+use godot_properties_parser::report::{PARSE_ERROR_RULE, Report, parse_diagnostics};
+use godot_properties_parser::{parse_project_file, parse_property_file, parse_scene_file};
+
+#[test]
+fn test_parse_failures_point_to_where_parsing_stopped() {
+    let source = "[gd_scene format=3]\n\n[node name=\"Main\" type=\"Node\"]\nspeed = 2\n\n  [node name=\"Ünïcode\" \n";
+    let diagnostics = parse_diagnostics(source, &parse_scene_file(source));
+    assert_eq!(diagnostics.len(), 1);
+    let diagnostic = &diagnostics[0];
+    assert_eq!(diagnostic.rule, PARSE_ERROR_RULE);
+    assert_eq!(diagnostic.severity, Severity::Error);
+    assert_eq!(
+        diagnostic.message,
+        "section header is not closed with `]`, the rest of the file is ignored"
+    );
+    assert_eq!(
+        (diagnostic.location.line, diagnostic.location.column),
+        (6, 3)
+    );
+    assert_eq!(
+        &source[diagnostic.location.span.start..diagnostic.location.span.end],
+        "[node name=\"Ünïcode\""
+    );
+
+    let project = "config_version=5\n\n[application]\nconfig/name=\"Game\"\n";
+    assert!(parse_diagnostics(project, &parse_project_file(project)).is_empty());
+
+    let diagnostic = |source: &str| {
+        let diagnostics = parse_diagnostics(source, &parse_property_file(source));
+        let diagnostic = diagnostics.into_iter().next().unwrap();
+        let span = diagnostic.location.span;
+        (
+            diagnostic.to_string(),
+            source[span.start..span.end].to_string(),
+        )
+    };
+    assert_eq!(
+        diagnostic("[preset.0]\n\nname=\"Linux\"\n[]\n"),
+        (
+            "4:1: error[parse-error]: section header has no name, the rest of the file is ignored"
+                .to_string(),
+            "[]".to_string()
+        )
+    );
+    assert_eq!(
+        diagnostic("[preset.0]\nname=\"Linux\"\npath=\"C:\\\"\n"),
+        (
+            "3:6: error[parse-error]: string is not closed before the end of the file, the rest of the file is ignored"
+                .to_string(),
+            "\"C:\\\"".to_string()
+        )
+    );
+}
+
+#[test]
+fn test_junit() {
+    let source = "[gd_scene format=3]\n\n[node name=\"A & B\" type=\"Node\"]\n";
+    let mut report = Report::new("game");
+    report.add(
+        Path::new("./game/levels/a&b.tscn"),
+        source,
+        vec![Diagnostic {
+            rule: "node-name".to_string(),
+            severity: Severity::Warning,
+            message: "name \"A & B\" has spaces".to_string(),
+            location: SourceMap::new(source).locate(Span::new(21, 52)),
+        }],
+    );
+    report.add(
+        Path::new("game/project.godot"),
+        "config_version=5\n",
+        Vec::new(),
+    );
+
+    let file = &report.files()[0];
+    assert_eq!(file.path, "levels/a&b.tscn");
+    assert_eq!(file.entries[0].end, Position::new(3, 32));
+    assert!(!report.has_errors());
+    assert_eq!(
+        report.to_junit(),
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="godot-properties-parser" tests="2" failures="1">
+  <testsuite name="levels/a&amp;b.tscn" tests="1" failures="1" errors="0" skipped="0">
+    <testcase name="node-name" classname="levels/a&amp;b.tscn">
+      <failure type="warning" message="name &quot;A &amp; B&quot; has spaces">levels/a&amp;b.tscn:3:1: warning[node-name]: name &quot;A &amp; B&quot; has spaces</failure>
+    </testcase>
+  </testsuite>
+  <testsuite name="project.godot" tests="1" failures="0" errors="0" skipped="0">
+    <testcase name="project.godot" classname="project.godot"/>
+  </testsuite>
+</testsuites>
+"#
+    );
+}
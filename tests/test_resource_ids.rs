@@ -0,0 +1,120 @@
+use godot_properties_parser::parse_scene_file;
+
+const MERGED: &str = r#"[gd_scene load_steps=9 format=3]
+
+[ext_resource type="Script" path="res://player.gd" id="1_aaaaa"]
+[ext_resource type="Texture2D" path="res://player.png" id="1_aaaaa"]
+[ext_resource type="Texture2D" path="res://shadow.png" id="7"]
+
+[sub_resource type="Gradient" id="1"]
+colors = PackedColorArray(0, 0, 0, 1, 1, 1, 1, 1)
+
+[sub_resource type="GradientTexture1D" id="GradientTexture1D_x1ab2"]
+gradient = SubResource("1")
+
+[sub_resource type="Gradient" id="1"]
+colors = PackedColorArray(1, 0, 0, 1, 0, 0, 1, 1)
+
+[node name="Player" type="Sprite2D"]
+texture = ExtResource("1_aaaaa")
+gradient = SubResource("1")
+
+[node name="Shadow" type="Sprite2D" parent="."]
+texture = ExtResource("7")
+material = SubResource("GradientTexture1D_x1ab2")
+"#;
+
+#[test]
+fn test_ext_resources_are_numbered_in_file_order() {
+    let (_, mut scene) = parse_scene_file(MERGED).unwrap();
+    scene.renumber_resources();
+
+    let ids: Vec<_> = scene
+        .ext_resources()
+        .iter()
+        .map(|ext| ext.get("id").unwrap().to_string())
+        .collect();
+    assert_eq!(ids[0], "1_aaaaa");
+    assert_eq!(ids[1], "2_aaaaa");
+    assert!(ids[2].starts_with("3_") && ids[2].len() == 7, "{}", ids[2]);
+
+    // The repeated id refers to its latest declaration
+    assert_eq!(
+        scene.find_node(".").unwrap().get("texture"),
+        Some("ExtResource(\"2_aaaaa\")")
+    );
+    assert_eq!(
+        scene.find_node("Shadow").unwrap().get("texture"),
+        Some(format!("ExtResource(\"{}\")", ids[2]).as_str())
+    );
+}
+
+#[test]
+fn test_repeated_sub_resource_ids_are_replaced() {
+    let (_, mut scene) = parse_scene_file(MERGED).unwrap();
+    scene.renumber_resources();
+
+    let subs = scene.sub_resources();
+    let ids: Vec<_> = subs.iter().map(|sub| sub.get("id").unwrap()).collect();
+    assert!(ids[0].starts_with("Gradient_"));
+    assert_eq!(ids[1], "GradientTexture1D_x1ab2");
+    assert!(ids[2].starts_with("Gradient_"));
+    assert_ne!(ids[0], ids[2]);
+
+    // Each reference keeps pointing at the gradient declared before it
+    assert_eq!(
+        subs[1].get("gradient"),
+        Some(format!("SubResource(\"{}\")", ids[0]).as_str())
+    );
+    assert_eq!(
+        scene.find_node(".").unwrap().get("gradient"),
+        Some(format!("SubResource(\"{}\")", ids[2]).as_str())
+    );
+
+    // Renumbering again changes nothing
+    let once = scene.to_string();
+    scene.renumber_resources();
+    assert_eq!(scene.to_string(), once);
+}
+
+#[test]
+fn test_load_steps() {
+    let (_, mut scene) = parse_scene_file(MERGED).unwrap();
+    scene.update_load_steps();
+    assert_eq!(scene.header().unwrap().get("load_steps"), Some("7"));
+
+    let (_, mut scene) =
+        parse_scene_file("[gd_scene load_steps=3 format=3]\n\n[node name=\"A\" type=\"Node\"]\n")
+            .unwrap();
+    scene.update_load_steps();
+    assert_eq!(scene.header().unwrap().to_string(), "[gd_scene format=3]\n");
+
+    let (_, mut resource) = parse_scene_file(
+        "[gd_resource type=\"Theme\" format=3]\n\n\
+         [sub_resource type=\"StyleBoxFlat\" id=\"StyleBoxFlat_a1b2c\"]\n\n\
+         [resource]\nPanel/styles/panel = SubResource(\"StyleBoxFlat_a1b2c\")\n",
+    )
+    .unwrap();
+    resource.update_load_steps();
+    assert_eq!(
+        resource.header().unwrap().to_string(),
+        "[gd_resource type=\"Theme\" load_steps=2 format=3]\n"
+    );
+}
+
+#[test]
+fn test_typed_array_parameters_are_renamed() {
+    let (_, mut scene) = parse_scene_file(
+        "[gd_scene format=3]\n\n\
+         [ext_resource type=\"Script\" path=\"res://item.gd\" id=\"4_item\"]\n\
+         [ext_resource type=\"Resource\" path=\"res://sword.tres\" id=\"9_sword\"]\n\n\
+         [node name=\"Shop\" type=\"Node\"]\n\
+         items = Array[ExtResource(\"4_item\")]([ExtResource(\"9_sword\")])\n",
+    )
+    .unwrap();
+    scene.renumber_resources();
+    assert_eq!(
+        scene.find_node(".").unwrap().get("items"),
+        Some("Array[ExtResource(\"1_item\")]([ExtResource(\"2_sword\")])")
+    );
+}
@@ -0,0 +1,294 @@
+use godot_properties_parser::parse_scene_file;
+use godot_properties_parser::parsers::parser_property::UntypedProperty;
+use godot_properties_parser::scene::SceneEditError;
+
+const LEVEL: &str = r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="PackedScene" path="res://player.tscn" id="1_player"]
+
+[node name="Level" type="Node2D"]
+
+[node name="UI" type="CanvasLayer" parent="."]
+
+[node name="Panel" type="Panel" parent="UI"]
+unique_name_in_owner = true
+
+[node name="Title" type="Label" parent="UI/Panel"]
+
+[node name="Follow" type="RemoteTransform2D" parent="UI/Panel"]
+remote_path = NodePath("../../../Camera")
+
+[node name="Player" parent="." instance=ExtResource("1_player")]
+
+[node name="Camera" type="Camera2D" parent="." node_paths=PackedStringArray("target", "labels")]
+target = NodePath("../Player")
+labels = [NodePath("%Panel/Title:text"), NodePath("../UI/Panel")]
+
+[editable path="Player"]
+
+[connection signal="hit" from="Player" to="UI/Panel" method="_on_hit"]
+[connection signal="resized" from="UI/Panel/Title" to="." method="_on_title_resized"]
+"#;
+
+fn level() -> godot_properties_parser::parsers::parser_scene_file::SceneFile<'static> {
+    parse_scene_file(LEVEL).unwrap().1.into_owned()
+}
+
+#[test]
+fn test_add_node() {
+    let mut scene = level();
+    let node = scene.add_node("UI", "Score", "Label").unwrap();
+    node.properties
+        .push(UntypedProperty::new_quoted("text", "0"));
+
+    // Inserted after the last node below UI
+    let nodes: Vec<_> = scene
+        .nodes()
+        .iter()
+        .map(|n| n.get("name").unwrap())
+        .collect();
+    assert_eq!(
+        nodes,
+        [
+            "Level", "UI", "Panel", "Title", "Follow", "Score", "Player", "Camera"
+        ]
+    );
+    assert!(scene.to_string().contains(
+        "[node name=\"Follow\" type=\"RemoteTransform2D\" parent=\"UI/Panel\"]\nremote_path = NodePath(\"../../../Camera\")\n\n[node name=\"Score\""
+    ));
+    assert_eq!(
+        scene.find_node("UI/Score").unwrap().to_string(),
+        "[node name=\"Score\" type=\"Label\" parent=\"UI\"]\ntext = \"0\"\n"
+    );
+
+    // Nodes inside an instanced scene can be parents too
+    assert!(scene.add_node("Player/Sprite", "Hat", "Sprite2D").is_ok());
+    assert_eq!(
+        scene.add_node("UI", "Panel", "Panel").unwrap_err(),
+        SceneEditError::NameTaken {
+            parent: "UI".to_string(),
+            name: "Panel".to_string()
+        }
+    );
+    assert_eq!(
+        scene.add_node("Missing", "A", "Node").unwrap_err(),
+        SceneEditError::NodeNotFound("Missing".to_string())
+    );
+    assert_eq!(
+        scene.add_node(".", "A/B", "Node").unwrap_err(),
+        SceneEditError::InvalidName("A/B".to_string())
+    );
+}
+
+#[test]
+fn test_remove_node() {
+    let mut scene = level();
+    let removed = scene.remove_node("UI/Panel").unwrap();
+
+    let removed: Vec<_> = removed.iter().map(|s| s.header_type.as_ref()).collect();
+    assert_eq!(
+        removed,
+        ["node", "node", "node", "connection", "connection"]
+    );
+    assert!(scene.find_node("UI").is_some());
+    assert!(scene.find_node("UI/Panel/Title").is_none());
+    assert!(scene.connections().is_empty());
+
+    // References from elsewhere are left behind and reported
+    let broken: Vec<_> = scene
+        .broken_node_paths()
+        .into_iter()
+        .map(|b| b.path)
+        .collect();
+    assert_eq!(broken, ["%Panel/Title:text", "../UI/Panel"]);
+
+    scene.remove_node("Player").unwrap();
+    assert!(scene.editables().is_empty());
+
+    assert_eq!(
+        scene.remove_node(".").unwrap_err(),
+        SceneEditError::RootNode
+    );
+    assert_eq!(
+        scene.remove_node("Player").unwrap_err(),
+        SceneEditError::NodeNotFound("Player".to_string())
+    );
+}
+
+#[test]
+fn test_rename_node() {
+    let mut scene = level();
+    assert_eq!(scene.rename_node("UI/Panel", "Hud").unwrap(), "UI/Hud");
+
+    assert_eq!(
+        scene.find_node("UI/Hud/Title").unwrap().get("parent"),
+        Some("UI/Hud")
+    );
+    assert!(scene.find_node("UI/Hud/Follow").is_some());
+    let connections: Vec<_> = scene
+        .connections()
+        .iter()
+        .map(|c| (c.get("from").unwrap(), c.get("to").unwrap()))
+        .collect();
+    assert_eq!(connections, [("Player", "UI/Hud"), ("UI/Hud/Title", ".")]);
+
+    // The unique name follows the rename, the relative path is rewritten
+    let camera = scene.find_node("Camera").unwrap();
+    assert_eq!(
+        camera.get("labels"),
+        Some("[NodePath(\"%Hud/Title:text\"), NodePath(\"../UI/Hud\")]")
+    );
+    assert_eq!(camera.get("target"), Some("NodePath(\"../Player\")"));
+    assert!(scene.broken_node_paths().is_empty());
+
+    scene.rename_node("Player", "Hero").unwrap();
+    assert_eq!(scene.editables()[0].get("path"), Some("Hero"));
+    assert_eq!(
+        scene.find_node("Camera").unwrap().get("target"),
+        Some("NodePath(\"../Hero\")")
+    );
+
+    assert_eq!(scene.rename_node(".", "World").unwrap(), ".");
+    assert_eq!(scene.find_node(".").unwrap().get("name"), Some("World"));
+    assert_eq!(
+        scene.rename_node("UI/Hud", "Bad:Name").unwrap_err(),
+        SceneEditError::InvalidName("Bad:Name".to_string())
+    );
+    assert_eq!(
+        scene.rename_node("Camera", "UI").unwrap_err(),
+        SceneEditError::NameTaken {
+            parent: ".".to_string(),
+            name: "UI".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_renamed_attributes_are_escaped() {
+    let mut scene = level();
+    assert_eq!(
+        scene.rename_node("UI", "Back\\slash").unwrap(),
+        "Back\\slash"
+    );
+    let written = scene.to_string();
+    assert!(written.contains("[node name=\"Back\\\\slash\" type=\"CanvasLayer\" parent=\".\"]"));
+    assert!(written.contains("[node name=\"Panel\" type=\"Panel\" parent=\"Back\\\\slash\"]"));
+
+    // Renaming the child again keeps a single level of escaping in its parent path
+    scene.rename_node("Back\\slash/Panel", "Hud").unwrap();
+    assert_eq!(
+        scene
+            .find_node("Back\\slash/Hud/Title")
+            .unwrap()
+            .get("parent"),
+        Some("Back\\\\slash/Hud")
+    );
+
+    // A header whose attribute count is out of range is clamped rather than panicking
+    let root = scene.find_node_mut(".").unwrap();
+    root.attribute_count = 10;
+    scene.rename_node(".", "World").unwrap();
+    assert_eq!(scene.find_node(".").unwrap().get("name"), Some("World"));
+
+    // Connections to escaped paths are removed with the node
+    let removed = scene.remove_node("Back\\slash").unwrap();
+    assert_eq!(removed.len(), 6);
+    assert!(scene.connections().is_empty());
+}
+
+#[test]
+fn test_moved_node_paths_keep_the_rest_of_the_value() {
+    let (_, mut scene) = parse_scene_file(
+        r#"[gd_scene format=3]
+
+[node name="Level" type="Node2D"]
+
+[node name="UI" type="CanvasLayer" parent="."]
+
+[node name="Camera" type="Camera2D" parent="."]
+path_data = {
+"speed": 1.50,
+"seed": 12345678901234567890,
+"targets": [^"../UI", NodePath( "../UI:offset" ), NodePath("../Camera")]
+}
+"#,
+    )
+    .unwrap();
+
+    scene.rename_node("UI", "Hud").unwrap();
+    assert_eq!(
+        scene.find_node("Camera").unwrap().get("path_data"),
+        Some(
+            "{\n\"speed\": 1.50,\n\"seed\": 12345678901234567890,\n\"targets\": [^\"../Hud\", NodePath( \"../Hud:offset\" ), NodePath(\"../Camera\")]\n}"
+        )
+    );
+}
+
+#[test]
+fn test_reparent_node() {
+    let mut scene = level();
+    assert_eq!(
+        scene.reparent_node("Camera", "UI/Panel").unwrap(),
+        "UI/Panel/Camera"
+    );
+
+    let camera = scene.find_node("UI/Panel/Camera").unwrap();
+    assert_eq!(camera.get("parent"), Some("UI/Panel"));
+    assert_eq!(camera.get("target"), Some("NodePath(\"../../../Player\")"));
+    assert_eq!(
+        camera.get("labels"),
+        Some("[NodePath(\"%Panel/Title:text\"), NodePath(\"..\")]")
+    );
+    assert_eq!(
+        scene
+            .find_node("UI/Panel/Follow")
+            .unwrap()
+            .get("remote_path"),
+        Some("NodePath(\"../Camera\")")
+    );
+    assert!(scene.broken_node_paths().is_empty());
+
+    // Parents stay ahead of their children
+    let paths: Vec<_> = scene
+        .nodes()
+        .iter()
+        .map(|n| godot_properties_parser::scene::node_path(n).unwrap())
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            ".",
+            "UI",
+            "UI/Panel",
+            "UI/Panel/Title",
+            "UI/Panel/Follow",
+            "UI/Panel/Camera",
+            "Player"
+        ]
+    );
+
+    // Moving a subtree updates the descendants and connections
+    scene.reparent_node("UI/Panel", ".").unwrap();
+    assert_eq!(
+        scene.find_node("Panel/Title").unwrap().get("parent"),
+        Some("Panel")
+    );
+    assert_eq!(scene.connections()[1].get("from"), Some("Panel/Title"));
+
+    // The result is a valid scene that parses back the same
+    let written = scene.to_string();
+    let (_, reparsed) = parse_scene_file(&written).unwrap();
+    assert_eq!(reparsed, scene);
+
+    assert_eq!(
+        scene.reparent_node("Panel", "Panel/Title").unwrap_err(),
+        SceneEditError::MoveIntoSubtree {
+            path: "Panel".to_string(),
+            new_parent: "Panel/Title".to_string()
+        }
+    );
+    assert_eq!(
+        scene.reparent_node(".", "UI").unwrap_err(),
+        SceneEditError::RootNode
+    );
+}
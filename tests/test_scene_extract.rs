@@ -0,0 +1,168 @@
+use godot_properties_parser::parse_scene_file;
+use godot_properties_parser::scene::{BoundaryReference, SceneEditError};
+
+const LEVEL: &str = r#"[gd_scene load_steps=6 format=3 uid="uid://c4level"]
+
+[ext_resource type="Script" path="res://level.gd" id="1_lvl01"]
+[ext_resource type="Texture2D" path="res://icon.svg" id="2_icon0"]
+[ext_resource type="Script" path="res://health_bar.gd" id="3_hbar0"]
+
+[sub_resource type="StyleBoxFlat" id="StyleBoxFlat_bg001"]
+bg_color = Color(0, 0, 0, 0.5)
+
+[sub_resource type="Theme" id="Theme_ui001"]
+Panel/styles/panel = SubResource("StyleBoxFlat_bg001")
+
+[node name="Level" type="Node2D"]
+script = ExtResource("1_lvl01")
+
+[node name="Logo" type="Sprite2D" parent="."]
+texture = ExtResource("2_icon0")
+
+[node name="UI" type="CanvasLayer" parent="."]
+
+[node name="HealthBar" type="Panel" parent="UI"]
+unique_name_in_owner = true
+theme = SubResource("Theme_ui001")
+script = ExtResource("3_hbar0")
+
+[node name="Icon" type="TextureRect" parent="UI/HealthBar"]
+texture = ExtResource("2_icon0")
+
+[node name="Label" type="Label" parent="UI/HealthBar/Icon"]
+
+[connection signal="resized" from="UI/HealthBar/Icon" to="UI/HealthBar" method="_on_icon_resized"]
+[connection signal="ready" from="." to="UI/HealthBar" method="_on_level_ready"]
+"#;
+
+#[test]
+fn test_extracted_scene() {
+    let (_, mut level) = parse_scene_file(LEVEL).unwrap();
+    let branch = level
+        .extract_subtree("UI/HealthBar", "res://ui/health_bar.tscn")
+        .unwrap();
+
+    assert_eq!(
+        branch.scene.to_string(),
+        r#"[gd_scene load_steps=5 format=3]
+
+[ext_resource type="Texture2D" path="res://icon.svg" id="1_icon0"]
+
+[ext_resource type="Script" path="res://health_bar.gd" id="2_hbar0"]
+
+[sub_resource type="StyleBoxFlat" id="StyleBoxFlat_bg001"]
+bg_color = Color(0, 0, 0, 0.5)
+
+[sub_resource type="Theme" id="Theme_ui001"]
+Panel/styles/panel = SubResource("StyleBoxFlat_bg001")
+
+[node name="HealthBar" type="Panel"]
+unique_name_in_owner = true
+theme = SubResource("Theme_ui001")
+script = ExtResource("2_hbar0")
+
+[node name="Icon" type="TextureRect" parent="."]
+texture = ExtResource("1_icon0")
+
+[node name="Label" type="Label" parent="Icon"]
+
+[connection signal="resized" from="Icon" to="." method="_on_icon_resized"]
+"#
+    );
+    assert_eq!(
+        branch.boundary_references,
+        vec![BoundaryReference::Connection {
+            signal: "ready".to_string(),
+            from: ".".to_string(),
+            to: "UI/HealthBar".to_string(),
+            method: "_on_level_ready".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_node_paths_leaving_the_branch() {
+    let scene = LEVEL.replace(
+        "[node name=\"Label\" type=\"Label\" parent=\"UI/HealthBar/Icon\"]\n",
+        "[node name=\"Label\" type=\"Label\" parent=\"UI/HealthBar/Icon\"]\n\
+         logo = NodePath(\"../../../../Logo\")\n\
+         icon = NodePath(\"..\")\n",
+    );
+    let (_, mut level) = parse_scene_file(&scene).unwrap();
+    let branch = level
+        .extract_subtree("UI/HealthBar", "res://ui/health_bar.tscn")
+        .unwrap();
+
+    // The path stays as written, the one within the branch is not reported
+    let label = branch.scene.find_node("Icon/Label").unwrap();
+    assert_eq!(label.get("logo"), Some("NodePath(\"../../../../Logo\")"));
+    assert_eq!(
+        branch.boundary_references[0],
+        BoundaryReference::NodePath {
+            node: "UI/HealthBar/Icon/Label".to_string(),
+            property: "logo".to_string(),
+            path: "../../../../Logo".to_string(),
+            resolved: "Logo".to_string(),
+        }
+    );
+    assert_eq!(branch.boundary_references.len(), 2);
+}
+
+#[test]
+fn test_branch_is_replaced_with_an_instance() {
+    let (_, mut level) = parse_scene_file(LEVEL).unwrap();
+    level
+        .extract_subtree("UI/HealthBar", "res://ui/health_bar.tscn")
+        .unwrap();
+
+    // The health bar script and theme are gone, the shared icon stays
+    let ext: Vec<_> = level
+        .ext_resources()
+        .iter()
+        .map(|e| (e.get("id").unwrap(), e.get("path").unwrap()))
+        .collect();
+    assert_eq!(ext.len(), 3);
+    assert_eq!(ext[0], ("1_lvl01", "res://level.gd"));
+    assert_eq!(ext[1], ("2_icon0", "res://icon.svg"));
+    assert_eq!(ext[2].1, "res://ui/health_bar.tscn");
+    assert!(ext[2].0.starts_with("3_"));
+    assert!(level.sub_resources().is_empty());
+    assert_eq!(level.header().unwrap().get("load_steps"), Some("4"));
+
+    let instance = level.find_node("UI/HealthBar").unwrap();
+    assert_eq!(
+        instance.to_string(),
+        format!(
+            "[node name=\"HealthBar\" parent=\"UI\" instance=ExtResource(\"{}\")]\nunique_name_in_owner = true\n",
+            ext[2].0
+        )
+    );
+    assert!(level.find_node("UI/HealthBar/Icon").is_none());
+
+    // The connection from outside the branch still points into the instance
+    let connections = level.connections();
+    assert_eq!(connections.len(), 1);
+    assert_eq!(connections[0].get("to"), Some("UI/HealthBar"));
+
+    let written = level.to_string();
+    let (remaining, reparsed) = parse_scene_file(&written).unwrap();
+    assert!(remaining.trim().is_empty());
+    assert_eq!(reparsed, level);
+}
+
+#[test]
+fn test_extract_errors() {
+    let (_, mut level) = parse_scene_file(LEVEL).unwrap();
+    assert_eq!(
+        level
+            .extract_subtree(".", "res://level_copy.tscn")
+            .unwrap_err(),
+        SceneEditError::RootNode
+    );
+    assert_eq!(
+        level
+            .extract_subtree("UI/Missing", "res://missing.tscn")
+            .unwrap_err(),
+        SceneEditError::NodeNotFound("UI/Missing".to_string())
+    );
+}
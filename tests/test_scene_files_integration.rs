@@ -32,7 +32,7 @@ fn test_parse_simple_header_scene() {
 
     let (remaining, scene) = result.unwrap();
     assert_eq!(remaining, "", "Parser did not consume entire file");
-    assert!(scene.header.is_some(), "Scene should have a header");
+    assert!(scene.header().is_some(), "Scene should have a header");
     assert_eq!(scene.all_sections.len(), 1);
 }
 
@@ -66,11 +66,11 @@ fn test_shop_sidebar_structure() {
     );
 
     let (_, scene) = result.unwrap();
-    assert!(scene.header.is_some(), "Scene should have a header");
+    assert!(scene.header().is_some(), "Scene should have a header");
 
     // Shop sidebar should have external resources and nodes
     assert!(
-        !scene.ext_resources.is_empty() || !scene.nodes.is_empty(),
+        !scene.ext_resources().is_empty() || !scene.nodes().is_empty(),
         "Scene should have external resources or nodes"
     );
 }
@@ -105,7 +105,7 @@ fn test_table_selection_entry_structure() {
     );
 
     let (_, scene) = result.unwrap();
-    assert!(scene.header.is_some(), "Scene should have a header");
+    assert!(scene.header().is_some(), "Scene should have a header");
 }
 
 /// Test that play_room.tscn parses successfully (complex file)
@@ -143,29 +143,32 @@ fn test_play_room_structure() {
     let (_, scene) = result.unwrap();
 
     // Verify header
-    assert!(scene.header.is_some(), "Scene should have a header");
-    let header = scene.header.as_ref().unwrap();
+    assert!(scene.header().is_some(), "Scene should have a header");
+    let header = scene.header().unwrap();
     assert_eq!(header.header_type, "gd_scene");
 
     // Verify categorized sections
     assert!(
-        !scene.ext_resources.is_empty(),
+        !scene.ext_resources().is_empty(),
         "Should have external resources"
     );
-    assert!(!scene.sub_resources.is_empty(), "Should have sub-resources");
-    assert!(!scene.nodes.is_empty(), "Should have nodes");
-    assert!(!scene.connections.is_empty(), "Should have connections");
+    assert!(
+        !scene.sub_resources().is_empty(),
+        "Should have sub-resources"
+    );
+    assert!(!scene.nodes().is_empty(), "Should have nodes");
+    assert!(!scene.connections().is_empty(), "Should have connections");
 
     // Verify totals
     assert_eq!(scene.all_sections.len(), 99, "Expected 99 total sections");
     assert_eq!(
-        scene.ext_resources.len(),
+        scene.ext_resources().len(),
         31,
         "Expected 31 external resources"
     );
-    assert_eq!(scene.sub_resources.len(), 14, "Expected 14 sub-resources");
-    assert_eq!(scene.nodes.len(), 41, "Expected 41 nodes");
-    assert_eq!(scene.connections.len(), 12, "Expected 12 connections");
+    assert_eq!(scene.sub_resources().len(), 14, "Expected 14 sub-resources");
+    assert_eq!(scene.nodes().len(), 41, "Expected 41 nodes");
+    assert_eq!(scene.connections().len(), 12, "Expected 12 connections");
 }
 
 /// Test all scene files can be parsed without errors
@@ -233,7 +236,7 @@ fn test_nodes_have_name_property() {
     let content = include_str!("scenes/play_room.tscn");
     let (_, scene) = parse_scene_file(content).unwrap();
 
-    for node in &scene.nodes {
+    for node in &scene.nodes() {
         let has_name = node.properties.iter().any(|p| p.key == "name");
         assert!(has_name, "Node section should have 'name' property");
     }
@@ -245,7 +248,7 @@ fn test_ext_resources_have_type_property() {
     let content = include_str!("scenes/play_room.tscn");
     let (_, scene) = parse_scene_file(content).unwrap();
 
-    for ext_res in &scene.ext_resources {
+    for ext_res in &scene.ext_resources() {
         let has_type = ext_res.properties.iter().any(|p| p.key == "type");
         assert!(has_type, "External resource should have 'type' property");
     }
@@ -257,7 +260,7 @@ fn test_sub_resources_have_id_property() {
     let content = include_str!("scenes/play_room.tscn");
     let (_, scene) = parse_scene_file(content).unwrap();
 
-    for sub_res in &scene.sub_resources {
+    for sub_res in &scene.sub_resources() {
         let has_id = sub_res.properties.iter().any(|p| p.key == "id");
         assert!(has_id, "Sub-resource should have 'id' property");
     }
@@ -269,7 +272,7 @@ fn test_connections_have_signal_property() {
     let content = include_str!("scenes/play_room.tscn");
     let (_, scene) = parse_scene_file(content).unwrap();
 
-    for connection in &scene.connections {
+    for connection in &scene.connections() {
         let has_signal = connection.properties.iter().any(|p| p.key == "signal");
         assert!(has_signal, "Connection should have 'signal' property");
     }
@@ -0,0 +1,178 @@
+use godot_properties_parser::parse_scene_file;
+use godot_properties_parser::parsers::parser_scene_file::SceneFile;
+use godot_properties_parser::scene::SceneEditError;
+
+const LEVEL: &str = r#"[gd_scene load_steps=4 format=3]
+
+[ext_resource type="Script" path="res://level.gd" id="1_lvl01"]
+[ext_resource type="Texture2D" path="res://icon.svg" id="2_icon0"]
+[ext_resource type="PackedScene" path="res://enemy.tscn" id="3_enemy"]
+
+[node name="Level" type="Node2D"]
+script = ExtResource("1_lvl01")
+
+[node name="Boss" parent="." instance=ExtResource("3_enemy")]
+position = Vector2(100, 50)
+speed = 20.0
+
+[node name="Sprite" parent="Boss"]
+modulate = Color(1, 0, 0, 1)
+
+[node name="Crown" type="Sprite2D" parent="Boss/Sprite"]
+texture = ExtResource("2_icon0")
+
+[editable path="Boss"]
+
+[connection signal="died" from="Boss" to="." method="_on_boss_died"]
+"#;
+
+const ENEMY: &str = r#"[gd_scene load_steps=4 format=3]
+
+[ext_resource type="Script" path="res://enemy.gd" id="1_enemy"]
+[ext_resource type="Texture2D" path="res://icon.svg" id="2_icon1"]
+
+[sub_resource type="CircleShape2D" id="CircleShape2D_hit01"]
+radius = 8.0
+
+[node name="Enemy" type="CharacterBody2D" groups=["enemies"]]
+script = ExtResource("1_enemy")
+speed = 10.0
+health = 3
+
+[node name="Sprite" type="Sprite2D" parent="."]
+texture = ExtResource("2_icon1")
+
+[node name="Hitbox" type="CollisionShape2D" parent="."]
+shape = SubResource("CircleShape2D_hit01")
+
+[connection signal="body_entered" from="Hitbox" to="." method="_on_hit"]
+"#;
+
+fn inlined() -> SceneFile<'static> {
+    let (_, mut level) = parse_scene_file(LEVEL).unwrap();
+    let (_, enemy) = parse_scene_file(ENEMY).unwrap();
+    level.inline_instance("Boss", &enemy).unwrap();
+    level.into_owned()
+}
+
+#[test]
+fn test_instanced_nodes_are_copied() {
+    let level = inlined();
+
+    let boss = level.find_node("Boss").unwrap();
+    assert_eq!(
+        boss.to_string(),
+        "[node name=\"Boss\" type=\"CharacterBody2D\" parent=\".\" groups=[\"enemies\"]]\n\
+         script = ExtResource(\"4_0zd80\")\n\
+         speed = 20.0\n\
+         health = 3\n\
+         position = Vector2(100, 50)\n"
+    );
+
+    // The override section is merged into the copied node
+    let sprite = level.find_node("Boss/Sprite").unwrap();
+    assert_eq!(sprite.get("type"), Some("Sprite2D"));
+    assert_eq!(sprite.get("texture"), Some("ExtResource(\"2_icon0\")"));
+    assert_eq!(sprite.get("modulate"), Some("Color(1, 0, 0, 1)"));
+
+    let names: Vec<_> = level
+        .nodes()
+        .iter()
+        .map(|n| n.get("name").unwrap())
+        .collect();
+    assert_eq!(names, ["Level", "Boss", "Sprite", "Hitbox", "Crown"]);
+    assert!(level.find_node("Boss/Sprite/Crown").is_some());
+}
+
+#[test]
+fn test_resources_are_merged() {
+    let level = inlined();
+
+    // The shared icon is reused, the enemy scene itself is no longer needed
+    let ext: Vec<_> = level
+        .ext_resources()
+        .iter()
+        .map(|e| (e.get("id").unwrap(), e.get("path").unwrap()))
+        .collect();
+    assert_eq!(
+        ext,
+        [
+            ("1_lvl01", "res://level.gd"),
+            ("2_icon0", "res://icon.svg"),
+            ("4_0zd80", "res://enemy.gd")
+        ]
+    );
+
+    let subs = level.sub_resources();
+    assert_eq!(subs.len(), 1);
+    let sub_id = subs[0].get("id").unwrap();
+    assert!(sub_id.starts_with("CircleShape2D_"));
+    assert_ne!(sub_id, "CircleShape2D_hit01");
+    assert_eq!(
+        level.find_node("Boss/Hitbox").unwrap().get("shape"),
+        Some(format!("SubResource(\"{}\")", sub_id).as_str())
+    );
+    assert_eq!(level.header().unwrap().get("load_steps"), Some("5"));
+}
+
+#[test]
+fn test_connections_and_editables() {
+    let level = inlined();
+
+    let connections: Vec<_> = level
+        .connections()
+        .iter()
+        .map(|c| (c.get("from").unwrap(), c.get("to").unwrap()))
+        .collect();
+    assert_eq!(connections, [("Boss", "."), ("Boss/Hitbox", "Boss")]);
+    assert!(level.editables().is_empty());
+
+    let written = level.to_string();
+    let (_, reparsed) = parse_scene_file(&written).unwrap();
+    assert_eq!(reparsed, level);
+}
+
+#[test]
+fn test_inline_errors() {
+    let (_, mut level) = parse_scene_file(LEVEL).unwrap();
+    let (_, enemy) = parse_scene_file(ENEMY).unwrap();
+    assert_eq!(
+        level.inline_instance(".", &enemy).unwrap_err(),
+        SceneEditError::NotAnInstance(".".to_string())
+    );
+    assert_eq!(
+        level.inline_instance("Missing", &enemy).unwrap_err(),
+        SceneEditError::NodeNotFound("Missing".to_string())
+    );
+}
+
+#[test]
+fn test_unique_names_must_not_clash() {
+    let level = LEVEL.replace(
+        "[editable path=\"Boss\"]",
+        "[node name=\"Hitbox\" type=\"Area2D\" parent=\".\"]\nunique_name_in_owner = true\n\n[editable path=\"Boss\"]",
+    );
+    let enemy = ENEMY.replace(
+        "shape = SubResource(\"CircleShape2D_hit01\")",
+        "unique_name_in_owner = true\nshape = SubResource(\"CircleShape2D_hit01\")",
+    );
+    let (_, mut level) = parse_scene_file(&level).unwrap();
+    let (_, enemy) = parse_scene_file(&enemy).unwrap();
+    let before = level.clone();
+    assert_eq!(
+        level.inline_instance("Boss", &enemy).unwrap_err(),
+        SceneEditError::UniqueNameTaken {
+            name: "Hitbox".to_string(),
+            path: "Hitbox".to_string(),
+        }
+    );
+    assert_eq!(level, before);
+
+    // Not unique in the level, the inlined %Hitbox is the only one
+    let (_, mut level) = parse_scene_file(LEVEL).unwrap();
+    level.inline_instance("Boss", &enemy).unwrap();
+    assert_eq!(
+        level.unique_names().get("Hitbox").map(String::as_str),
+        Some("Boss/Hitbox")
+    );
+}
@@ -0,0 +1,77 @@
+use godot_properties_parser::parse_scene_file;
+use godot_properties_parser::scene::node_path_properties;
+
+const PLAY_ROOM_CONTENT: &str = include_str!("scenes/play_room.tscn");
+const SHOP_SIDEBAR_CONTENT: &str = include_str!("scenes/shop_sidebar.tscn");
+const TABLE_SELECTION_ENTRY_CONTENT: &str = include_str!("scenes/table_selection_entry.tscn");
+
+const RENAMED_SCENE: &str = r#"[gd_scene format=3]
+
+[node name="Level" type="Node2D"]
+
+[node name="Camera" type="Camera2D" parent="." node_paths=PackedStringArray("target", "hud", "unset")]
+target = NodePath("../Player")
+hud = NodePath("../HUD")
+unset = NodePath("")
+
+[node name="Hero" type="CharacterBody2D" parent="."]
+
+[node name="HUD" type="CanvasLayer" parent="."]
+"#;
+
+#[test]
+fn test_node_path_properties() {
+    let (_, scene) = parse_scene_file(PLAY_ROOM_CONTENT).unwrap();
+    let camera = scene.find_node("Camera2D").unwrap();
+
+    assert_eq!(node_path_properties(camera), vec!["room", "ui"]);
+}
+
+#[test]
+fn test_play_room_node_paths_resolve() {
+    let (_, scene) = parse_scene_file(PLAY_ROOM_CONTENT).unwrap();
+    let exported = scene.exported_node_paths();
+
+    assert_eq!(exported.len(), 4);
+    assert_eq!(exported[0].node, "Camera2D");
+    assert_eq!(exported[0].property, "room");
+    assert_eq!(exported[0].resolved.as_deref(), Some("."));
+    assert_eq!(exported[1].resolved.as_deref(), Some("UI"));
+    assert!(scene.broken_node_paths().is_empty());
+}
+
+#[test]
+fn test_array_of_node_paths() {
+    let (_, scene) = parse_scene_file(SHOP_SIDEBAR_CONTENT).unwrap();
+    let exported = scene.exported_node_paths();
+
+    assert!(exported.iter().any(|e| e.property == "buttons"));
+    assert!(exported.iter().any(|e| e.property == "routes"));
+    assert_eq!(
+        exported[0].resolved.as_deref(),
+        Some("VBoxContainer/NavigationButtonContainer/SidebarNavigationButtons/ItemsButton")
+    );
+    assert!(scene.broken_node_paths().is_empty());
+}
+
+#[test]
+fn test_node_paths_on_instanced_node() {
+    let (_, scene) = parse_scene_file(TABLE_SELECTION_ENTRY_CONTENT).unwrap();
+    let exported = scene.exported_node_paths();
+
+    assert_eq!(exported.len(), 1);
+    assert_eq!(exported[0].node, "SelectButton/JuicyButtonEffectOnParent");
+    assert_eq!(exported[0].resolved.as_deref(), Some("."));
+}
+
+#[test]
+fn test_broken_node_path_after_rename() {
+    let (_, scene) = parse_scene_file(RENAMED_SCENE).unwrap();
+    let broken = scene.broken_node_paths();
+
+    assert_eq!(broken.len(), 1);
+    assert_eq!(broken[0].node, "Camera");
+    assert_eq!(broken[0].property, "target");
+    assert_eq!(broken[0].path, "../Player");
+    assert_eq!(broken[0].resolved, None);
+}
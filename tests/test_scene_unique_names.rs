@@ -0,0 +1,147 @@
+use godot_properties_parser::parse_scene_file;
+use godot_properties_parser::scene::DuplicateUniqueName;
+
+const PLAY_ROOM_CONTENT: &str = include_str!("scenes/play_room.tscn");
+
+const MENU_SCENE: &str = r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="PackedScene" path="res://ui/dialog.tscn" id="1_dialog"]
+
+[node name="Menu" type="Control"]
+
+[node name="Panel" type="Panel" parent="."]
+unique_name_in_owner = true
+
+[node name="Title" type="Label" parent="Panel"]
+
+[node name="Buttons" type="VBoxContainer" parent="Panel"]
+
+[node name="Start" type="Button" parent="Panel/Buttons"]
+unique_name_in_owner = true
+
+[node name="Start" type="Button" parent="."]
+unique_name_in_owner = true
+
+[node name="Dialog" parent="." instance=ExtResource("1_dialog")]
+
+[node name="Confirm" parent="Dialog/Buttons"]
+unique_name_in_owner = true
+text = "OK"
+"#;
+
+#[test]
+fn test_unique_names_of_root_owner() {
+    let (_, scene) = parse_scene_file(MENU_SCENE).unwrap();
+    let names = scene.unique_names();
+
+    assert_eq!(names.len(), 2);
+    assert_eq!(names["Panel"], "Panel");
+    assert_eq!(names["Start"], "Panel/Buttons/Start");
+    assert!(!names.contains_key("Confirm"));
+}
+
+#[test]
+fn test_duplicate_unique_names() {
+    let (_, scene) = parse_scene_file(MENU_SCENE).unwrap();
+
+    assert_eq!(
+        scene.duplicate_unique_names(),
+        vec![DuplicateUniqueName {
+            owner: ".".to_string(),
+            name: "Start".to_string(),
+            paths: vec!["Panel/Buttons/Start".to_string(), "Start".to_string()],
+        }]
+    );
+}
+
+#[test]
+fn test_resolve_unique_paths() {
+    let (_, scene) = parse_scene_file(MENU_SCENE).unwrap();
+
+    assert_eq!(
+        scene.resolve_node_path(".", "%Panel/Title"),
+        Some("Panel/Title".to_string())
+    );
+    assert_eq!(
+        scene.resolve_node_path("Panel/Title", "%Panel/Buttons"),
+        Some("Panel/Buttons".to_string())
+    );
+    assert_eq!(scene.resolve_node_path(".", "%Panel/Missing"), None);
+    assert_eq!(scene.resolve_node_path(".", "%Missing"), None);
+}
+
+#[test]
+fn test_resolve_unique_path_inside_instance() {
+    let (_, scene) = parse_scene_file(MENU_SCENE).unwrap();
+
+    assert_eq!(
+        scene.resolve_node_path("Dialog", "%Confirm"),
+        Some("Dialog/Buttons/Confirm".to_string())
+    );
+    assert_eq!(
+        scene.resolve_node_path(".", "Dialog/Anything"),
+        Some("Dialog/Anything".to_string())
+    );
+
+    // Names the instance does not own are looked up among its owner's
+    assert_eq!(
+        scene.resolve_node_path("Dialog", "%Panel/Title"),
+        Some("Panel/Title".to_string())
+    );
+    assert_eq!(
+        scene.resolve_node_path("Dialog/Buttons/Confirm", "%Confirm"),
+        Some("Dialog/Buttons/Confirm".to_string())
+    );
+    assert_eq!(scene.resolve_node_path(".", "%Confirm"), None);
+}
+
+#[test]
+fn test_resolver_matches_resolve_node_path() {
+    let (_, scene) = parse_scene_file(MENU_SCENE).unwrap();
+    let resolver = scene.node_path_resolver();
+
+    for (from, path) in [
+        (".", "%Panel/Title"),
+        ("Panel/Title", "../Buttons/Start"),
+        ("Dialog", "%Confirm"),
+        (".", "Missing"),
+    ] {
+        assert_eq!(
+            resolver.resolve(from, path),
+            scene.resolve_node_path(from, path),
+            "{}",
+            path
+        );
+    }
+}
+
+#[test]
+fn test_resolve_relative_paths() {
+    let (_, scene) = parse_scene_file(MENU_SCENE).unwrap();
+
+    assert_eq!(
+        scene.resolve_node_path("Panel/Title", "../Buttons/Start"),
+        Some("Panel/Buttons/Start".to_string())
+    );
+    assert_eq!(
+        scene.resolve_node_path("Panel", ".."),
+        Some(".".to_string())
+    );
+    assert_eq!(scene.resolve_node_path(".", ".."), None);
+    assert_eq!(scene.resolve_node_path(".", "/root/Menu"), None);
+}
+
+#[test]
+fn test_play_room_unique_names() {
+    let (_, scene) = parse_scene_file(PLAY_ROOM_CONTENT).unwrap();
+    let names = scene.unique_names();
+
+    assert_eq!(names.len(), 14);
+    assert_eq!(names["TableCloth"], "Table/TableCloth");
+    assert_eq!(names["CapsuleButton"], "UI/ButtonGroup2/CapsuleButton");
+    assert_eq!(
+        names["UpperSidebar"],
+        "UI/VBoxContainer/VSplitContainer/UpperSidebar"
+    );
+    assert!(scene.duplicate_unique_names().is_empty());
+}
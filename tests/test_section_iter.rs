@@ -0,0 +1,124 @@
+use godot_properties_parser::parsers::parser_property_file::Section;
+use godot_properties_parser::{ParseErrorKind, SectionIter, SectionReader, parse_property_file};
+use std::io::{BufReader, Cursor};
+
+const PLAY_ROOM_CONTENT: &str = include_str!("scenes/play_room.tscn");
+const PROJECT_CONTENT: &str = include_str!("projects/project_gamblers_table.godot");
+
+const MULTILINE_SCENE: &str = r#"[gd_resource type="Resource" format=3]
+
+[resource]
+data = {
+"rows": [
+[1, 2],
+[3, 4]
+],
+"text": "not a
+[header]"
+}
+name = "Table"
+"#;
+
+fn headers(sections: &[Section]) -> Vec<String> {
+    sections.iter().map(|s| s.header_type.to_string()).collect()
+}
+
+#[test]
+fn test_iter_matches_parse_property_file() {
+    for content in [PLAY_ROOM_CONTENT, PROJECT_CONTENT, MULTILINE_SCENE] {
+        let (_, file) = parse_property_file(content).unwrap();
+        let iter = SectionIter::new(content);
+
+        assert_eq!(
+            iter.preamble_properties(),
+            file.preamble_properties.as_slice()
+        );
+        assert_eq!(iter.collect::<Vec<_>>(), file.sections);
+    }
+}
+
+#[test]
+fn test_skip_bodies_keeps_header_attributes() {
+    let (_, file) = parse_property_file(PLAY_ROOM_CONTENT).unwrap();
+    let sections: Vec<Section> = SectionIter::new(PLAY_ROOM_CONTENT).skip_bodies().collect();
+
+    assert_eq!(headers(&sections), headers(&file.sections));
+
+    let node = sections.iter().find(|s| s.header_type == "node").unwrap();
+    assert!(node.get("name").is_some());
+    assert!(node.get("unique_name_in_owner").is_none());
+}
+
+#[test]
+fn test_skip_bodies_ignores_brackets_inside_values() {
+    let sections: Vec<Section> = SectionIter::new(MULTILINE_SCENE).skip_bodies().collect();
+
+    assert_eq!(headers(&sections), vec!["gd_resource", "resource"]);
+    assert!(sections[1].properties.is_empty());
+}
+
+#[test]
+fn test_stop_early() {
+    let input = "[gd_scene format=3]\n\n[ext_resource id=\"1\"]\n\n[node name=\"A\"]\n\n[broken\n";
+    let mut iter = SectionIter::new(input);
+
+    let first_two: Vec<Section> = iter.by_ref().take(2).collect();
+    assert_eq!(headers(&first_two), vec!["gd_scene", "ext_resource"]);
+    assert_eq!(iter.remaining(), "");
+    assert_eq!(iter.error(), None);
+
+    assert_eq!(iter.next().unwrap().header_type, "node");
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.remaining(), "[broken\n");
+    assert_eq!(iter.error().unwrap().kind, ParseErrorKind::UnclosedHeader);
+}
+
+#[test]
+fn test_reader_matches_iter() {
+    let mut reader = SectionReader::new(BufReader::new(Cursor::new(PROJECT_CONTENT)));
+    let expected = SectionIter::new(PROJECT_CONTENT);
+
+    assert_eq!(
+        reader.preamble_properties().unwrap(),
+        expected.preamble_properties()
+    );
+    let sections: Vec<Section> = reader.map(Result::unwrap).collect();
+    assert_eq!(sections, expected.collect::<Vec<_>>());
+}
+
+#[test]
+fn test_reader_skip_bodies() {
+    let reader = SectionReader::new(Cursor::new(MULTILINE_SCENE)).skip_bodies();
+    let sections: Vec<Section> = reader.map(Result::unwrap).collect();
+
+    assert_eq!(headers(&sections), vec!["gd_resource", "resource"]);
+    assert_eq!(sections[0].get("type"), Some("Resource"));
+    assert!(sections[1].properties.is_empty());
+}
+
+#[test]
+fn test_reader_reports_malformed_header() {
+    let mut reader = SectionReader::new(Cursor::new("[node name=\"A\"]\n\n[broken\n"));
+
+    assert_eq!(reader.next().unwrap().unwrap().get("name"), Some("A"));
+    let error = reader.next().unwrap().unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(
+        error.to_string(),
+        "3:8: section header is not closed with `]`"
+    );
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn test_skip_bodies_stops_at_unterminated_string() {
+    let input = "[node name=\"A\"]\ntext = \"never closed\n\n[node]\n";
+    let mut iter = SectionIter::new(input).skip_bodies();
+
+    assert_eq!(iter.next().unwrap().get("name"), Some("A"));
+    assert_eq!(iter.next(), None);
+    let error = iter.error().unwrap();
+    assert_eq!(error.kind, ParseErrorKind::UnterminatedString);
+    assert_eq!((error.location.line, error.location.column), (2, 8));
+    assert!(iter.remaining().starts_with("\"never closed"));
+}
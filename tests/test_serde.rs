@@ -0,0 +1,198 @@
+#![cfg(feature = "serde")]
+
+use godot_properties_parser::parsers::de::{from_project_file, from_section, from_str};
+use godot_properties_parser::parsers::parser_property_file::PropertyFile;
+use godot_properties_parser::{parse_project_file, parse_property_file, parse_scene_file};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+const PLAY_ROOM_CONTENT: &str = include_str!("scenes/play_room.tscn");
+const PROJECT_CONTENT: &str = include_str!("projects/project_gamblers_table.godot");
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Window {
+    stretch: Stretch,
+    #[serde(rename = "vsync/vsync_mode")]
+    vsync_mode: i32,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Stretch {
+    mode: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Display {
+    window: Window,
+}
+
+#[test]
+fn test_section_into_nested_struct() {
+    let (_, project) = parse_project_file(PROJECT_CONTENT).unwrap();
+    let display: Display = from_section(project.section("display").unwrap()).unwrap();
+
+    assert_eq!(
+        display.window,
+        Window {
+            stretch: Stretch {
+                mode: "canvas_items".to_string()
+            },
+            vsync_mode: 0,
+        }
+    );
+}
+
+#[test]
+fn test_typed_values() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Vector2 {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Resource {
+        ExtResource(String),
+        SubResource(String),
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Node<'a> {
+        name: &'a str,
+        position: Vector2,
+        size: (f64, f64),
+        visible: bool,
+        texture: Resource,
+        groups: Vec<String>,
+        tint: Option<String>,
+        tooltip: String,
+    }
+
+    let (_, file) = parse_property_file(
+        "[node name=\"Icon\" type=\"Sprite2D\"]\nposition = Vector2(12, -4.5)\nsize = Vector2(3, 4)\n\
+         visible = false\ntexture = ExtResource(\"1_abc\")\ngroups = [\"ui\", &\"room\"]\n\
+         tint = null\ntooltip = \"Say \\\"hi\\\"\"\n",
+    )
+    .unwrap();
+    let node: Node = from_section(&file.sections[0]).unwrap();
+
+    assert_eq!(node.name, "Icon");
+    assert_eq!(node.position, Vector2 { x: 12.0, y: -4.5 });
+    assert_eq!(node.size, (3.0, 4.0));
+    assert!(!node.visible);
+    assert_eq!(node.texture, Resource::ExtResource("1_abc".to_string()));
+    assert_eq!(node.groups, vec!["ui", "room"]);
+    assert_eq!(node.tint, None);
+    assert_eq!(node.tooltip, "Say \"hi\"");
+}
+
+#[test]
+fn test_quoted_values_are_strings() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Preset {
+        name: Option<String>,
+        version: String,
+        runnable: String,
+        export_path: String,
+        custom: Option<String>,
+    }
+
+    let (_, file) = parse_property_file(
+        "[preset.0]\nname=\"null\"\nversion=\"42\"\nrunnable=\"true\"\n\
+         export_path=\"C:\\\\build\"\ncustom=null\n",
+    )
+    .unwrap();
+    let preset: Preset = from_section(&file.sections[0]).unwrap();
+    assert_eq!(
+        preset,
+        Preset {
+            name: Some("null".to_string()),
+            version: "42".to_string(),
+            runnable: "true".to_string(),
+            export_path: "C:\\build".to_string(),
+            custom: None,
+        }
+    );
+
+    let (_, file) = parse_property_file("[preset.1]\nversion=\"42\"\n").unwrap();
+    let error = from_section::<BTreeMap<String, i32>>(&file.sections[0]).unwrap_err();
+    assert_eq!(error.key.as_deref(), Some("version"));
+}
+
+#[test]
+fn test_project_file_into_struct() {
+    #[derive(Debug, Deserialize)]
+    struct Application {
+        #[serde(rename = "config/name")]
+        name: String,
+        #[serde(rename = "config/features")]
+        features: Vec<String>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Project {
+        config_version: u32,
+        application: Application,
+        autoload: BTreeMap<String, String>,
+    }
+
+    let (_, project) = parse_project_file(PROJECT_CONTENT).unwrap();
+    let project: Project = from_project_file(&project).unwrap();
+
+    assert_eq!(project.config_version, 5);
+    assert_eq!(project.application.name, "Gamblers Table");
+    assert_eq!(
+        project.application.features,
+        vec!["4.4", "GL Compatibility"]
+    );
+    assert_eq!(
+        project.autoload["EventBus"],
+        "*res://autoload/event_bus/event_bus.tscn"
+    );
+}
+
+#[test]
+fn test_errors_name_the_key() {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Display {
+        window: Window,
+    }
+
+    let (_, file) =
+        parse_property_file("[display]\nwindow/stretch/mode=3\nwindow/vsync/vsync_mode=\"on\"\n")
+            .unwrap();
+    let error = from_section::<Display>(&file.sections[0]).unwrap_err();
+
+    assert_eq!(error.key.as_deref(), Some("window/vsync/vsync_mode"));
+    assert!(
+        error
+            .to_string()
+            .starts_with("window/vsync/vsync_mode: invalid type")
+    );
+}
+
+#[test]
+fn test_from_str() {
+    let color: [f32; 4] = from_str("Color(1, 0.5, 0, 1)").unwrap();
+    assert_eq!(color, [1.0, 0.5, 0.0, 1.0]);
+
+    let map: BTreeMap<String, i64> = from_str("{ \"a\": 1, \"b\": 2 }").unwrap();
+    assert_eq!(map["b"], 2);
+
+    assert!(from_str::<i32>("Vector2(").is_err());
+}
+
+#[test]
+fn test_parsed_files_round_trip_through_serde() {
+    let (_, file) = parse_property_file(PLAY_ROOM_CONTENT).unwrap();
+    let json = serde_json::to_string(&file).unwrap();
+    let restored: PropertyFile = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(restored, file);
+    assert_eq!(restored.sections[1].location, file.sections[1].location);
+
+    let (_, scene) = parse_scene_file(PLAY_ROOM_CONTENT).unwrap();
+    let value = serde_json::to_value(&scene).unwrap();
+    assert_eq!(value["all_sections"][0]["header_type"], "gd_scene");
+}
@@ -0,0 +1,162 @@
+use godot_properties_parser::parsers::lexer::Span;
+use godot_properties_parser::parsers::parser_property_file::Section;
+use godot_properties_parser::parsers::source_map::{Location, Position, SourceMap};
+use godot_properties_parser::{
+    SectionIter, SectionReader, parse_property_file, parse_property_file_bytes,
+};
+use std::io::Cursor;
+
+const PLAY_ROOM_CONTENT: &str = include_str!("scenes/play_room.tscn");
+
+const SCENE: &str = r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="Script" path="res://main.gd" id="1"]
+
+; The root node
+[node name="Main" type="Node2D"]
+script = ExtResource("1")
+data = {
+"key": [1, 2]
+}
+title = "Ünïcode"
+"#;
+
+fn locations(section: &Section) -> Vec<(Location, Location)> {
+    section
+        .properties
+        .iter()
+        .map(|p| (p.key_location, p.value_location))
+        .collect()
+}
+
+#[test]
+fn test_section_locations() {
+    let (_, file) = parse_property_file(SCENE).unwrap();
+    let sections = &file.sections;
+
+    assert_eq!(sections[0].location.line, 1);
+    assert_eq!(
+        sections[0].location.span.slice(SCENE),
+        "[gd_scene load_steps=2 format=3]"
+    );
+    assert_eq!(sections[1].location.line, 3);
+    assert_eq!(sections[2].location.line, 6);
+    assert_eq!(sections[2].location.column, 1);
+
+    // A section with a body ends at its last value
+    let node = sections[2].location.span.slice(SCENE);
+    assert!(node.starts_with("[node name=\"Main\""));
+    assert!(node.ends_with("title = \"Ünïcode\""));
+}
+
+#[test]
+fn test_attribute_locations() {
+    let (_, file) = parse_property_file(SCENE).unwrap();
+    let ext_resource = &file.sections[1];
+
+    let path = ext_resource.property("path").unwrap();
+    assert_eq!(path.key_location.position(), Position::new(3, 29));
+    assert_eq!(path.key_location.span.slice(SCENE), "path");
+    assert_eq!(path.value_location.position(), Position::new(3, 34));
+    assert_eq!(path.value_location.span.slice(SCENE), "\"res://main.gd\"");
+    assert_eq!(path.location().span.slice(SCENE), "path=\"res://main.gd\"");
+}
+
+#[test]
+fn test_body_property_locations() {
+    let (_, file) = parse_property_file(SCENE).unwrap();
+    let node = &file.sections[2];
+
+    let script = node.property("script").unwrap();
+    assert_eq!(script.key_location.position(), Position::new(7, 1));
+    assert_eq!(script.value_location.position(), Position::new(7, 10));
+    assert_eq!(
+        script.value_location.span.slice(SCENE),
+        "ExtResource(\"1\")"
+    );
+
+    let data = node.property("data").unwrap();
+    assert_eq!(data.value_location.line, 8);
+    assert_eq!(
+        data.value_location.span.slice(SCENE),
+        "{\n\"key\": [1, 2]\n}"
+    );
+
+    // Lines are counted through multi-line values and columns in characters
+    let title = node.property("title").unwrap();
+    assert_eq!(title.key_location.position(), Position::new(11, 1));
+    assert_eq!(title.value_location.position(), Position::new(11, 9));
+}
+
+#[test]
+fn test_equality_ignores_locations() {
+    let (_, file) = parse_property_file("[a]\nx = 1\n").unwrap();
+    let (_, moved) = parse_property_file("\n\n[a]\n\n   x = 1\n").unwrap();
+
+    assert_eq!(file, moved);
+    assert_ne!(
+        file.sections[0].properties[0].key_location,
+        moved.sections[0].properties[0].key_location
+    );
+}
+
+#[test]
+fn test_reader_and_skipped_bodies_keep_locations() {
+    let expected: Vec<_> = SectionIter::new(PLAY_ROOM_CONTENT).collect();
+
+    let reader = SectionReader::new(Cursor::new(PLAY_ROOM_CONTENT));
+    for (section, expected) in reader.zip(&expected) {
+        let section = section.unwrap();
+        assert_eq!(section.location, expected.location);
+        assert_eq!(locations(&section), locations(expected));
+    }
+
+    for (section, expected) in SectionIter::new(PLAY_ROOM_CONTENT)
+        .skip_bodies()
+        .zip(&expected)
+    {
+        assert_eq!(section.location.position(), expected.location.position());
+        assert_eq!(section.location.span.start, expected.location.span.start);
+    }
+}
+
+#[test]
+fn test_windows_line_endings_keep_lines() {
+    let bytes = SCENE.replace('\n', "\r\n").into_bytes();
+    let parsed = parse_property_file_bytes(&bytes);
+    let (_, expected) = parse_property_file(SCENE).unwrap();
+
+    for (section, expected) in parsed.value.sections.iter().zip(&expected.sections) {
+        assert_eq!(section.location, expected.location);
+        assert_eq!(locations(section), locations(expected));
+    }
+}
+
+#[test]
+fn test_source_map_positions() {
+    let map = SourceMap::new(SCENE);
+
+    assert_eq!(map.line_count(), 12);
+    assert_eq!(map.line(6), Some("[node name=\"Main\" type=\"Node2D\"]"));
+    assert_eq!(map.line(13), None);
+
+    let (_, file) = parse_property_file(SCENE).unwrap();
+    let title = file.sections[2].property("title").unwrap();
+    let span = title.value_location.span;
+
+    assert_eq!(map.locate(span), title.value_location);
+    assert_eq!(
+        map.range(span),
+        (Position::new(11, 9), Position::new(11, 18))
+    );
+    assert_eq!(map.offset(Position::new(11, 9)), Some(span.start));
+    assert_eq!(map.offset(Position::new(11, 18)), Some(span.end));
+    assert_eq!(map.offset(Position::new(11, 19)), None);
+    assert_eq!(map.position(SCENE.len()), Position::new(12, 1));
+    assert_eq!(map.line_span(2), Some(Span::new(33, 33)));
+
+    // Offsets inside a character are rounded down to its start
+    let map = SourceMap::new("name=\"Ünïcode\"");
+    assert_eq!(map.position(7), Position::new(1, 7));
+    assert_eq!(map.position(8), Position::new(1, 8));
+}